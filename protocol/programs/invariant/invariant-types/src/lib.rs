@@ -3,12 +3,14 @@ pub mod errors;
 pub mod log;
 pub mod macros;
 pub mod math;
+pub mod simulate;
 pub mod structs;
 pub mod utils;
 
 use anchor_lang::prelude::*;
 
-declare_id!("D5SXuX6TdmefcWr8vg5RV8cywCWRLQeLXq51riw1F44X");
+// same as the invariant program, addresses are derived with it
+declare_id!("CsT21LCRqBfh4SCcNZXtWjRZ6xvYKvdpEBaytCVmWnVJ");
 pub const SEED: &str = "Invariant";
 pub const STATE_SEED: &str = "statev1";
 pub const TICK_SEED: &str = "tickv1";
@@ -1781,3 +1781,212 @@ mod tests {
         }
     }
 }
+
+// compares the mirrored math with the program implementation
+#[cfg(test)]
+mod differential_tests {
+    use std::panic::catch_unwind;
+
+    use decimal::{Decimal, Factories};
+    use invariant::decimals as program;
+    use invariant::math as program_math;
+
+    use crate::{
+        decimals::{FixedPoint, Liquidity, TokenAmount},
+        structs::MAX_TICK,
+    };
+
+    use super::{
+        calculate_price_sqrt, compute_swap_step, get_delta_x, get_delta_y,
+        is_enough_amount_to_push_price,
+    };
+
+    const TICKS: [i32; 9] = [
+        -MAX_TICK, -100_000, -1_000, -1, 0, 1, 1_000, 100_000, MAX_TICK,
+    ];
+
+    fn liquidities() -> [Liquidity; 4] {
+        [
+            Liquidity::new(0),
+            Liquidity::from_integer(1),
+            Liquidity::from_integer(1_000_000),
+            Liquidity::from_integer(u64::MAX),
+        ]
+    }
+
+    fn amounts() -> [TokenAmount; 4] {
+        [
+            TokenAmount(1),
+            TokenAmount(1_000),
+            TokenAmount(1_000_000_000),
+            TokenAmount(u64::MAX / 2),
+        ]
+    }
+
+    fn fees() -> [FixedPoint; 3] {
+        [
+            FixedPoint::new(0),
+            FixedPoint::from_scale(3, 3),
+            FixedPoint::from_scale(1, 1),
+        ]
+    }
+
+    #[test]
+    fn test_calculate_price_sqrt_matches_program() {
+        let mut tick = -MAX_TICK;
+        while tick <= MAX_TICK {
+            assert_eq!(
+                calculate_price_sqrt(tick).v,
+                program_math::calculate_price_sqrt(tick).v,
+                "tick {}",
+                tick
+            );
+            tick += 997;
+        }
+        assert_eq!(
+            calculate_price_sqrt(MAX_TICK).v,
+            program_math::calculate_price_sqrt(MAX_TICK).v
+        );
+    }
+
+    #[test]
+    fn test_get_delta_matches_program() {
+        for a in TICKS {
+            for b in TICKS {
+                for liquidity in liquidities() {
+                    for up in [true, false] {
+                        let price_a = calculate_price_sqrt(a);
+                        let price_b = calculate_price_sqrt(b);
+                        let program_price_a = program::Price::new(price_a.v);
+                        let program_price_b = program::Price::new(price_b.v);
+                        let program_liquidity = program::Liquidity::new(liquidity.v);
+
+                        assert_eq!(
+                            get_delta_x(price_a, price_b, liquidity, up).map(|x| x.0),
+                            program_math::get_delta_x(
+                                program_price_a,
+                                program_price_b,
+                                program_liquidity,
+                                up
+                            )
+                            .map(|x| x.0)
+                        );
+                        assert_eq!(
+                            get_delta_y(price_a, price_b, liquidity, up).map(|y| y.0),
+                            program_math::get_delta_y(
+                                program_price_a,
+                                program_price_b,
+                                program_liquidity,
+                                up
+                            )
+                            .map(|y| y.0)
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_compute_swap_step_matches_program() {
+        for current in TICKS {
+            for target in TICKS {
+                for liquidity in liquidities() {
+                    for amount in amounts() {
+                        for fee in fees() {
+                            for by_amount_in in [true, false] {
+                                let current_price = calculate_price_sqrt(current);
+                                let target_price = calculate_price_sqrt(target);
+
+                                let result = catch_unwind(|| {
+                                    compute_swap_step(
+                                        current_price,
+                                        target_price,
+                                        liquidity,
+                                        amount,
+                                        by_amount_in,
+                                        fee,
+                                    )
+                                });
+                                let program_result = catch_unwind(|| {
+                                    program_math::compute_swap_step(
+                                        program::Price::new(current_price.v),
+                                        program::Price::new(target_price.v),
+                                        program::Liquidity::new(liquidity.v),
+                                        program::TokenAmount(amount.0),
+                                        by_amount_in,
+                                        program::FixedPoint::new(fee.v),
+                                    )
+                                });
+                                // both sides have to fail on the same input, either by panic or error
+                                let (result, program_result) = match (result, program_result) {
+                                    (Ok(Ok(result)), Ok(program_result)) => {
+                                        (result, program_result)
+                                    }
+                                    (Err(_) | Ok(Err(_)), Err(_)) => continue,
+                                    _ => panic!(
+                                        "results diverged for {} {} {:?} {:?} {} {:?}",
+                                        current, target, liquidity, amount, by_amount_in, fee
+                                    ),
+                                };
+
+                                assert_eq!(
+                                    result.next_price_sqrt.v,
+                                    program_result.next_price_sqrt.v
+                                );
+                                assert_eq!(result.amount_in.0, program_result.amount_in.0);
+                                assert_eq!(result.amount_out.0, program_result.amount_out.0);
+                                assert_eq!(result.fee_amount.0, program_result.fee_amount.0);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_enough_amount_to_push_price_matches_program() {
+        for current in TICKS {
+            for liquidity in liquidities() {
+                for amount in amounts() {
+                    for fee in fees() {
+                        for by_amount_in in [true, false] {
+                            for x_to_y in [true, false] {
+                                let price = calculate_price_sqrt(current);
+
+                                let result = is_enough_amount_to_push_price(
+                                    amount,
+                                    price,
+                                    liquidity,
+                                    fee,
+                                    by_amount_in,
+                                    x_to_y,
+                                );
+                                let program_result = catch_unwind(|| {
+                                    program_math::is_enough_amount_to_push_price(
+                                        program::TokenAmount(amount.0),
+                                        program::Price::new(price.v),
+                                        program::Liquidity::new(liquidity.v),
+                                        program::FixedPoint::new(fee.v),
+                                        by_amount_in,
+                                        x_to_y,
+                                    )
+                                });
+
+                                // the program panics where the mirror returns an error
+                                match (result, program_result) {
+                                    (Ok(result), Ok(program_result)) => {
+                                        assert_eq!(result, program_result)
+                                    }
+                                    (Err(_), Err(_)) => {}
+                                    _ => panic!("results diverged"),
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use std::cell::RefCell;
+
+use anchor_lang::prelude::Pubkey;
+
+use crate::{
+    decimals::*,
+    errors::InvariantErrorCode,
+    log::get_tick_at_sqrt_price,
+    math::{compute_swap_step, cross_tick, get_closer_limit, is_enough_amount_to_push_price},
+    structs::{Pool, Tick, Tickmap},
+    utils::{get_pool_address, get_tick_address, TrackableError, TrackableResult},
+    MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+};
+use crate::{err, function, location, ok_or_mark_trace, trace};
+
+#[derive(PartialEq, Debug)]
+pub struct SimulateSwapResult {
+    pub amount_in: TokenAmount, // fee included
+    pub amount_out: TokenAmount,
+    pub fee: TokenAmount,
+    pub pool: Pool,                  // pool state after the swap
+    pub crossed_ticks: Vec<Tick>,    // tick states after crossing
+    pub required_ticks: Vec<Pubkey>, // tick accounts to pass as remaining accounts
+}
+
+// Mirrors the swap loop of the program, ticks have to contain every initialized tick the swap reaches
+pub fn simulate_swap(
+    pool: &Pool,
+    tickmap: &Tickmap,
+    ticks: &[Tick],
+    x_to_y: bool,
+    amount: TokenAmount,
+    by_amount_in: bool,
+    sqrt_price_limit: Price,
+) -> TrackableResult<SimulateSwapResult> {
+    if amount.is_zero() {
+        return Err(err!(&InvariantErrorCode::ZeroAmount.to_string()));
+    }
+
+    let mut pool = *pool;
+    let pool_address = get_pool_address(pool.token_x, pool.token_y, pool.fee.v, pool.tick_spacing);
+
+    // limit is on the right side of price
+    let limit_on_right_side = if x_to_y {
+        ({ pool.sqrt_price }) > sqrt_price_limit && sqrt_price_limit <= Price::new(MAX_SQRT_PRICE)
+    } else {
+        ({ pool.sqrt_price }) < sqrt_price_limit && sqrt_price_limit >= Price::new(MIN_SQRT_PRICE)
+    };
+    if !limit_on_right_side {
+        return Err(err!(&InvariantErrorCode::WrongLimit.to_string()));
+    }
+
+    let mut remaining_amount = amount;
+
+    let mut total_amount_in = TokenAmount(0);
+    let mut total_amount_out = TokenAmount(0);
+    let mut total_fee = TokenAmount(0);
+    let mut crossed_ticks: Vec<Tick> = Vec::new();
+    let mut required_ticks: Vec<Pubkey> = Vec::new();
+
+    while !remaining_amount.is_zero() {
+        let (swap_limit, limiting_tick) = get_closer_limit(
+            sqrt_price_limit,
+            x_to_y,
+            pool.current_tick_index,
+            pool.tick_spacing,
+            tickmap,
+        )
+        .map_err(|e| err!(&e.to_string()))?;
+
+        let result = ok_or_mark_trace!(compute_swap_step(
+            pool.sqrt_price,
+            swap_limit,
+            pool.liquidity,
+            remaining_amount,
+            by_amount_in,
+            pool.fee,
+        ))?;
+        // make remaining amount smaller
+        if by_amount_in {
+            remaining_amount -= result.amount_in + result.fee_amount;
+        } else {
+            remaining_amount -= result.amount_out;
+        }
+
+        pool.add_fee(result.fee_amount, FixedPoint::from_integer(0), x_to_y);
+
+        pool.sqrt_price = result.next_price_sqrt;
+
+        total_amount_in += result.amount_in + result.fee_amount;
+        total_amount_out += result.amount_out;
+        total_fee += result.fee_amount;
+
+        // Fail if price would go over swap limit
+        if { pool.sqrt_price } == sqrt_price_limit && !remaining_amount.is_zero() {
+            return Err(err!(&InvariantErrorCode::PriceLimitReached.to_string()));
+        }
+
+        // crossing tick
+        // trunk-ignore(clippy/unnecessary_unwrap)
+        if result.next_price_sqrt == swap_limit && limiting_tick.is_some() {
+            let (tick_index, initialized) = limiting_tick.unwrap();
+
+            let is_enough_amount_to_cross = ok_or_mark_trace!(is_enough_amount_to_push_price(
+                remaining_amount,
+                result.next_price_sqrt,
+                pool.liquidity,
+                pool.fee,
+                by_amount_in,
+                x_to_y,
+            ))?;
+
+            if initialized {
+                let tick = ticks
+                    .iter()
+                    .find(|tick| { tick.index } == tick_index)
+                    .ok_or_else(|| err!(&InvariantErrorCode::TickNotFound.to_string()))?;
+                let tick = RefCell::new(*tick);
+                required_ticks.push(get_tick_address(pool_address, tick_index));
+
+                // crossing tick
+                if !x_to_y || is_enough_amount_to_cross {
+                    cross_tick(&mut tick.borrow_mut(), &mut pool)
+                        .map_err(|e| err!(&e.to_string()))?;
+                    crossed_ticks.push(tick.into_inner());
+                } else if !remaining_amount.is_zero() {
+                    if by_amount_in {
+                        pool.add_fee(remaining_amount, FixedPoint::from_integer(0), x_to_y);
+                        total_amount_in += remaining_amount;
+                        total_fee += remaining_amount;
+                    }
+                    remaining_amount = TokenAmount(0);
+                }
+            }
+            // set tick to limit (below if price is going down, because current tick should always be below price)
+            pool.current_tick_index = if x_to_y && is_enough_amount_to_cross {
+                tick_index.checked_sub(pool.tick_spacing as i32).unwrap()
+            } else {
+                tick_index
+            };
+        } else {
+            assert!(
+                pool.current_tick_index
+                    .checked_rem(pool.tick_spacing.into())
+                    .unwrap()
+                    == 0,
+                "tick not divisible by spacing"
+            );
+            pool.current_tick_index =
+                get_tick_at_sqrt_price(result.next_price_sqrt, pool.tick_spacing);
+        }
+    }
+
+    Ok(SimulateSwapResult {
+        amount_in: total_amount_in,
+        amount_out: total_amount_out,
+        fee: total_fee,
+        pool,
+        crossed_ticks,
+        required_ticks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use decimal::{Decimal, Factories};
+
+    use crate::{
+        decimals::{FixedPoint, Liquidity, Price, TokenAmount},
+        math::{calculate_price_sqrt, compute_swap_step},
+        structs::{Pool, Tick, Tickmap},
+        utils::{get_pool_address, get_tick_address},
+        MIN_SQRT_PRICE,
+    };
+
+    use super::simulate_swap;
+
+    fn setup(liquidity_change: Liquidity) -> (Pool, Tickmap, Tick) {
+        let tick_spacing = 10;
+        let pool = Pool {
+            tick_spacing,
+            fee: FixedPoint::from_scale(3, 3),
+            protocol_fee: FixedPoint::from_scale(1, 2),
+            liquidity: Liquidity::from_integer(1_000_000),
+            sqrt_price: Price::from_integer(1),
+            current_tick_index: 0,
+            ..Default::default()
+        };
+        let mut tickmap = Tickmap::default();
+        tickmap.flip(true, -10, tick_spacing);
+        let tick = Tick {
+            index: -10,
+            sign: true,
+            liquidity_change,
+            liquidity_gross: liquidity_change,
+            sqrt_price: calculate_price_sqrt(-10),
+            ..Default::default()
+        };
+        (pool, tickmap, tick)
+    }
+
+    #[test]
+    fn test_simulate_swap_without_crossing() {
+        let (pool, tickmap, tick) = setup(Liquidity::from_integer(400_000));
+        let amount = TokenAmount(100);
+
+        let result = simulate_swap(
+            &pool,
+            &tickmap,
+            &[tick],
+            true,
+            amount,
+            true,
+            Price::new(MIN_SQRT_PRICE),
+        )
+        .unwrap();
+        let step = compute_swap_step(
+            pool.sqrt_price,
+            calculate_price_sqrt(-10),
+            pool.liquidity,
+            amount,
+            true,
+            pool.fee,
+        )
+        .unwrap();
+
+        assert_eq!(result.amount_in, amount);
+        assert_eq!(result.amount_out, step.amount_out);
+        assert_eq!(result.fee, step.fee_amount);
+        assert_eq!({ result.pool.sqrt_price }, step.next_price_sqrt);
+        assert_eq!({ result.pool.current_tick_index }, -10);
+        assert_eq!({ result.pool.liquidity }, { pool.liquidity });
+        assert!(result.crossed_ticks.is_empty());
+        assert!(result.required_ticks.is_empty());
+    }
+
+    #[test]
+    fn test_simulate_swap_crossing_tick() {
+        let (pool, tickmap, tick) = setup(Liquidity::from_integer(400_000));
+        let pool_address =
+            get_pool_address(pool.token_x, pool.token_y, pool.fee.v, pool.tick_spacing);
+
+        let result = simulate_swap(
+            &pool,
+            &tickmap,
+            &[tick],
+            true,
+            TokenAmount(1000),
+            true,
+            Price::new(MIN_SQRT_PRICE),
+        )
+        .unwrap();
+
+        assert_eq!(result.amount_in, TokenAmount(1000));
+        assert_eq!(result.crossed_ticks.len(), 1);
+        assert_eq!({ result.crossed_ticks[0].index }, -10);
+        assert_eq!(
+            result.required_ticks,
+            vec![get_tick_address(pool_address, -10)]
+        );
+        assert_eq!({ result.pool.liquidity }, Liquidity::from_integer(600_000));
+        assert!({ result.pool.current_tick_index } < -10);
+        assert!({ result.pool.sqrt_price } < calculate_price_sqrt(-10));
+    }
+
+    #[test]
+    fn test_simulate_swap_not_enough_amount_to_cross() {
+        let (pool, tickmap, tick) = setup(Liquidity::from_integer(400_000));
+        let target = calculate_price_sqrt(-10);
+        let step = compute_swap_step(
+            pool.sqrt_price,
+            target,
+            pool.liquidity,
+            TokenAmount(u32::MAX as u64),
+            true,
+            pool.fee,
+        )
+        .unwrap();
+        // a single token left after reaching the tick cannot move the price
+        let amount = step.amount_in + step.fee_amount + TokenAmount(1);
+
+        let result = simulate_swap(
+            &pool,
+            &tickmap,
+            &[tick],
+            true,
+            amount,
+            true,
+            Price::new(MIN_SQRT_PRICE),
+        )
+        .unwrap();
+
+        assert_eq!(result.amount_in, amount);
+        assert_eq!(result.amount_out, step.amount_out);
+        assert_eq!(result.fee, step.fee_amount + TokenAmount(1));
+        assert_eq!({ result.pool.sqrt_price }, target);
+        assert_eq!({ result.pool.current_tick_index }, -10);
+        assert_eq!({ result.pool.liquidity }, { pool.liquidity });
+        assert!(result.crossed_ticks.is_empty());
+        assert_eq!(result.required_ticks.len(), 1);
+    }
+
+    #[test]
+    fn test_simulate_swap_errors() {
+        let (pool, tickmap, tick) = setup(Liquidity::from_integer(400_000));
+        let limit = Price::new(MIN_SQRT_PRICE);
+
+        // zero amount
+        {
+            let err = simulate_swap(&pool, &tickmap, &[tick], true, TokenAmount(0), true, limit)
+                .unwrap_err();
+            assert_eq!(err.cause, "Amount is zero");
+        }
+        // limit on the wrong side
+        {
+            let err = simulate_swap(
+                &pool,
+                &tickmap,
+                &[tick],
+                false,
+                TokenAmount(10),
+                true,
+                limit,
+            )
+            .unwrap_err();
+            assert_eq!(err.cause, "Price limit is on the wrong side of price");
+        }
+        // initialized tick not provided
+        {
+            let err = simulate_swap(&pool, &tickmap, &[], true, TokenAmount(1000), true, limit)
+                .unwrap_err();
+            assert_eq!(err.cause, "Correct tick not found in context");
+        }
+        // price limit reached before the whole amount was swapped
+        {
+            let err = simulate_swap(
+                &pool,
+                &tickmap,
+                &[tick],
+                true,
+                TokenAmount(1000),
+                true,
+                calculate_price_sqrt(-5),
+            )
+            .unwrap_err();
+            assert_eq!(err.cause, "Price would cross swap limit");
+        }
+    }
+}
@@ -31,3 +31,39 @@ pub struct Pool {
     pub bump: u8,
 }
 size!(Pool);
+
+impl Pool {
+    pub fn add_fee(
+        &mut self,
+        amount: TokenAmount,
+        ref_percentage: FixedPoint,
+        in_x: bool,
+    ) -> TokenAmount {
+        let protocol_fee = TokenAmount::from_decimal_up(amount.big_mul_up(self.protocol_fee));
+        let ref_fee = match ref_percentage.is_zero() {
+            true => TokenAmount(0),
+            false => TokenAmount::from_decimal(amount.big_mul(ref_percentage)),
+        };
+        let pool_fee = amount - protocol_fee - ref_fee;
+
+        if (pool_fee.is_zero() && protocol_fee.is_zero()) || self.liquidity.is_zero() {
+            return ref_fee;
+        }
+        let fee_growth = FeeGrowth::from_fee(self.liquidity, pool_fee);
+
+        if in_x {
+            self.fee_growth_global_x = self.fee_growth_global_x.unchecked_add(fee_growth);
+            self.fee_protocol_token_x = self
+                .fee_protocol_token_x
+                .checked_add(protocol_fee.0)
+                .unwrap();
+        } else {
+            self.fee_growth_global_y = self.fee_growth_global_y.unchecked_add(fee_growth);
+            self.fee_protocol_token_y = self
+                .fee_protocol_token_y
+                .checked_add(protocol_fee.0)
+                .unwrap();
+        }
+        ref_fee
+    }
+}
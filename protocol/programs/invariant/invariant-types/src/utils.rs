@@ -2,7 +2,7 @@ use std::cmp::Ordering;
 
 use anchor_lang::prelude::Pubkey;
 
-use crate::ID;
+use crate::{ID, TICK_SEED};
 
 pub type TrackableResult<T> = Result<T, TrackableError>;
 
@@ -58,6 +58,16 @@ pub fn get_pool_address(
     second_token: Pubkey,
     fee: u128,
     tick_spacing: u16,
+) -> Pubkey {
+    find_pool_address(&ID, first_token, second_token, fee, tick_spacing)
+}
+
+fn find_pool_address(
+    program_id: &Pubkey,
+    first_token: Pubkey,
+    second_token: Pubkey,
+    fee: u128,
+    tick_spacing: u16,
 ) -> Pubkey {
     let inverse = first_token.to_string().cmp(&second_token.to_string()) == Ordering::Less;
     let (token_x, token_y) = match inverse {
@@ -73,11 +83,19 @@ pub fn get_pool_address(
             &fee.to_le_bytes(),
             &tick_spacing.to_le_bytes(),
         ],
-        &ID,
+        program_id,
     );
     pool_address
 }
 
+pub fn get_tick_address(pool: Pubkey, index: i32) -> Pubkey {
+    let (tick_address, _) = Pubkey::find_program_address(
+        &[TICK_SEED.as_bytes(), pool.as_ref(), &index.to_le_bytes()],
+        &ID,
+    );
+    tick_address
+}
+
 #[macro_use]
 pub mod trackable_result {
     #[macro_export]
@@ -216,11 +234,19 @@ mod tests {
         let fee = 10000000;
         let tick_spacing = 1;
 
-        let pool_address_1 = get_pool_address(token_x, token_y, fee, tick_spacing);
-        let pool_address_2 = get_pool_address(token_y, token_x, fee, tick_spacing);
+        // invariant deployed on solana mainnet
+        let program_id = Pubkey::from_str("HyaB3W9q6XdA5xwpU4XnSZV94htfmbmqJXZcEbRaJutt").unwrap();
+        let pool_address_1 = find_pool_address(&program_id, token_x, token_y, fee, tick_spacing);
+        let pool_address_2 = find_pool_address(&program_id, token_y, token_x, fee, tick_spacing);
         let expected = Pubkey::from_str("BRt1iVYDNoohkL1upEb8UfHE8yji6gEDAmuN9Y4yekyc").unwrap();
 
         assert_eq!(pool_address_1, expected);
         assert_eq!(pool_address_2, expected);
+
+        // derived with the id of the invariant program
+        assert_eq!(ID, invariant::ID);
+        let pool_address = get_pool_address(token_x, token_y, fee, tick_spacing);
+        let expected = Pubkey::from_str("GS6DNNgij2H1Zhwe7SGNFB16S3Zj5qgPuxdkfG2NaTCR").unwrap();
+        assert_eq!(pool_address, expected);
     }
 }
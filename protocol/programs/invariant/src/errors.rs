@@ -66,4 +66,30 @@ pub enum ErrorCode {
     InvalidTickSpacing = 30, // 178e
     #[msg("Invalid token program")]
     InvalidTokenProgram = 31, // 178f
+    #[msg("Oracle is missing or different than expected")]
+    InvalidOracle = 32, // 1790
+    #[msg("Oracle has no records")]
+    OracleEmpty = 33, // 1791
+    #[msg("Observation is older than the oldest record")]
+    ObservationTooOld = 34, // 1792
+    #[msg("Oracle size has to grow within the reallocation limit")]
+    InvalidOracleSize = 35, // 1793
+    #[msg("Route has to chain between 2 and 4 pools")]
+    InvalidRoute = 36, // 1794
+    #[msg("Amount out is below the minimum")]
+    AmountOutBelowMinimum = 37, // 1795
+    #[msg("Amount in is above the maximum")]
+    AmountInAboveMaximum = 38, // 1796
+    #[msg("Referral fee share is above the maximum")]
+    InvalidReferralFee = 39, // 1797
+    #[msg("Referral registry is full")]
+    ReferralRegistryFull = 40, // 1798
+    #[msg("Referrer is not registered")]
+    ReferrerNotFound = 41, // 1799
+    #[msg("Mint has an unsupported Token-2022 extension")]
+    UnsupportedMintExtension = 42, // 179a
+    #[msg("Account is already in the current layout")]
+    AlreadyMigrated = 43, // 179b
+    #[msg("Transfer fee calculation overflowed")]
+    TransferFeeOverflow = 44, // 179c
 }
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct SwapEvent {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub x_to_y: bool,
+    pub amount_in: u64, // fee included
+    pub amount_out: u64,
+    pub fee: u64,
+    pub referral_fee: u64,
+    pub ticks_crossed: Vec<i32>,
+    pub sqrt_price_before: u128,
+    pub sqrt_price_after: u128,
+    pub tick_index_after: i32,
+}
+
+#[event]
+pub struct CreatePositionEvent {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub id: u128,
+    pub lower_tick_index: i32,
+    pub upper_tick_index: i32,
+    pub liquidity: u128,
+    pub amount_x: u64,
+    pub amount_y: u64,
+}
+
+#[event]
+pub struct ChangeLiquidityEvent {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub id: u128,
+    pub add_liquidity: bool,
+    pub liquidity_delta: u128,
+    pub liquidity_after: u128,
+    pub amount_x: u64,
+    pub amount_y: u64,
+}
+
+#[event]
+pub struct RemovePositionEvent {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub id: u128,
+    pub lower_tick_index: i32,
+    pub upper_tick_index: i32,
+    pub liquidity: u128,
+    pub amount_x: u64, // owed fees included
+    pub amount_y: u64,
+}
+
+#[event]
+pub struct ClaimFeeEvent {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub id: u128,
+    pub amount_x: u64,
+    pub amount_y: u64,
+}
+
+#[event]
+pub struct WithdrawProtocolFeeEvent {
+    pub pool: Pubkey,
+    pub account_x: Pubkey,
+    pub account_y: Pubkey,
+    pub amount_x: u64,
+    pub amount_y: u64,
+}
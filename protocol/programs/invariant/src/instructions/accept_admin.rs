@@ -0,0 +1,23 @@
+use crate::structs::State;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(mut, seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(constraint = &state.load()?.pending_admin == new_admin.key @ InvalidAdmin)]
+    pub new_admin: Signer<'info>,
+}
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn handler(&self) -> Result<()> {
+        msg!("INVARIANT: ACCEPT ADMIN");
+
+        let mut state = self.state.load_mut()?;
+        state.admin = self.new_admin.key();
+        state.pending_admin = Pubkey::default();
+
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::events::ChangeLiquidityEvent;
 use crate::interfaces::take_tokens::TakeTokens;
 use crate::structs::pool::Pool;
 use crate::structs::position::Position;
@@ -183,6 +184,7 @@ impl<'info> ChangeLiquidity<'info> {
         add_liquidity: bool,
         slippage_limit_lower: Price,
         slippage_limit_upper: Price,
+        remaining_accounts: &'info [AccountInfo<'info>],
     ) -> Result<()> {
         msg!("INVARIANT: CHANGE POSITION LIQUIDITY");
 
@@ -193,6 +195,8 @@ impl<'info> ChangeLiquidity<'info> {
         let current_timestamp = get_current_timestamp();
         let slot = get_current_slot();
 
+        record_oracle(pool, remaining_accounts, current_timestamp, false)?;
+
         let liquidity = position.liquidity;
         require!(
             add_liquidity || liquidity_delta != liquidity,
@@ -234,11 +238,24 @@ impl<'info> ChangeLiquidity<'info> {
             ErrorCode::ZeroOutput
         );
 
+        emit!(ChangeLiquidityEvent {
+            pool: self.pool.key(),
+            owner: self.owner.key(),
+            id: position.id,
+            add_liquidity,
+            liquidity_delta: liquidity_delta.v,
+            liquidity_after: position.liquidity.v,
+            amount_x: amount_x.0,
+            amount_y: amount_y.0,
+        });
+
         if add_liquidity {
+            // transfer fee is paid on top, so the reserves receive the computed amounts
             match self.token_x_program.key() {
                 token_2022::ID => token_2022::transfer_checked(
                     self.take_x_2022(),
-                    amount_x.0,
+                    amount_x.0
+                        + get_transfer_inverse_fee(&self.token_x.to_account_info(), amount_x.0)?,
                     self.token_x.decimals,
                 )?,
                 token::ID => token::transfer(self.take_x(), amount_x.0)?,
@@ -247,7 +264,8 @@ impl<'info> ChangeLiquidity<'info> {
             match self.token_y_program.key() {
                 token_2022::ID => token_2022::transfer_checked(
                     self.take_y_2022(),
-                    amount_y.0,
+                    amount_y.0
+                        + get_transfer_inverse_fee(&self.token_y.to_account_info(), amount_y.0)?,
                     self.token_y.decimals,
                 )?,
                 token::ID => token::transfer(self.take_y(), amount_y.0)?,
@@ -0,0 +1,31 @@
+use crate::decimals::*;
+use crate::structs::ReferralRegistry;
+use crate::ErrorCode::*;
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ChangeReferralFeeCap<'info> {
+    #[account(mut,
+        seeds = [b"referralv1".as_ref()],
+        bump = referral_registry.load()?.bump
+    )]
+    pub referral_registry: AccountLoader<'info, ReferralRegistry>,
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(constraint = &state.load()?.admin == admin.key @ InvalidAdmin)]
+    pub admin: Signer<'info>,
+}
+
+impl<'info> ChangeReferralFeeCap<'info> {
+    pub fn handler(&self, max_fee_share: FixedPoint) -> Result<()> {
+        msg!("INVARIANT: CHANGE REFERRAL FEE CAP");
+
+        ReferralRegistry::validate_max_fee_share(max_fee_share)?;
+        // registered shares above the cap are lowered to it on swap
+        let mut referral_registry = self.referral_registry.load_mut()?;
+        referral_registry.max_fee_share = max_fee_share;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use crate::decimals::*;
+use crate::events::ClaimFeeEvent;
 use crate::interfaces::send_tokens::SendTokens;
 use crate::structs::pool::Pool;
 use crate::structs::position::Position;
@@ -161,6 +162,14 @@ impl<'info> ClaimFee<'info> {
         position.tokens_owed_y =
             position.tokens_owed_y - FixedPoint::from_decimal(fee_to_collect_y);
 
+        emit!(ClaimFeeEvent {
+            pool: self.pool.key(),
+            owner: self.owner.key(),
+            id: position.id,
+            amount_x: fee_to_collect_x.0,
+            amount_y: fee_to_collect_y.0,
+        });
+
         let signer: &[&[&[u8]]] = get_signer!(state.nonce);
 
         match self.token_x_program.key() {
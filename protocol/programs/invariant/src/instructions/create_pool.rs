@@ -4,6 +4,7 @@ use crate::structs::fee_tier::FeeTier;
 use crate::structs::pool::Pool;
 use crate::structs::tickmap::Tickmap;
 use crate::structs::State;
+use crate::token_extensions::has_supported_extensions;
 use crate::util::check_tick;
 use crate::util::get_current_timestamp;
 use crate::ErrorCode::{self};
@@ -66,6 +67,11 @@ impl<'info> CreatePool<'info> {
                 == Ordering::Less,
             ErrorCode::InvalidPoolTokenAddresses
         );
+        require!(
+            has_supported_extensions(&self.token_x.to_account_info())?
+                && has_supported_extensions(&self.token_y.to_account_info())?,
+            ErrorCode::UnsupportedMintExtension
+        );
 
         let pool = &mut self.pool.load_init()?;
         let fee_tier = self.fee_tier.load()?;
@@ -1,10 +1,11 @@
+use crate::events::CreatePositionEvent;
 use crate::interfaces::take_tokens::TakeTokens;
 use crate::structs::pool::Pool;
 use crate::structs::position::Position;
 use crate::structs::position_list::PositionList;
 use crate::structs::tick::Tick;
 use crate::structs::Tickmap;
-use crate::util::check_ticks;
+use crate::util::{check_ticks, record_oracle};
 use crate::ErrorCode::{self, *};
 use crate::*;
 use anchor_lang::prelude::*;
@@ -154,6 +155,7 @@ impl<'info> CreatePosition<'info> {
         slippage_limit_lower: Price,
         slippage_limit_upper: Price,
         bump: u8,
+        remaining_accounts: &'info [AccountInfo<'info>],
     ) -> Result<()> {
         msg!("INVARIANT: CREATE POSITION");
 
@@ -166,6 +168,8 @@ impl<'info> CreatePosition<'info> {
         let mut tickmap = self.tickmap.load_mut()?;
         let slot = get_current_slot();
 
+        record_oracle(pool, remaining_accounts, current_timestamp, false)?;
+
         // validate price
         let price = pool.sqrt_price;
         require!(price >= slippage_limit_lower, ErrorCode::PriceLimitReached);
@@ -211,17 +215,33 @@ impl<'info> CreatePosition<'info> {
             current_timestamp,
         )?;
 
+        emit!(CreatePositionEvent {
+            pool: self.pool.key(),
+            owner: self.owner.key(),
+            id: position.id,
+            lower_tick_index: lower_tick.index,
+            upper_tick_index: upper_tick.index,
+            liquidity: liquidity_delta.v,
+            amount_x: amount_x.0,
+            amount_y: amount_y.0,
+        });
+
+        // transfer fee is paid on top, so the reserves receive the computed amounts
         match self.token_x_program.key() {
-            token_2022::ID => {
-                token_2022::transfer_checked(self.take_x_2022(), amount_x.0, self.token_x.decimals)?
-            }
+            token_2022::ID => token_2022::transfer_checked(
+                self.take_x_2022(),
+                amount_x.0 + get_transfer_inverse_fee(&self.token_x.to_account_info(), amount_x.0)?,
+                self.token_x.decimals,
+            )?,
             token::ID => token::transfer(self.take_x(), amount_x.0)?,
             _ => return Err(ErrorCode::InvalidTokenProgram.into()),
         };
         match self.token_y_program.key() {
-            token_2022::ID => {
-                token_2022::transfer_checked(self.take_y_2022(), amount_y.0, self.token_y.decimals)?
-            }
+            token_2022::ID => token_2022::transfer_checked(
+                self.take_y_2022(),
+                amount_y.0 + get_transfer_inverse_fee(&self.token_y.to_account_info(), amount_y.0)?,
+                self.token_y.decimals,
+            )?,
             token::ID => token::transfer(self.take_y(), amount_y.0)?,
             _ => return Err(ErrorCode::InvalidTokenProgram.into()),
         };
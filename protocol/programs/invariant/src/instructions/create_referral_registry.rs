@@ -0,0 +1,40 @@
+use crate::decimals::*;
+use crate::structs::ReferralRegistry;
+use crate::ErrorCode::*;
+use crate::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+#[derive(Accounts)]
+pub struct CreateReferralRegistry<'info> {
+    #[account(init,
+        seeds = [b"referralv1".as_ref()],
+        bump, payer = admin, space = ReferralRegistry::LEN
+    )]
+    pub referral_registry: AccountLoader<'info, ReferralRegistry>,
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(mut, constraint = &state.load()?.admin == admin.key @ InvalidAdmin)]
+    pub admin: Signer<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = system_program::ID)]
+    /// CHECK: Ignore
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> CreateReferralRegistry<'info> {
+    pub fn handler(&self, max_fee_share: FixedPoint, bump: u8) -> Result<()> {
+        msg!("INVARIANT: CREATE REFERRAL REGISTRY");
+
+        ReferralRegistry::validate_max_fee_share(max_fee_share)?;
+        let referral_registry = &mut self.referral_registry.load_init()?;
+
+        **referral_registry = ReferralRegistry {
+            max_fee_share,
+            bump,
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+}
@@ -27,6 +27,7 @@ pub fn handler(ctx: Context<CreateState>, nonce: u8) -> Result<()> {
         authority: *ctx.accounts.program_authority.key,
         nonce,
         bump: ctx.bumps.state,
+        pending_admin: Pubkey::default(),
     };
     Ok(())
 }
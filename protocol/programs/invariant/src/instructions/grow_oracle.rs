@@ -0,0 +1,59 @@
+use crate::structs::oracle::Oracle;
+use crate::structs::pool::Pool;
+use crate::ErrorCode::{self, *};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::system_program::{transfer, Transfer};
+
+#[derive(Accounts)]
+pub struct GrowOracle<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(mut, constraint = oracle.key() == pool.load()?.oracle_address @ InvalidOracle)]
+    /// CHECK: loaded with extension in handler
+    pub oracle: AccountInfo<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(address = system_program::ID)]
+    /// CHECK: Ignore
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> GrowOracle<'info> {
+    pub fn handler(&self, size: u16) -> Result<()> {
+        msg!("INVARIANT: GROW ORACLE");
+
+        let size_next = Oracle::load_with_extension(&self.oracle)?.0.size_next;
+        require!(size > size_next, ErrorCode::InvalidOracleSize);
+
+        let space = Oracle::space(size);
+        let current_space = self.oracle.data_len();
+        if space > current_space {
+            require!(
+                space - current_space <= MAX_PERMITTED_DATA_INCREASE,
+                ErrorCode::InvalidOracleSize
+            );
+
+            let lamports = Rent::get()?
+                .minimum_balance(space)
+                .saturating_sub(self.oracle.lamports());
+            if lamports != 0 {
+                transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        Transfer {
+                            from: self.payer.to_account_info(),
+                            to: self.oracle.to_account_info(),
+                        },
+                    ),
+                    lamports,
+                )?;
+            }
+            self.oracle.realloc(space, true)?;
+        }
+
+        Oracle::load_with_extension_mut(&self.oracle)?.0.size_next = size;
+
+        Ok(())
+    }
+}
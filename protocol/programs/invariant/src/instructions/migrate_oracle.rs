@@ -0,0 +1,61 @@
+use crate::structs::oracle::Oracle;
+use crate::structs::pool::Pool;
+use crate::ErrorCode::{self, *};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+use std::mem::size_of;
+
+#[derive(Accounts)]
+pub struct MigrateOracle<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(mut, owner = crate::ID, constraint = oracle.key() == pool.load()?.oracle_address @ InvalidOracle)]
+    /// CHECK: legacy layout, can't be loaded as Oracle
+    pub oracle: AccountInfo<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(address = system_program::ID)]
+    /// CHECK: Ignore
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> MigrateOracle<'info> {
+    pub fn handler(&self) -> Result<()> {
+        msg!("INVARIANT: MIGRATE ORACLE");
+
+        require_eq!(
+            self.oracle.data_len(),
+            Oracle::LEGACY_SPACE,
+            ErrorCode::InvalidOracleSize
+        );
+        require!(
+            self.oracle.try_borrow_data()?[..8] == Oracle::DISCRIMINATOR,
+            ErrorCode::InvalidOracle
+        );
+
+        let space = 8 + size_of::<Oracle>();
+        let lamports = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(self.oracle.lamports());
+        if lamports != 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: self.oracle.to_account_info(),
+                    },
+                ),
+                lamports,
+            )?;
+        }
+        self.oracle.realloc(space, true)?;
+
+        // legacy oracles were never recorded to, so there is no history to carry over
+        self.oracle.try_borrow_mut_data()?[8..].fill(0);
+        Oracle::load_with_extension_mut(&self.oracle)?.0.init();
+
+        Ok(())
+    }
+}
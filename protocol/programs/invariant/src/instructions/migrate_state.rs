@@ -0,0 +1,58 @@
+use crate::structs::State;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+#[derive(Accounts)]
+pub struct MigrateState<'info> {
+    #[account(mut, owner = crate::ID, seeds = [b"statev1".as_ref()], bump)]
+    /// CHECK: legacy layout, can't be loaded as State
+    pub state: AccountInfo<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(address = system_program::ID)]
+    /// CHECK: Ignore
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> MigrateState<'info> {
+    pub fn handler(&self) -> Result<()> {
+        msg!("INVARIANT: MIGRATE STATE");
+
+        {
+            let data = self.state.try_borrow_data()?;
+            if data.len() < State::LEGACY_LEN || data[..8] != State::DISCRIMINATOR {
+                return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+            }
+            require_eq!(data.len(), State::LEGACY_LEN, ErrorCode::AlreadyMigrated);
+            // admin is the first field in both layouts
+            require!(
+                data[8..40] == self.admin.key.to_bytes(),
+                ErrorCode::InvalidAdmin
+            );
+        }
+
+        let space = State::LEN;
+        let lamports = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(self.state.lamports());
+        if lamports != 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.admin.to_account_info(),
+                        to: self.state.to_account_info(),
+                    },
+                ),
+                lamports,
+            )?;
+        }
+        // appended fields start zeroed, there is no pending admin
+        self.state.realloc(space, true)?;
+
+        Ok(())
+    }
+}
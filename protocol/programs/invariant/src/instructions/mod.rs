@@ -1,35 +1,59 @@
+pub mod accept_admin;
 pub mod change_fee_receiver;
 pub mod change_liquidity;
 pub mod change_protocol_fee;
+pub mod change_referral_fee_cap;
 pub mod claim_fee;
 pub mod create_fee_tier;
 pub mod create_pool;
 pub mod create_position;
 pub mod create_position_list;
+pub mod create_referral_registry;
 pub mod create_state;
 pub mod create_tick;
+pub mod grow_oracle;
 pub mod init_reserves;
 pub mod initialize_oracle;
+pub mod migrate_oracle;
+pub mod migrate_state;
+pub mod observe;
+pub mod propose_admin;
+pub mod quote_swap;
 pub mod remove_position;
+pub mod remove_referrer;
+pub mod set_referrer;
 pub mod swap;
+pub mod swap_route;
 pub mod transfer_position_ownership;
 pub mod update_seconds_per_liquidity;
 pub mod withdraw_protocol_fee;
 
+pub use accept_admin::*;
 pub use change_fee_receiver::*;
 pub use change_liquidity::*;
 pub use change_protocol_fee::*;
+pub use change_referral_fee_cap::*;
 pub use claim_fee::*;
 pub use create_fee_tier::*;
 pub use create_pool::*;
 pub use create_position::*;
 pub use create_position_list::*;
+pub use create_referral_registry::*;
 pub use create_state::*;
 pub use create_tick::*;
+pub use grow_oracle::*;
 pub use init_reserves::*;
 pub use initialize_oracle::*;
+pub use migrate_oracle::*;
+pub use migrate_state::*;
+pub use observe::*;
+pub use propose_admin::*;
+pub use quote_swap::*;
 pub use remove_position::*;
+pub use remove_referrer::*;
+pub use set_referrer::*;
 pub use swap::*;
+pub use swap_route::*;
 pub use transfer_position_ownership::*;
 pub use update_seconds_per_liquidity::*;
 pub use withdraw_protocol_fee::*;
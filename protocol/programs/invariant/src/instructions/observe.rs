@@ -0,0 +1,42 @@
+use crate::results::ObserveResult;
+use crate::structs::oracle::Oracle;
+use crate::structs::pool::Pool;
+use crate::util::get_current_timestamp;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Observe<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(constraint = oracle.key() == pool.load()?.oracle_address @ InvalidOracle)]
+    /// CHECK: loaded with extension in handler
+    pub oracle: AccountInfo<'info>,
+}
+
+impl<'info> Observe<'info> {
+    pub fn handler(&self, seconds_agos: Vec<u32>) -> Result<ObserveResult> {
+        msg!("INVARIANT: OBSERVE");
+
+        let pool = self.pool.load()?;
+        let (oracle, extension) = Oracle::load_with_extension(&self.oracle)?;
+        let current_timestamp = get_current_timestamp();
+
+        let mut result = ObserveResult::default();
+        for seconds_ago in seconds_agos {
+            let record = oracle.observe(
+                &extension,
+                current_timestamp,
+                seconds_ago as u64,
+                pool.sqrt_price,
+                pool.current_tick_index,
+                pool.seconds_per_liquidity_at(current_timestamp),
+            )?;
+            result.tick_cumulatives.push(record.tick_cumulative);
+            result
+                .seconds_per_liquidity_cumulatives
+                .push(record.seconds_per_liquidity_cumulative.v);
+        }
+
+        Ok(result)
+    }
+}
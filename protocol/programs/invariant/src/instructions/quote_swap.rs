@@ -0,0 +1,81 @@
+use crate::decimals::*;
+use crate::instructions::swap::{
+    compute_swap, find_tick, get_owner_amounts, get_swap_amount, ComputeSwapResult,
+};
+use crate::results::QuoteSwapResult;
+use crate::structs::pool::Pool;
+use crate::structs::tickmap::Tickmap;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct QuoteSwap<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(
+        constraint = tickmap.to_account_info().key == &pool.load()?.tickmap @ InvalidTickmap,
+        constraint = tickmap.to_account_info().owner == __program_id @ InvalidTickmapOwner
+    )]
+    pub tickmap: AccountLoader<'info, Tickmap>,
+    #[account(constraint = token_x.key() == pool.load()?.token_x @ InvalidTokenAccount)]
+    pub token_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(constraint = token_y.key() == pool.load()?.token_y @ InvalidTokenAccount)]
+    pub token_y: Box<InterfaceAccount<'info, Mint>>,
+}
+
+impl<'info> QuoteSwap<'info> {
+    pub fn handler(
+        ctx: Context<'_, '_, 'info, 'info, QuoteSwap<'info>>,
+        x_to_y: bool,
+        amount: u64,
+        by_amount_in: bool,
+        sqrt_price_limit: u128,
+    ) -> Result<QuoteSwapResult> {
+        msg!("INVARIANT: QUOTE SWAP");
+
+        // swap runs on copies, accounts are left untouched
+        let mut pool = *ctx.accounts.pool.load()?;
+        let tickmap = ctx.accounts.tickmap.load()?;
+        let pool_key = ctx.accounts.pool.key();
+
+        let (mint_in, mint_out) = match x_to_y {
+            true => (
+                ctx.accounts.token_x.to_account_info(),
+                ctx.accounts.token_y.to_account_info(),
+            ),
+            false => (
+                ctx.accounts.token_y.to_account_info(),
+                ctx.accounts.token_x.to_account_info(),
+            ),
+        };
+        let swap_amount = get_swap_amount(&mint_in, &mint_out, amount, by_amount_in)?;
+
+        let ComputeSwapResult {
+            amount_in,
+            amount_out,
+            fee,
+            referral_fee,
+            crossed_ticks,
+        } = compute_swap(
+            &mut pool,
+            &tickmap,
+            x_to_y,
+            swap_amount,
+            by_amount_in,
+            Price::new(sqrt_price_limit),
+            FixedPoint::from_integer(0),
+            |tick_index| Ok(*find_tick(ctx.remaining_accounts, &pool_key, tick_index)?.load()?),
+        )?;
+        let (amount_in, amount_out) =
+            get_owner_amounts(&mint_in, &mint_out, amount_in, amount_out, referral_fee)?;
+
+        Ok(QuoteSwapResult {
+            amount_in: amount_in.0,
+            amount_out: amount_out.0,
+            fee: fee.0,
+            ticks_crossed: crossed_ticks.iter().map(|tick| tick.index).collect(),
+            sqrt_price: pool.sqrt_price.v,
+            tick_index: pool.current_tick_index,
+        })
+    }
+}
@@ -1,11 +1,12 @@
 use crate::decimals::*;
+use crate::events::RemovePositionEvent;
 use crate::interfaces::send_tokens::SendTokens;
 use crate::structs::pool::Pool;
 use crate::structs::position::Position;
 use crate::structs::position_list::PositionList;
 use crate::structs::tick::Tick;
 use crate::structs::tickmap::Tickmap;
-use crate::util::{check_ticks, close};
+use crate::util::{check_ticks, close, record_oracle};
 use crate::ErrorCode::{self, *};
 use crate::*;
 use anchor_lang::prelude::*;
@@ -153,7 +154,13 @@ impl<'info> SendTokens<'info> for RemovePosition<'info> {
 }
 
 impl<'info> RemovePosition<'info> {
-    pub fn handler(&self, index: u32, lower_tick_index: i32, upper_tick_index: i32) -> Result<()> {
+    pub fn handler(
+        &self,
+        index: u32,
+        lower_tick_index: i32,
+        upper_tick_index: i32,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
         msg!("INVARIANT: REMOVE POSITION");
 
         let state = self.state.load()?;
@@ -163,11 +170,13 @@ impl<'info> RemovePosition<'info> {
         let tickmap = &mut self.tickmap.load_mut()?;
         let current_timestamp = get_current_timestamp();
 
+        record_oracle(pool, remaining_accounts, current_timestamp, false)?;
+
         // closing tick can't be in the same scope as loaded tick
         let close_lower;
         let close_upper;
 
-        let (amount_x, amount_y) = {
+        let (amount_x, amount_y, liquidity) = {
             let lower_tick = &mut self.lower_tick.load_mut()?;
             let upper_tick = &mut self.upper_tick.load_mut()?;
 
@@ -189,9 +198,20 @@ impl<'info> RemovePosition<'info> {
             close_lower = lower_tick.liquidity_gross.is_zero();
             close_upper = upper_tick.liquidity_gross.is_zero();
 
-            (amount_x, amount_y)
+            (amount_x, amount_y, liquidity_delta)
         };
 
+        emit!(RemovePositionEvent {
+            pool: self.pool.key(),
+            owner: self.owner.key(),
+            id: removed_position.id,
+            lower_tick_index,
+            upper_tick_index,
+            liquidity: liquidity.v,
+            amount_x: amount_x.0,
+            amount_y: amount_y.0,
+        });
+
         if close_lower {
             {
                 let lower_tick = &mut self.lower_tick.load_mut()?;
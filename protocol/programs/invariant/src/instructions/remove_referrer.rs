@@ -0,0 +1,26 @@
+use crate::structs::ReferralRegistry;
+use crate::ErrorCode::*;
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RemoveReferrer<'info> {
+    #[account(mut,
+        seeds = [b"referralv1".as_ref()],
+        bump = referral_registry.load()?.bump
+    )]
+    pub referral_registry: AccountLoader<'info, ReferralRegistry>,
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(constraint = &state.load()?.admin == admin.key @ InvalidAdmin)]
+    pub admin: Signer<'info>,
+}
+
+impl<'info> RemoveReferrer<'info> {
+    pub fn handler(&self, referrer: Pubkey) -> Result<()> {
+        msg!("INVARIANT: REMOVE REFERRER");
+
+        let mut referral_registry = self.referral_registry.load_mut()?;
+        referral_registry.remove_referrer(referrer)
+    }
+}
@@ -0,0 +1,27 @@
+use crate::decimals::*;
+use crate::structs::ReferralRegistry;
+use crate::ErrorCode::*;
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetReferrer<'info> {
+    #[account(mut,
+        seeds = [b"referralv1".as_ref()],
+        bump = referral_registry.load()?.bump
+    )]
+    pub referral_registry: AccountLoader<'info, ReferralRegistry>,
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(constraint = &state.load()?.admin == admin.key @ InvalidAdmin)]
+    pub admin: Signer<'info>,
+}
+
+impl<'info> SetReferrer<'info> {
+    pub fn handler(&self, referrer: Pubkey, fee_share: FixedPoint) -> Result<()> {
+        msg!("INVARIANT: SET REFERRER");
+
+        let mut referral_registry = self.referral_registry.load_mut()?;
+        referral_registry.set_referrer(referrer, fee_share)
+    }
+}
@@ -1,20 +1,24 @@
+use crate::events::SwapEvent;
 use crate::interfaces::send_tokens::SendTokens;
 use crate::interfaces::take_ref_tokens::TakeRefTokens;
 use crate::interfaces::take_tokens::TakeTokens;
 use crate::log::get_tick_at_sqrt_price;
 use crate::math::compute_swap_step;
+use crate::results::SwapResult;
 use crate::structs::pool::Pool;
+use crate::structs::referral_registry::ReferralRegistry;
 use crate::structs::tick::Tick;
 use crate::structs::tickmap::Tickmap;
-use crate::util::get_closer_limit;
+use crate::util::{get_closer_limit, record_oracle};
 use crate::ErrorCode::{self, *};
 use crate::*;
-use crate::{decimals::*, referral::whitelist::contains_owner};
+use crate::{decimals::*, referral::get_referral_fee_share};
 use anchor_lang::prelude::*;
 use anchor_spl::token;
 use anchor_spl::token_2022;
 use anchor_spl::token_interface::TokenInterface;
 use anchor_spl::token_interface::{Mint, TokenAccount};
+use std::cell::RefCell;
 
 #[derive(Accounts)]
 pub struct Swap<'info> {
@@ -67,6 +71,12 @@ pub struct Swap<'info> {
     pub token_x_program: Interface<'info, TokenInterface>,
     #[account(constraint = token_y_program.key() == token::ID || token_y_program.key() == token_2022::ID)]
     pub token_y_program: Interface<'info, TokenInterface>,
+    // token account of the referrer in the input token, of either token program
+    #[account(mut)]
+    pub referral: Option<InterfaceAccount<'info, TokenAccount>>,
+    // referrers and their fee shares, required for the referral to get a share
+    #[account(seeds = [b"referralv1".as_ref()], bump = referral_registry.load()?.bump)]
+    pub referral_registry: Option<AccountLoader<'info, ReferralRegistry>>,
 }
 
 impl<'info> TakeTokens<'info> for Swap<'info> {
@@ -230,168 +240,132 @@ impl<'info> Swap<'info> {
         amount: u64,
         by_amount_in: bool, // whether amount specifies input or output
         sqrt_price_limit: u128,
-    ) -> Result<()> {
+        max_amount_in: u64, // fee included
+        min_amount_out: u64,
+    ) -> Result<SwapResult> {
         msg!("INVARIANT: SWAP");
-        require!(amount != 0, ErrorCode::ZeroAmount);
 
         let sqrt_price_limit = Price::new(sqrt_price_limit);
         let mut pool = ctx.accounts.pool.load_mut()?;
         let tickmap = ctx.accounts.tickmap.load()?;
         let state = ctx.accounts.state.load()?;
 
-        let ref_account = match ctx
-            .remaining_accounts
-            .iter()
-            .find(|account| *account.owner == token::ID)
-        {
-            Some(account) => match InterfaceAccount::<'info, TokenAccount>::try_from(account) {
-                Ok(token) => {
-                    let is_valid_mint = token.mint
-                        == match x_to_y {
-                            true => ctx.accounts.account_x.mint,
-                            false => ctx.accounts.account_y.mint,
-                        };
-                    let is_on_whitelist = contains_owner(token.owner);
-                    match is_valid_mint && is_on_whitelist {
-                        true => Some(account),
-                        false => None,
-                    }
-                }
-                Err(_) => None,
-            },
+        let referral = match &ctx.accounts.referral {
+            Some(account) => {
+                let (mint, token_program) = match x_to_y {
+                    true => (
+                        ctx.accounts.account_x.mint,
+                        ctx.accounts.token_x_program.key(),
+                    ),
+                    false => (
+                        ctx.accounts.account_y.mint,
+                        ctx.accounts.token_y_program.key(),
+                    ),
+                };
+                require_keys_eq!(account.mint, mint, InvalidMint);
+                require_keys_eq!(
+                    *account.to_account_info().owner,
+                    token_program,
+                    InvalidTokenProgram
+                );
+                // referrers missing from the registry get no share
+                get_referral_fee_share(ctx.accounts.referral_registry.as_ref(), account.owner)
+                    .map(|fee_share| (account.to_account_info(), fee_share))
+            }
             None => None,
         };
+        let ref_account = referral.as_ref().map(|(account, _)| account);
 
-        // limit is on the right side of price
-        if x_to_y {
-            require!(
-                { pool.sqrt_price } > sqrt_price_limit
-                    && sqrt_price_limit <= Price::new(MAX_SQRT_PRICE),
-                ErrorCode::WrongLimit
-            );
-        } else {
-            require!(
-                { pool.sqrt_price } < sqrt_price_limit
-                    && sqrt_price_limit >= Price::new(MIN_SQRT_PRICE),
-                ErrorCode::WrongLimit
-            );
-        }
+        // price before the swap, so a single transaction can't move the recorded one
+        // required, as a skipped price change would skew the cumulative tick
+        record_oracle(&pool, ctx.remaining_accounts, get_current_timestamp(), true)?;
 
-        let mut remaining_amount = TokenAmount(amount);
-
-        let mut total_amount_in = TokenAmount(0);
-        let mut total_amount_out = TokenAmount(0);
-        let mut total_amount_referral = TokenAmount(0);
-
-        while !remaining_amount.is_zero() {
-            let (swap_limit, limiting_tick) = get_closer_limit(
-                sqrt_price_limit,
-                x_to_y,
-                pool.current_tick_index,
-                pool.tick_spacing,
-                &tickmap,
-            )?;
-
-            let result = compute_swap_step(
-                pool.sqrt_price,
-                swap_limit,
-                pool.liquidity,
-                remaining_amount,
-                by_amount_in,
-                pool.fee,
-            );
-            // make remaining amount smaller
-            if by_amount_in {
-                remaining_amount -= result.amount_in + result.fee_amount;
-            } else {
-                remaining_amount -= result.amount_out;
-            }
-
-            total_amount_referral += match ref_account.is_some() {
-                true => pool.add_fee(result.fee_amount, FixedPoint::from_scale(2, 1), x_to_y),
-                false => pool.add_fee(result.fee_amount, FixedPoint::from_integer(0), x_to_y),
-            };
-
-            pool.sqrt_price = result.next_price_sqrt;
-
-            total_amount_in += result.amount_in + result.fee_amount;
-            total_amount_out += result.amount_out;
-
-            // Fail if price would go over swap limit
-            if { pool.sqrt_price } == sqrt_price_limit && !remaining_amount.is_zero() {
-                return Err(ErrorCode::PriceLimitReached.into());
-            }
-
-            // crossing tick
-            // trunk-ignore(clippy/unnecessary_unwrap)
-            if result.next_price_sqrt == swap_limit && limiting_tick.is_some() {
-                let (tick_index, initialized) = limiting_tick.unwrap();
-
-                let is_enough_amount_to_cross = is_enough_amount_to_push_price(
-                    remaining_amount,
-                    result.next_price_sqrt,
-                    pool.liquidity,
-                    pool.fee,
-                    by_amount_in,
-                    x_to_y,
-                );
+        let sqrt_price_before = pool.sqrt_price;
+        let pool_key = ctx.accounts.pool.key();
+        let ref_percentage = match referral {
+            Some((_, fee_share)) => fee_share,
+            None => FixedPoint::from_integer(0),
+        };
 
-                if initialized {
-                    // Calculating address of the crossed tick
-                    let (tick_address, _) = Pubkey::find_program_address(
-                        &[
-                            b"tickv1",
-                            ctx.accounts.pool.to_account_info().key.as_ref(),
-                            &tick_index.to_le_bytes(),
-                        ],
-                        ctx.program_id,
-                    );
-
-                    // Finding the correct tick in remaining accounts
-                    let loader = match ctx
-                        .remaining_accounts
-                        .iter()
-                        .find(|account| *account.key == tick_address)
-                    {
-                        Some(account) => AccountLoader::<'_, Tick>::try_from(account).unwrap(),
-                        None => return Err(ErrorCode::TickNotFound.into()),
-                    };
-                    let mut tick = loader.load_mut().unwrap();
-
-                    // crossing tick
-                    if !x_to_y || is_enough_amount_to_cross {
-                        msg!("INVARIANT: CROSSING TICK {} ", { tick.index });
-                        cross_tick(&mut tick, &mut pool, get_current_timestamp())?;
-                    } else if !remaining_amount.is_zero() {
-                        if by_amount_in {
-                            pool.add_fee(remaining_amount, FixedPoint::from_integer(0), x_to_y);
-                            total_amount_in += remaining_amount;
-                        }
-                        remaining_amount = TokenAmount(0);
-                    }
-                }
-                // set tick to limit (below if price is going down, because current tick should always be below price)
-                pool.current_tick_index = if x_to_y && is_enough_amount_to_cross {
-                    tick_index.checked_sub(pool.tick_spacing as i32).unwrap()
-                } else {
-                    tick_index
-                };
-            } else {
-                assert!(
-                    pool.current_tick_index
-                        .checked_rem(pool.tick_spacing.into())
-                        .unwrap()
-                        == 0,
-                    "tick not divisible by spacing"
-                );
-                pool.current_tick_index =
-                    get_tick_at_sqrt_price(result.next_price_sqrt, pool.tick_spacing);
-            }
+        let (mint_in, mint_out) = match x_to_y {
+            true => (
+                ctx.accounts.token_x.to_account_info(),
+                ctx.accounts.token_y.to_account_info(),
+            ),
+            false => (
+                ctx.accounts.token_y.to_account_info(),
+                ctx.accounts.token_x.to_account_info(),
+            ),
+        };
+        let swap_amount = get_swap_amount(&mint_in, &mint_out, amount, by_amount_in)?;
+
+        let mut tick_loaders: Vec<(i32, AccountLoader<Tick>)> = Vec::new();
+        let ComputeSwapResult {
+            amount_in: total_amount_in,
+            amount_out: total_amount_out,
+            fee: total_fee,
+            referral_fee: total_amount_referral,
+            crossed_ticks,
+        } = compute_swap(
+            &mut pool,
+            &tickmap,
+            x_to_y,
+            swap_amount,
+            by_amount_in,
+            sqrt_price_limit,
+            ref_percentage,
+            |tick_index| {
+                let loader = find_tick(ctx.remaining_accounts, &pool_key, tick_index)?;
+                let tick = *loader.load()?;
+                tick_loaders.push((tick_index, loader));
+                Ok(tick)
+            },
+        )?;
+
+        // save crossed ticks
+        for crossed_tick in crossed_ticks.iter() {
+            let (_, loader) = tick_loaders
+                .iter()
+                .find(|(index, _)| *index == crossed_tick.index)
+                .unwrap();
+            *loader.load_mut()? = *crossed_tick;
         }
+        let ticks_crossed: Vec<i32> = crossed_ticks.iter().map(|tick| tick.index).collect();
+
+        let (total_amount_in, amount_received) = get_owner_amounts(
+            &mint_in,
+            &mint_out,
+            total_amount_in,
+            total_amount_out,
+            total_amount_referral,
+        )?;
 
-        if total_amount_out.0 == 0 {
+        if amount_received.0 == 0 {
             return Err(ErrorCode::NoGainSwap.into());
         }
+        // slippage guards, checked before any tokens are moved
+        require!(
+            total_amount_in.0 <= max_amount_in,
+            ErrorCode::AmountInAboveMaximum
+        );
+        require!(
+            amount_received.0 >= min_amount_out,
+            ErrorCode::AmountOutBelowMinimum
+        );
+
+        emit!(SwapEvent {
+            pool: ctx.accounts.pool.key(),
+            owner: ctx.accounts.owner.key(),
+            x_to_y,
+            amount_in: total_amount_in.0,
+            amount_out: amount_received.0,
+            fee: total_fee.0,
+            referral_fee: total_amount_referral.0,
+            ticks_crossed,
+            sqrt_price_before: sqrt_price_before.v,
+            sqrt_price_after: pool.sqrt_price.v,
+            tick_index_after: pool.current_tick_index,
+        });
 
         // Execute swap
         let signer: &[&[&[u8]]] = get_signer!(state.nonce);
@@ -585,6 +559,203 @@ impl<'info> Swap<'info> {
             return Err(ErrorCode::InvalidTokenProgram.into());
         }
 
-        Ok(())
+        Ok(SwapResult {
+            total_amount_in: total_amount_in.0,
+            total_amount_out: amount_received.0,
+            fee: total_fee.0,
+            sqrt_price: pool.sqrt_price.v,
+        })
     }
 }
+
+pub struct ComputeSwapResult {
+    pub amount_in: TokenAmount, // fee included
+    pub amount_out: TokenAmount,
+    pub fee: TokenAmount,
+    pub referral_fee: TokenAmount,
+    pub crossed_ticks: Vec<Tick>,
+}
+
+// finds initialized tick in remaining accounts by its address
+pub fn find_tick<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    pool: &Pubkey,
+    tick_index: i32,
+) -> Result<AccountLoader<'info, Tick>> {
+    let (tick_address, _) = Pubkey::find_program_address(
+        &[b"tickv1", pool.as_ref(), &tick_index.to_le_bytes()],
+        &crate::ID,
+    );
+
+    match remaining_accounts
+        .iter()
+        .find(|account| *account.key == tick_address)
+    {
+        Some(account) => AccountLoader::<'info, Tick>::try_from(account),
+        None => Err(ErrorCode::TickNotFound.into()),
+    }
+}
+
+// pool swaps amounts reaching and leaving its reserves, transfer fees are on the owner
+pub fn get_swap_amount(
+    mint_in: &AccountInfo,
+    mint_out: &AccountInfo,
+    amount: u64,
+    by_amount_in: bool,
+) -> Result<u64> {
+    Ok(match by_amount_in {
+        true => amount - get_transfer_fee(mint_in, amount)?,
+        false => amount + get_transfer_inverse_fee(mint_out, amount)?,
+    })
+}
+
+// transfer fee of the input is sent on top of it, the one of the output is withheld from it
+pub fn get_owner_amounts(
+    mint_in: &AccountInfo,
+    mint_out: &AccountInfo,
+    amount_in: TokenAmount,
+    amount_out: TokenAmount,
+    referral_fee: TokenAmount,
+) -> Result<(TokenAmount, TokenAmount)> {
+    Ok((
+        amount_in
+            + TokenAmount(get_transfer_inverse_fee(
+                mint_in,
+                (amount_in - referral_fee).0,
+            )?),
+        amount_out - TokenAmount(get_transfer_fee(mint_out, amount_out.0)?),
+    ))
+}
+
+// swap steps shared with quote_swap, crossed ticks are returned instead of being saved
+#[allow(clippy::too_many_arguments)]
+pub fn compute_swap(
+    pool: &mut Pool,
+    tickmap: &Tickmap,
+    x_to_y: bool,
+    amount: u64,
+    by_amount_in: bool,
+    sqrt_price_limit: Price,
+    ref_percentage: FixedPoint,
+    mut load_tick: impl FnMut(i32) -> Result<Tick>,
+) -> Result<ComputeSwapResult> {
+    require!(amount != 0, ErrorCode::ZeroAmount);
+
+    // limit is on the right side of price
+    if x_to_y {
+        require!(
+            { pool.sqrt_price } > sqrt_price_limit
+                && sqrt_price_limit <= Price::new(MAX_SQRT_PRICE),
+            ErrorCode::WrongLimit
+        );
+    } else {
+        require!(
+            { pool.sqrt_price } < sqrt_price_limit
+                && sqrt_price_limit >= Price::new(MIN_SQRT_PRICE),
+            ErrorCode::WrongLimit
+        );
+    }
+
+    let mut remaining_amount = TokenAmount(amount);
+
+    let mut total_amount_in = TokenAmount(0);
+    let mut total_amount_out = TokenAmount(0);
+    let mut total_amount_referral = TokenAmount(0);
+    let mut total_fee = TokenAmount(0);
+    let mut crossed_ticks: Vec<Tick> = Vec::new();
+
+    while !remaining_amount.is_zero() {
+        let (swap_limit, limiting_tick) = get_closer_limit(
+            sqrt_price_limit,
+            x_to_y,
+            pool.current_tick_index,
+            pool.tick_spacing,
+            tickmap,
+        )?;
+
+        let result = compute_swap_step(
+            pool.sqrt_price,
+            swap_limit,
+            pool.liquidity,
+            remaining_amount,
+            by_amount_in,
+            pool.fee,
+        );
+        // make remaining amount smaller
+        if by_amount_in {
+            remaining_amount -= result.amount_in + result.fee_amount;
+        } else {
+            remaining_amount -= result.amount_out;
+        }
+
+        total_amount_referral += pool.add_fee(result.fee_amount, ref_percentage, x_to_y);
+
+        pool.sqrt_price = result.next_price_sqrt;
+
+        total_amount_in += result.amount_in + result.fee_amount;
+        total_amount_out += result.amount_out;
+        total_fee += result.fee_amount;
+
+        // Fail if price would go over swap limit
+        if { pool.sqrt_price } == sqrt_price_limit && !remaining_amount.is_zero() {
+            return Err(ErrorCode::PriceLimitReached.into());
+        }
+
+        // crossing tick
+        // trunk-ignore(clippy/unnecessary_unwrap)
+        if result.next_price_sqrt == swap_limit && limiting_tick.is_some() {
+            let (tick_index, initialized) = limiting_tick.unwrap();
+
+            let is_enough_amount_to_cross = is_enough_amount_to_push_price(
+                remaining_amount,
+                result.next_price_sqrt,
+                pool.liquidity,
+                pool.fee,
+                by_amount_in,
+                x_to_y,
+            );
+
+            if initialized {
+                let tick = RefCell::new(load_tick(tick_index)?);
+
+                // crossing tick
+                if !x_to_y || is_enough_amount_to_cross {
+                    msg!("INVARIANT: CROSSING TICK {} ", tick_index);
+                    cross_tick(&mut tick.borrow_mut(), pool, get_current_timestamp())?;
+                    crossed_ticks.push(tick.into_inner());
+                } else if !remaining_amount.is_zero() {
+                    if by_amount_in {
+                        pool.add_fee(remaining_amount, FixedPoint::from_integer(0), x_to_y);
+                        total_amount_in += remaining_amount;
+                        total_fee += remaining_amount;
+                    }
+                    remaining_amount = TokenAmount(0);
+                }
+            }
+            // set tick to limit (below if price is going down, because current tick should always be below price)
+            pool.current_tick_index = if x_to_y && is_enough_amount_to_cross {
+                tick_index.checked_sub(pool.tick_spacing as i32).unwrap()
+            } else {
+                tick_index
+            };
+        } else {
+            assert!(
+                pool.current_tick_index
+                    .checked_rem(pool.tick_spacing.into())
+                    .unwrap()
+                    == 0,
+                "tick not divisible by spacing"
+            );
+            pool.current_tick_index =
+                get_tick_at_sqrt_price(result.next_price_sqrt, pool.tick_spacing);
+        }
+    }
+
+    Ok(ComputeSwapResult {
+        amount_in: total_amount_in,
+        amount_out: total_amount_out,
+        fee: total_fee,
+        referral_fee: total_amount_referral,
+        crossed_ticks,
+    })
+}
@@ -0,0 +1,315 @@
+use crate::events::SwapEvent;
+use crate::instructions::swap::{compute_swap, find_tick, ComputeSwapResult};
+use crate::results::SwapRouteResult;
+use crate::structs::pool::Pool;
+use crate::structs::tick::Tick;
+use crate::structs::tickmap::Tickmap;
+use crate::util::record_oracle;
+use crate::ErrorCode::{self, *};
+use crate::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token_2022;
+use anchor_spl::token_interface::TokenInterface;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use std::convert::TryFrom;
+
+pub const MIN_ROUTE_HOPS: u8 = 2;
+pub const MAX_ROUTE_HOPS: u8 = 4;
+// pool, tickmap, token_x, token_y, reserve_x, reserve_y, token_x_program, token_y_program, account_out
+const HOP_ACCOUNTS_LEN: usize = 9;
+
+// Remaining accounts start with the input token account of the owner, followed by HOP_ACCOUNTS_LEN accounts per hop.
+// Tick and oracle accounts of every hop go after them in any order, they are matched by address.
+#[derive(Accounts)]
+pub struct SwapRoute<'info> {
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    pub owner: Signer<'info>,
+    #[account(constraint = &state.load()?.authority == program_authority.key @ InvalidAuthority)]
+    /// CHECK: Ignore
+    pub program_authority: AccountInfo<'info>,
+}
+
+struct RouteHop<'info> {
+    pool: AccountLoader<'info, Pool>,
+    tickmap: AccountLoader<'info, Tickmap>,
+    token_x: InterfaceAccount<'info, Mint>,
+    token_y: InterfaceAccount<'info, Mint>,
+    reserve_x: InterfaceAccount<'info, TokenAccount>,
+    reserve_y: InterfaceAccount<'info, TokenAccount>,
+    token_x_program: Interface<'info, TokenInterface>,
+    token_y_program: Interface<'info, TokenInterface>,
+    account_out: InterfaceAccount<'info, TokenAccount>,
+}
+
+impl<'info> RouteHop<'info> {
+    // same checks as the constraints of Swap
+    fn load(
+        accounts: &'info [AccountInfo<'info>],
+        owner: &Pubkey,
+        program_authority: &Pubkey,
+    ) -> Result<Self> {
+        let hop = RouteHop {
+            pool: AccountLoader::try_from(&accounts[0])?,
+            tickmap: AccountLoader::try_from(&accounts[1])?,
+            token_x: InterfaceAccount::try_from(&accounts[2])?,
+            token_y: InterfaceAccount::try_from(&accounts[3])?,
+            reserve_x: InterfaceAccount::try_from(&accounts[4])?,
+            reserve_y: InterfaceAccount::try_from(&accounts[5])?,
+            token_x_program: Interface::try_from(&accounts[6])?,
+            token_y_program: Interface::try_from(&accounts[7])?,
+            account_out: InterfaceAccount::try_from(&accounts[8])?,
+        };
+        let pool = hop.pool.load()?;
+
+        require_keys_eq!(hop.tickmap.key(), pool.tickmap, InvalidTickmap);
+        require_keys_eq!(hop.token_x.key(), pool.token_x, InvalidTokenAccount);
+        require_keys_eq!(hop.token_y.key(), pool.token_y, InvalidTokenAccount);
+        require_keys_eq!(
+            hop.reserve_x.key(),
+            pool.token_x_reserve,
+            InvalidTokenAccount
+        );
+        require_keys_eq!(
+            hop.reserve_y.key(),
+            pool.token_y_reserve,
+            InvalidTokenAccount
+        );
+        require_keys_eq!(hop.reserve_x.owner, *program_authority, InvalidAuthority);
+        require_keys_eq!(hop.reserve_y.owner, *program_authority, InvalidAuthority);
+        require_keys_eq!(
+            *hop.token_x.to_account_info().owner,
+            hop.token_x_program.key(),
+            InvalidTokenProgram
+        );
+        require_keys_eq!(
+            *hop.token_y.to_account_info().owner,
+            hop.token_y_program.key(),
+            InvalidTokenProgram
+        );
+        require_keys_eq!(hop.account_out.owner, *owner, InvalidOwner);
+
+        drop(pool);
+        Ok(hop)
+    }
+}
+
+fn transfer<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    if token_program.key() == token::ID {
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token::Transfer {
+                    from,
+                    to,
+                    authority,
+                },
+                signer,
+            ),
+            amount,
+        )
+    } else {
+        token_2022::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token_2022::TransferChecked {
+                    mint: mint.to_account_info(),
+                    from,
+                    to,
+                    authority,
+                },
+                signer,
+            ),
+            amount,
+            mint.decimals,
+        )
+    }
+}
+
+impl<'info> SwapRoute<'info> {
+    pub fn handler(
+        ctx: Context<'_, '_, 'info, 'info, SwapRoute<'info>>,
+        amount: u64,
+        min_amount_out: u64,
+        hops: u8,
+    ) -> Result<SwapRouteResult> {
+        msg!("INVARIANT: SWAP ROUTE");
+
+        require!(
+            (MIN_ROUTE_HOPS..=MAX_ROUTE_HOPS).contains(&hops),
+            ErrorCode::InvalidRoute
+        );
+        let hop_accounts_end = 1 + hops as usize * HOP_ACCOUNTS_LEN;
+        require!(
+            ctx.remaining_accounts.len() >= hop_accounts_end,
+            ErrorCode::InvalidRoute
+        );
+
+        let owner = ctx.accounts.owner.key();
+        let program_authority = ctx.accounts.program_authority.key();
+        let state = ctx.accounts.state.load()?;
+        let signer: &[&[&[u8]]] = get_signer!(state.nonce);
+
+        let mut account_in =
+            InterfaceAccount::<'info, TokenAccount>::try_from(&ctx.remaining_accounts[0])?;
+        require_keys_eq!(account_in.owner, owner, InvalidOwner);
+
+        let mut total_amount_in = 0;
+        let mut hop_amount = amount;
+        let mut fees: Vec<u64> = Vec::with_capacity(hops as usize);
+
+        for hop_accounts in ctx.remaining_accounts[1..hop_accounts_end].chunks(HOP_ACCOUNTS_LEN) {
+            let hop = RouteHop::load(hop_accounts, &owner, &program_authority)?;
+            let mut pool = hop.pool.load_mut()?;
+            let tickmap = hop.tickmap.load()?;
+
+            // direction follows from the token received in the previous hop
+            let x_to_y = if account_in.mint == pool.token_x {
+                true
+            } else if account_in.mint == pool.token_y {
+                false
+            } else {
+                return Err(ErrorCode::InvalidRoute.into());
+            };
+            let (token_out, sqrt_price_limit) = match x_to_y {
+                true => (pool.token_y, Price::new(MIN_SQRT_PRICE)),
+                false => (pool.token_x, Price::new(MAX_SQRT_PRICE)),
+            };
+            require_keys_eq!(hop.account_out.mint, token_out, InvalidRoute);
+
+            let (mint_in, reserve_in, program_in, mint_out, reserve_out, program_out) = match x_to_y
+            {
+                true => (
+                    &hop.token_x,
+                    &hop.reserve_x,
+                    &hop.token_x_program,
+                    &hop.token_y,
+                    &hop.reserve_y,
+                    &hop.token_y_program,
+                ),
+                false => (
+                    &hop.token_y,
+                    &hop.reserve_y,
+                    &hop.token_y_program,
+                    &hop.token_x,
+                    &hop.reserve_x,
+                    &hop.token_x_program,
+                ),
+            };
+            // pool swaps what reaches its reserve, transfer fees are on the owner
+            let swap_amount =
+                hop_amount - get_transfer_fee(&mint_in.to_account_info(), hop_amount)?;
+
+            record_oracle(&pool, ctx.remaining_accounts, get_current_timestamp(), true)?;
+
+            let sqrt_price_before = pool.sqrt_price;
+            let pool_key = hop.pool.key();
+
+            let mut tick_loaders: Vec<(i32, AccountLoader<Tick>)> = Vec::new();
+            let ComputeSwapResult {
+                amount_in,
+                amount_out,
+                fee,
+                referral_fee,
+                crossed_ticks,
+            } = compute_swap(
+                &mut pool,
+                &tickmap,
+                x_to_y,
+                swap_amount,
+                true,
+                sqrt_price_limit,
+                FixedPoint::from_integer(0),
+                |tick_index| {
+                    let loader = find_tick(ctx.remaining_accounts, &pool_key, tick_index)?;
+                    let tick = *loader.load()?;
+                    tick_loaders.push((tick_index, loader));
+                    Ok(tick)
+                },
+            )?;
+
+            // save crossed ticks
+            for crossed_tick in crossed_ticks.iter() {
+                let (_, loader) = tick_loaders
+                    .iter()
+                    .find(|(index, _)| *index == crossed_tick.index)
+                    .unwrap();
+                *loader.load_mut()? = *crossed_tick;
+            }
+            let ticks_crossed: Vec<i32> = crossed_ticks.iter().map(|tick| tick.index).collect();
+
+            let amount_in = amount_in
+                + TokenAmount(get_transfer_inverse_fee(
+                    &mint_in.to_account_info(),
+                    amount_in.0,
+                )?);
+            let amount_received = amount_out
+                - TokenAmount(get_transfer_fee(&mint_out.to_account_info(), amount_out.0)?);
+
+            if amount_received.0 == 0 {
+                return Err(ErrorCode::NoGainSwap.into());
+            }
+
+            emit!(SwapEvent {
+                pool: pool_key,
+                owner,
+                x_to_y,
+                amount_in: amount_in.0,
+                amount_out: amount_received.0,
+                fee: fee.0,
+                referral_fee: referral_fee.0,
+                ticks_crossed,
+                sqrt_price_before: sqrt_price_before.v,
+                sqrt_price_after: pool.sqrt_price.v,
+                tick_index_after: pool.current_tick_index,
+            });
+
+            // intermediate tokens pass through the owner's accounts
+            transfer(
+                program_in,
+                mint_in,
+                account_in.to_account_info(),
+                reserve_in.to_account_info(),
+                ctx.accounts.owner.to_account_info(),
+                &[],
+                amount_in.0,
+            )?;
+            transfer(
+                program_out,
+                mint_out,
+                reserve_out.to_account_info(),
+                hop.account_out.to_account_info(),
+                ctx.accounts.program_authority.clone(),
+                signer,
+                amount_out.0,
+            )?;
+
+            if fees.is_empty() {
+                total_amount_in = amount_in.0;
+            }
+            fees.push(fee.0);
+            hop_amount = amount_received.0;
+            account_in = hop.account_out.clone();
+        }
+
+        require!(
+            hop_amount >= min_amount_out,
+            ErrorCode::AmountOutBelowMinimum
+        );
+
+        Ok(SwapRouteResult {
+            total_amount_in,
+            total_amount_out: hop_amount,
+            fees,
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use crate::events::WithdrawProtocolFeeEvent;
 use crate::interfaces::send_tokens::SendTokens;
 use crate::structs::pool::Pool;
 use crate::structs::state::State;
@@ -139,6 +140,14 @@ impl<'info> WithdrawProtocolFee<'info> {
             _ => return Err(ErrorCode::InvalidTokenProgram.into()),
         };
 
+        emit!(WithdrawProtocolFeeEvent {
+            pool: self.pool.key(),
+            account_x: self.account_x.key(),
+            account_y: self.account_y.key(),
+            amount_x: pool.fee_protocol_token_x,
+            amount_y: pool.fee_protocol_token_y,
+        });
+
         pool.fee_protocol_token_x = 0;
         pool.fee_protocol_token_y = 0;
 
@@ -1,12 +1,15 @@
 pub mod decimals;
 mod errors;
+pub mod events;
 mod instructions;
 mod interfaces;
 mod log;
 mod macros;
-mod math;
+pub mod math;
 mod referral;
+pub mod results;
 pub mod structs;
+pub mod token_extensions;
 mod uint;
 mod util;
 
@@ -17,7 +20,9 @@ use crate::decimals::*;
 use errors::ErrorCode;
 use instructions::*;
 use math::*;
+use results::{ObserveResult, QuoteSwapResult, SwapResult, SwapRouteResult};
 use structs::{Pool, State};
+use token_extensions::*;
 use util::*;
 
 declare_id!("CsT21LCRqBfh4SCcNZXtWjRZ6xvYKvdpEBaytCVmWnVJ");
@@ -54,14 +59,59 @@ pub mod invariant {
         amount: u64,
         by_amount_in: bool, // whether amount specifies input or output
         sqrt_price_limit: u128,
-    ) -> Result<()> {
-        Swap::handler(ctx, x_to_y, amount, by_amount_in, sqrt_price_limit)
+        max_amount_in: u64,
+        min_amount_out: u64,
+    ) -> Result<SwapResult> {
+        Swap::handler(
+            ctx,
+            x_to_y,
+            amount,
+            by_amount_in,
+            sqrt_price_limit,
+            max_amount_in,
+            min_amount_out,
+        )
+    }
+
+    pub fn swap_route<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapRoute<'info>>,
+        amount: u64,
+        min_amount_out: u64,
+        hops: u8,
+    ) -> Result<SwapRouteResult> {
+        SwapRoute::handler(ctx, amount, min_amount_out, hops)
+    }
+
+    pub fn quote_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, QuoteSwap<'info>>,
+        x_to_y: bool,
+        amount: u64,
+        by_amount_in: bool,
+        sqrt_price_limit: u128,
+    ) -> Result<QuoteSwapResult> {
+        QuoteSwap::handler(ctx, x_to_y, amount, by_amount_in, sqrt_price_limit)
     }
 
     pub fn initialize_oracle(ctx: Context<InitializeOracle>) -> Result<()> {
         ctx.accounts.handler()
     }
 
+    pub fn grow_oracle(ctx: Context<GrowOracle>, size: u16) -> Result<()> {
+        ctx.accounts.handler(size)
+    }
+
+    pub fn migrate_oracle(ctx: Context<MigrateOracle>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    pub fn observe(ctx: Context<Observe>, seconds_agos: Vec<u32>) -> Result<ObserveResult> {
+        ctx.accounts.handler(seconds_agos)
+    }
+
     pub fn create_tick(ctx: Context<CreateTick>, index: i32) -> Result<()> {
         ctx.accounts.handler(index, ctx.bumps.tick)
     }
@@ -70,8 +120,8 @@ pub mod invariant {
         ctx.accounts.handler(ctx.bumps.position_list)
     }
 
-    pub fn create_position(
-        ctx: Context<CreatePosition>,
+    pub fn create_position<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreatePosition<'info>>,
         _lower_tick_index: i32,
         _upper_tick_index: i32,
         liquidity_delta: Liquidity,
@@ -83,21 +133,26 @@ pub mod invariant {
             slippage_limit_lower,
             slippage_limit_upper,
             ctx.bumps.position,
+            ctx.remaining_accounts,
         )
     }
 
-    pub fn remove_position(
-        ctx: Context<RemovePosition>,
+    pub fn remove_position<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemovePosition<'info>>,
         index: u32,
         lower_tick_index: i32,
         upper_tick_index: i32,
     ) -> Result<()> {
-        ctx.accounts
-            .handler(index, lower_tick_index, upper_tick_index)
+        ctx.accounts.handler(
+            index,
+            lower_tick_index,
+            upper_tick_index,
+            ctx.remaining_accounts,
+        )
     }
 
-    pub fn change_liquidity(
-        ctx: Context<ChangeLiquidity>,
+    pub fn change_liquidity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ChangeLiquidity<'info>>,
         _index: u32,
         liquidity_delta: Liquidity,
         add_liquidity: bool,
@@ -109,6 +164,7 @@ pub mod invariant {
             add_liquidity,
             slippage_limit_lower,
             slippage_limit_upper,
+            ctx.remaining_accounts,
         )
     }
 
@@ -154,6 +210,46 @@ pub mod invariant {
     pub fn change_fee_receiver(ctx: Context<ChangeFeeReceiver>) -> Result<()> {
         ctx.accounts.handler()
     }
+
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn propose_admin(ctx: Context<ProposeAdmin>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn create_referral_registry(
+        ctx: Context<CreateReferralRegistry>,
+        max_fee_share: FixedPoint,
+    ) -> Result<()> {
+        ctx.accounts
+            .handler(max_fee_share, ctx.bumps.referral_registry)
+    }
+
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn set_referrer(
+        ctx: Context<SetReferrer>,
+        referrer: Pubkey,
+        fee_share: FixedPoint,
+    ) -> Result<()> {
+        ctx.accounts.handler(referrer, fee_share)
+    }
+
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn remove_referrer(ctx: Context<RemoveReferrer>, referrer: Pubkey) -> Result<()> {
+        ctx.accounts.handler(referrer)
+    }
+
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn change_referral_fee_cap(
+        ctx: Context<ChangeReferralFeeCap>,
+        max_fee_share: FixedPoint,
+    ) -> Result<()> {
+        ctx.accounts.handler(max_fee_share)
+    }
 }
 
 fn admin(state_loader: &AccountLoader<State>, signer: &AccountInfo) -> Result<()> {
@@ -1,6 +1,7 @@
 use std::convert::TryInto;
 
 use crate::decimals::*;
+use crate::structs::oracle::Record;
 use crate::structs::pool::Pool;
 use crate::structs::tick::Tick;
 use crate::structs::tickmap::MAX_TICK;
@@ -501,6 +502,21 @@ pub fn calculate_max_liquidity_per_tick(tick_spacing: u16) -> Liquidity {
     }
 }
 
+// arithmetic mean of the tick between two records, rounded toward negative infinity
+pub fn calculate_twap_tick(start: &Record, end: &Record) -> i32 {
+    let elapsed = end.timestamp.checked_sub(start.timestamp).unwrap();
+    assert!(elapsed != 0, "records at the same timestamp");
+
+    end.tick_cumulative
+        .wrapping_sub(start.tick_cumulative)
+        .div_euclid(elapsed as i64) as i32
+}
+
+// mean of ticks is the geometric mean of prices
+pub fn calculate_twap_price_sqrt(start: &Record, end: &Record) -> Price {
+    calculate_price_sqrt(calculate_twap_tick(start, end))
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -1814,4 +1830,68 @@ mod tests {
                 .eq(&U256::from_str("C9F1D0F9A36142B8E4CBC87BC4509E926142668A984E1EB3F").unwrap()));
         }
     }
+
+    #[test]
+    fn test_calculate_twap_tick() {
+        let record = |timestamp: u64, tick_cumulative: i64| Record {
+            timestamp,
+            tick_cumulative,
+            ..Default::default()
+        };
+        // constant tick
+        {
+            let result = calculate_twap_tick(&record(100, 1000), &record(110, 1500));
+            assert_eq!(result, 50);
+        }
+        // mean of two ticks
+        {
+            // 10s at tick 20 and 30s at tick -60
+            let result = calculate_twap_tick(&record(0, 0), &record(40, 200 - 1800));
+            assert_eq!(result, -40);
+        }
+        // rounding
+        {
+            let result = calculate_twap_tick(&record(0, 0), &record(3, 10));
+            assert_eq!(result, 3);
+            let result = calculate_twap_tick(&record(0, 0), &record(3, -10));
+            assert_eq!(result, -4);
+        }
+        // wrapped cumulative
+        {
+            let result = calculate_twap_tick(&record(0, i64::MAX - 5), &record(5, i64::MIN + 19));
+            assert_eq!(result, 5);
+        }
+        // max tick
+        {
+            let result = calculate_twap_tick(
+                &record(0, 0),
+                &record(u32::MAX as u64, MAX_TICK as i64 * u32::MAX as i64),
+            );
+            assert_eq!(result, MAX_TICK);
+        }
+    }
+
+    #[test]
+    fn test_calculate_twap_price_sqrt() {
+        let record = |timestamp: u64, tick_cumulative: i64| Record {
+            timestamp,
+            tick_cumulative,
+            ..Default::default()
+        };
+        // constant price
+        {
+            let result = calculate_twap_price_sqrt(&record(0, 0), &record(10, 0));
+            assert_eq!(result, Price::from_integer(1));
+        }
+        // geometric mean of prices at ticks 100 and -100 is 1
+        {
+            let result = calculate_twap_price_sqrt(&record(0, 0), &record(20, 1000 - 1000));
+            assert_eq!(result, Price::from_integer(1));
+        }
+        // price at mean tick
+        {
+            let result = calculate_twap_price_sqrt(&record(0, 0), &record(20, 1000 + 3000));
+            assert_eq!(result, calculate_price_sqrt(200));
+        }
+    }
 }
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+// published with `set_return_data`, read back through `cpi::Return::get`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwapResult {
+    pub total_amount_in: u64, // fee included
+    pub total_amount_out: u64,
+    pub fee: u64,
+    pub sqrt_price: u128,
+}
+
+// cumulative values for each requested seconds_ago
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObserveResult {
+    pub tick_cumulatives: Vec<i64>,
+    pub seconds_per_liquidity_cumulatives: Vec<u128>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct QuoteSwapResult {
+    pub amount_in: u64, // fee included
+    pub amount_out: u64,
+    pub fee: u64,
+    pub ticks_crossed: Vec<i32>,
+    pub sqrt_price: u128,
+    pub tick_index: i32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SwapRouteResult {
+    pub total_amount_in: u64, // fee included
+    pub total_amount_out: u64,
+    pub fees: Vec<u64>, // in the input token of each hop
+}
@@ -3,6 +3,7 @@ pub mod oracle;
 pub mod pool;
 pub mod position;
 pub mod position_list;
+pub mod referral_registry;
 pub mod state;
 pub mod tick;
 pub mod tickmap;
@@ -12,6 +13,7 @@ pub use oracle::*;
 pub use pool::*;
 pub use position::*;
 pub use position_list::*;
+pub use referral_registry::*;
 pub use state::*;
 pub use tick::*;
 pub use tickmap::*;
@@ -1,5 +1,10 @@
 use crate::decimals::*;
+use crate::uint::U256;
+use crate::ErrorCode;
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use std::cell::{Ref, RefMut};
+use std::mem::size_of;
 
 const SIZE: u16 = 256; // UPDATE IN ARRAYS AS WELL!
 
@@ -10,29 +15,239 @@ pub struct Oracle {
     pub head: u16,
     pub amount: u16,
     pub size: u16,
+    pub size_next: u16, // buffer grows to it once head reaches the end
 }
 
 #[zero_copy(unsafe)]
 #[repr(packed)]
+#[derive(Default)]
 pub struct Record {
     pub timestamp: u64,
     pub price: Price,
+    pub tick_cumulative: i64,
+    pub seconds_per_liquidity_cumulative: FixedPoint,
+}
+
+// records are also stored outside of Oracle, in its extension
+unsafe impl bytemuck::Pod for Record {}
+unsafe impl bytemuck::Zeroable for Record {}
+
+impl Record {
+    // extends record to timestamp, tick has to be unchanged since it
+    // seconds per liquidity is taken from the pool, so it stays exact between records
+    pub fn transform(
+        &self,
+        timestamp: u64,
+        price: Price,
+        tick: i32,
+        seconds_per_liquidity_cumulative: FixedPoint,
+    ) -> Self {
+        let delta = timestamp.checked_sub(self.timestamp).unwrap();
+
+        Record {
+            timestamp,
+            price,
+            tick_cumulative: self
+                .tick_cumulative
+                .wrapping_add((tick as i64).wrapping_mul(delta as i64)),
+            seconds_per_liquidity_cumulative,
+        }
+    }
+
+    // record at timestamp between self and next one
+    pub fn interpolate(&self, next: &Record, timestamp: u64) -> Self {
+        let elapsed = next.timestamp - self.timestamp;
+        let offset = timestamp - self.timestamp;
+
+        // price recorded in the next record held since the previous one
+        let tick_delta = next.tick_cumulative.wrapping_sub(self.tick_cumulative) / elapsed as i64;
+        let seconds_per_liquidity_delta = next
+            .seconds_per_liquidity_cumulative
+            .unchecked_sub(self.seconds_per_liquidity_cumulative);
+
+        Record {
+            timestamp,
+            price: next.price,
+            tick_cumulative: self
+                .tick_cumulative
+                .wrapping_add(tick_delta.wrapping_mul(offset as i64)),
+            seconds_per_liquidity_cumulative: self.seconds_per_liquidity_cumulative.unchecked_add(
+                FixedPoint::new(
+                    (U256::from(seconds_per_liquidity_delta.v) * U256::from(offset)
+                        / U256::from(elapsed))
+                    .as_u128(),
+                ),
+            ),
+        }
+    }
 }
 
 impl Oracle {
-    pub fn add_record(&mut self, timestamp: u64, price: Price) {
-        let record = Record { timestamp, price };
+    // records without cumulative values and no size_next, before the extension was added
+    pub const LEGACY_SPACE: usize =
+        8 + SIZE as usize * (size_of::<u64>() + size_of::<Price>()) + 3 * size_of::<u16>();
+
+    // records above SIZE are stored in the account data right after the struct
+    pub fn space(size: u16) -> usize {
+        8 + size_of::<Oracle>() + (size.saturating_sub(SIZE) as usize) * size_of::<Record>()
+    }
+
+    pub fn load_with_extension<'a>(
+        account: &'a AccountInfo,
+    ) -> Result<(Ref<'a, Oracle>, Ref<'a, [Record]>)> {
+        Self::check_account(account)?;
+        let data = account.try_borrow_data()?;
+
+        Ok(Ref::map_split(data, |data| {
+            let (oracle, extension) = data[8..].split_at(size_of::<Oracle>());
+            let length = extension.len() - extension.len() % size_of::<Record>();
+            (
+                bytemuck::from_bytes(oracle),
+                bytemuck::cast_slice(&extension[..length]),
+            )
+        }))
+    }
+
+    pub fn load_with_extension_mut<'a>(
+        account: &'a AccountInfo,
+    ) -> Result<(RefMut<'a, Oracle>, RefMut<'a, [Record]>)> {
+        Self::check_account(account)?;
+        let data = account.try_borrow_mut_data()?;
+
+        Ok(RefMut::map_split(data, |data| {
+            let (oracle, extension) = data[8..].split_at_mut(size_of::<Oracle>());
+            let length = extension.len() - extension.len() % size_of::<Record>();
+            (
+                bytemuck::from_bytes_mut(oracle),
+                bytemuck::cast_slice_mut(&mut extension[..length]),
+            )
+        }))
+    }
+
+    fn check_account(account: &AccountInfo) -> Result<()> {
+        if account.owner != &crate::ID {
+            return Err(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram.into());
+        }
+        let data = account.try_borrow_data()?;
+        if data.len() < Self::space(SIZE) || data[..8] != Oracle::DISCRIMINATOR {
+            return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        Ok(())
+    }
+
+    fn get(&self, extension: &[Record], index: u16) -> Record {
+        match index < SIZE {
+            true => self.data[index as usize],
+            false => extension[(index - SIZE) as usize],
+        }
+    }
+
+    fn set(&mut self, extension: &mut [Record], index: u16, record: Record) {
+        match index < SIZE {
+            true => self.data[index as usize] = record,
+            false => extension[(index - SIZE) as usize] = record,
+        }
+    }
+
+    // index of the i-th oldest record
+    fn index_at(&self, i: u16) -> u16 {
+        ((self.head as u32 + 1 + self.size as u32 - self.amount as u32 + i as u32)
+            % self.size as u32) as u16
+    }
+
+    pub fn add_record(&mut self, extension: &mut [Record], record: Record) {
+        // new slots are used only after wrapping, so records stay in order
+        if self.head == self.size - 1 && self.size_next > self.size {
+            self.size = self.size_next;
+        }
 
         self.head = (self.head + 1) % self.size;
-        self.data[self.head as usize] = record;
+        self.set(extension, self.head, record);
 
         if self.amount < self.size {
             self.amount += 1;
         }
     }
 
+    // at most one record per timestamp, later calls within it are ignored
+    pub fn record(
+        &mut self,
+        extension: &mut [Record],
+        timestamp: u64,
+        price: Price,
+        tick: i32,
+        seconds_per_liquidity: FixedPoint,
+    ) {
+        let record = match self.amount {
+            0 => Record {
+                timestamp,
+                price,
+                seconds_per_liquidity_cumulative: seconds_per_liquidity,
+                ..Default::default()
+            },
+            _ => {
+                let last = self.get(extension, self.head);
+                if { last.timestamp } >= timestamp {
+                    return;
+                }
+                last.transform(timestamp, price, tick, seconds_per_liquidity)
+            }
+        };
+
+        self.add_record(extension, record);
+    }
+
+    // cumulative values seconds_ago before timestamp, current state extends the newest record
+    pub fn observe(
+        &self,
+        extension: &[Record],
+        timestamp: u64,
+        seconds_ago: u64,
+        price: Price,
+        tick: i32,
+        seconds_per_liquidity: FixedPoint,
+    ) -> Result<Record> {
+        require!(self.amount != 0, ErrorCode::OracleEmpty);
+        let target = timestamp
+            .checked_sub(seconds_ago)
+            .ok_or(ErrorCode::ObservationTooOld)?;
+
+        let newest = self.get(extension, self.head);
+        if target >= newest.timestamp {
+            if target == newest.timestamp {
+                return Ok(newest);
+            }
+            let current = newest.transform(timestamp, price, tick, seconds_per_liquidity);
+            return Ok(match target == timestamp {
+                true => current,
+                false => newest.interpolate(&current, target),
+            });
+        }
+
+        let oldest = self.get(extension, self.index_at(0));
+        require!(target >= oldest.timestamp, ErrorCode::ObservationTooOld);
+
+        // oldest is at or before target, newest after it
+        let (mut low, mut high) = (0, self.amount - 1);
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            match { self.get(extension, self.index_at(mid)).timestamp } <= target {
+                true => low = mid,
+                false => high = mid,
+            }
+        }
+
+        let before = self.get(extension, self.index_at(low));
+        let after = self.get(extension, self.index_at(high));
+        Ok(match target == before.timestamp {
+            true => before,
+            false => before.interpolate(&after, target),
+        })
+    }
+
     pub fn init(&mut self) {
         self.size = SIZE;
+        self.size_next = SIZE;
         self.head = SIZE - 1;
     }
 }
@@ -41,6 +256,18 @@ impl Oracle {
 mod tests {
     use super::*;
 
+    fn empty_oracle() -> Oracle {
+        let mut oracle = Oracle {
+            data: [Record::default(); 256],
+            head: 0,
+            amount: 0,
+            size: 0,
+            size_next: 0,
+        };
+        oracle.init();
+        oracle
+    }
+
     #[test]
     fn add_recording() {
         print!("{}", std::mem::align_of::<Oracle>());
@@ -48,10 +275,12 @@ mod tests {
             data: [Record {
                 price: Price::from_integer(0),
                 timestamp: 0,
+                ..Default::default()
             }; 256],
             head: SIZE - 1,
             amount: 0,
             size: SIZE,
+            size_next: SIZE,
         };
         assert_eq!({ oracle.size }, SIZE);
 
@@ -59,7 +288,14 @@ mod tests {
 
         // fill
         while index < SIZE as u64 {
-            oracle.add_record(index, Price::new(index as u128));
+            oracle.add_record(
+                &mut [],
+                Record {
+                    timestamp: index,
+                    price: Price::new(index as u128),
+                    ..Default::default()
+                },
+            );
 
             assert_eq!(oracle.head as u64, index);
             assert_eq!(oracle.amount as u64, index + 1);
@@ -71,7 +307,14 @@ mod tests {
 
         // second fill
         while index < 2 * SIZE as u64 {
-            oracle.add_record(index, Price::new(index as u128));
+            oracle.add_record(
+                &mut [],
+                Record {
+                    timestamp: index,
+                    price: Price::new(index as u128),
+                    ..Default::default()
+                },
+            );
 
             assert_eq!(oracle.head as u64, index - SIZE as u64);
             assert_eq!(oracle.amount as u64, SIZE as u64);
@@ -81,4 +324,212 @@ mod tests {
             index += 1;
         }
     }
+
+    #[test]
+    fn legacy_space() {
+        // 256 records of timestamp and price, head, amount and size
+        assert_eq!(Oracle::LEGACY_SPACE, 8 + 256 * 24 + 6);
+        assert!(Oracle::space(SIZE) - Oracle::LEGACY_SPACE <= 10240);
+    }
+
+    #[test]
+    fn record_once_per_timestamp() {
+        let mut oracle = empty_oracle();
+        let liquidity = FixedPoint::new(0);
+
+        // first record is always added
+        oracle.record(&mut [], 0, Price::new(1), 0, liquidity);
+        assert_eq!({ oracle.amount }, 1);
+        assert_eq!({ oracle.data[oracle.head as usize].price.v }, 1);
+
+        // same timestamp keeps the first price
+        oracle.record(&mut [], 0, Price::new(2), 0, liquidity);
+        assert_eq!({ oracle.amount }, 1);
+        assert_eq!({ oracle.data[oracle.head as usize].price.v }, 1);
+
+        // next timestamp
+        oracle.record(&mut [], 5, Price::new(3), 0, liquidity);
+        assert_eq!({ oracle.amount }, 2);
+        assert_eq!({ oracle.data[oracle.head as usize].timestamp }, 5);
+        assert_eq!({ oracle.data[oracle.head as usize].price.v }, 3);
+
+        // older timestamp is ignored
+        oracle.record(&mut [], 4, Price::new(4), 0, liquidity);
+        assert_eq!({ oracle.amount }, 2);
+        assert_eq!({ oracle.data[oracle.head as usize].timestamp }, 5);
+    }
+
+    #[test]
+    fn record_cumulatives() {
+        let mut oracle = empty_oracle();
+
+        // tick passed along a record is the one held since the previous record
+        oracle.record(&mut [], 100, Price::new(1), 0, FixedPoint::new(0));
+        // tick 10 held for 8 seconds with liquidity of 4
+        oracle.record(&mut [], 108, Price::new(1), 10, FixedPoint::from_integer(2));
+        // tick -20 held for 2 seconds, no liquidity
+        oracle.record(
+            &mut [],
+            110,
+            Price::new(1),
+            -20,
+            FixedPoint::from_integer(2),
+        );
+
+        let records = [oracle.data[0], oracle.data[1], oracle.data[2]];
+        assert_eq!({ records[0].tick_cumulative }, 0);
+        assert_eq!({ records[1].tick_cumulative }, 80);
+        assert_eq!({ records[2].tick_cumulative }, 40);
+
+        assert_eq!(
+            { records[0].seconds_per_liquidity_cumulative },
+            FixedPoint::new(0)
+        );
+        assert_eq!(
+            { records[1].seconds_per_liquidity_cumulative },
+            FixedPoint::from_integer(2)
+        );
+        assert_eq!(
+            { records[2].seconds_per_liquidity_cumulative },
+            FixedPoint::from_integer(2)
+        );
+    }
+
+    #[test]
+    fn grow_buffer() {
+        let mut oracle = empty_oracle();
+        let mut extension = [Record::default(); 44];
+        let size_next = SIZE + 44;
+
+        // growing before first record uses the new slots first
+        {
+            let mut oracle = empty_oracle();
+            let mut extension = [Record::default(); 44];
+            oracle.size_next = size_next;
+            oracle.record(&mut extension, 1, Price::new(1), 0, FixedPoint::new(0));
+            assert_eq!({ oracle.size }, size_next);
+            assert_eq!({ oracle.head }, SIZE);
+            assert_eq!({ extension[0].timestamp }, 1);
+            assert_eq!({ oracle.get(&extension, oracle.index_at(0)).timestamp }, 1);
+        }
+
+        for timestamp in 1..=SIZE as u64 {
+            oracle.record(
+                &mut extension,
+                timestamp,
+                Price::new(1),
+                1,
+                FixedPoint::new(0),
+            );
+        }
+        assert_eq!({ oracle.amount }, SIZE);
+        oracle.size_next = size_next;
+
+        // fills the new slots before wrapping
+        for timestamp in SIZE as u64 + 1..=size_next as u64 {
+            oracle.record(
+                &mut extension,
+                timestamp,
+                Price::new(1),
+                1,
+                FixedPoint::new(0),
+            );
+            assert_eq!(oracle.head as u64, timestamp - 1);
+        }
+        assert_eq!({ oracle.size }, size_next);
+        assert_eq!({ oracle.amount }, size_next);
+        assert_eq!({ extension[43].timestamp }, size_next as u64);
+        assert_eq!({ oracle.get(&extension, oracle.index_at(0)).timestamp }, 1);
+
+        // wraps with the new size
+        oracle.record(
+            &mut extension,
+            size_next as u64 + 1,
+            Price::new(1),
+            1,
+            FixedPoint::new(0),
+        );
+        assert_eq!({ oracle.head }, 0);
+        assert_eq!({ oracle.amount }, size_next);
+        assert_eq!({ oracle.get(&extension, oracle.index_at(0)).timestamp }, 2);
+    }
+
+    #[test]
+    fn observe() {
+        let mut oracle = empty_oracle();
+        let seconds_per_liquidity =
+            |timestamp: u64| FixedPoint::from_integer(timestamp - 100) / Liquidity::from_integer(2);
+        let current = seconds_per_liquidity(130);
+
+        // empty
+        {
+            let result = oracle.observe(&[], 10, 0, Price::new(1), 0, current);
+            assert!(result.is_err());
+        }
+
+        oracle.record(&mut [], 100, Price::new(1), 0, seconds_per_liquidity(100));
+        oracle.record(&mut [], 110, Price::new(2), 10, seconds_per_liquidity(110));
+        oracle.record(&mut [], 120, Price::new(3), -30, seconds_per_liquidity(120));
+
+        // exact record
+        {
+            let result = oracle
+                .observe(&[], 130, 20, Price::new(4), 5, current)
+                .unwrap();
+            assert_eq!({ result.timestamp }, 110);
+            assert_eq!({ result.tick_cumulative }, 100);
+            assert_eq!({ result.price }, Price::new(2));
+        }
+        // between records
+        {
+            let result = oracle
+                .observe(&[], 130, 26, Price::new(4), 5, current)
+                .unwrap();
+            assert_eq!({ result.timestamp }, 104);
+            assert_eq!({ result.tick_cumulative }, 40);
+            assert_eq!({ result.price }, Price::new(2));
+            assert_eq!(
+                { result.seconds_per_liquidity_cumulative },
+                FixedPoint::from_integer(2)
+            );
+
+            let result = oracle
+                .observe(&[], 130, 15, Price::new(4), 5, current)
+                .unwrap();
+            assert_eq!({ result.timestamp }, 115);
+            assert_eq!({ result.tick_cumulative }, 100 - 150);
+        }
+        // after newest record
+        {
+            let result = oracle
+                .observe(&[], 130, 0, Price::new(4), 5, current)
+                .unwrap();
+            assert_eq!({ result.timestamp }, 130);
+            assert_eq!({ result.tick_cumulative }, 100 - 300 + 50);
+            assert_eq!({ result.price }, Price::new(4));
+            assert_eq!(
+                { result.seconds_per_liquidity_cumulative },
+                FixedPoint::from_integer(15)
+            );
+        }
+        // between newest record and the current state
+        {
+            let result = oracle
+                .observe(&[], 130, 5, Price::new(4), 5, current)
+                .unwrap();
+            assert_eq!({ result.timestamp }, 125);
+            assert_eq!({ result.tick_cumulative }, 100 - 300 + 25);
+            assert_eq!(
+                { result.seconds_per_liquidity_cumulative },
+                seconds_per_liquidity(125)
+            );
+        }
+        // before oldest record
+        {
+            let result = oracle.observe(&[], 130, 31, Price::new(4), 5, current);
+            assert!(result.is_err());
+            let result = oracle.observe(&[], 130, 131, Price::new(4), 5, current);
+            assert!(result.is_err());
+        }
+    }
 }
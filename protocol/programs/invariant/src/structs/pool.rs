@@ -41,9 +41,12 @@ impl Pool {
         in_x: bool,
     ) -> TokenAmount {
         let protocol_fee = TokenAmount::from_decimal_up(amount.big_mul_up(self.protocol_fee));
+        // referrer is paid from what is left after the protocol fee
         let ref_fee = match ref_percentage.is_zero() {
             true => TokenAmount(0),
-            false => TokenAmount::from_decimal(amount.big_mul(ref_percentage)),
+            false => {
+                TokenAmount::from_decimal(amount.big_mul(ref_percentage)).min(amount - protocol_fee)
+            }
         };
         let pool_fee = amount - protocol_fee - ref_fee;
 
@@ -91,6 +94,17 @@ impl Pool {
         self.last_timestamp = current_timestamp;
     }
 
+    // global value at timestamp, liquidity hasn't changed since the last update
+    pub fn seconds_per_liquidity_at(&self, timestamp: u64) -> FixedPoint {
+        match self.liquidity.is_zero() {
+            true => self.seconds_per_liquidity_global,
+            false => self.seconds_per_liquidity_global.unchecked_add(
+                FixedPoint::from_integer(timestamp.checked_sub(self.last_timestamp).unwrap())
+                    / self.liquidity,
+            ),
+        }
+    }
+
     pub fn set_oracle(&mut self, address: Pubkey) {
         self.oracle_address = address;
         self.oracle_initialized = true;
@@ -187,6 +201,19 @@ mod tests {
             assert_eq!({ pool.fee_protocol_token_y }, 0);
             assert_eq!(ref_fee, TokenAmount(0));
         }
+        // protocol and referral shares above the whole fee
+        {
+            let mut pool = Pool {
+                protocol_fee: FixedPoint::from_scale(8, 1),
+                ..pool
+            };
+            let amount = TokenAmount::new(10);
+            let ref_fee = pool.add_fee(amount, FixedPoint::from_scale(5, 1), true);
+
+            assert_eq!({ pool.fee_growth_global_x }, FeeGrowth::new(0));
+            assert_eq!({ pool.fee_protocol_token_x }, 8);
+            assert_eq!(ref_fee, TokenAmount(2));
+        }
     }
 
     #[test]
@@ -230,4 +257,38 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_seconds_per_liquidity_at() {
+        let mut pool = Pool {
+            liquidity: Liquidity::from_integer(4),
+            seconds_per_liquidity_global: FixedPoint::from_integer(1),
+            last_timestamp: 100,
+            ..Default::default()
+        };
+        assert_eq!(
+            pool.seconds_per_liquidity_at(100),
+            FixedPoint::from_integer(1)
+        );
+        assert_eq!(
+            pool.seconds_per_liquidity_at(108),
+            FixedPoint::from_integer(3)
+        );
+
+        // liquidity change in between, no oracle record needed to stay exact
+        pool.update_seconds_per_liquidity_global(108);
+        pool.update_liquidity_safely(Liquidity::from_integer(4), true)
+            .unwrap();
+        assert_eq!(
+            pool.seconds_per_liquidity_at(116),
+            FixedPoint::from_integer(4)
+        );
+
+        // no liquidity
+        pool.liquidity = Liquidity::new(0);
+        assert_eq!(
+            pool.seconds_per_liquidity_at(200),
+            FixedPoint::from_integer(3)
+        );
+    }
 }
@@ -0,0 +1,193 @@
+use crate::decimals::*;
+use crate::{account_size, ErrorCode};
+use anchor_lang::prelude::*;
+
+pub const MAX_REFERRERS: usize = 32; // UPDATE IN ARRAYS AS WELL!
+pub const MAX_FEE_SHARE: u128 = 500_000_000_000; // 50%, rest of the swap fee is left to the protocol and LPs
+
+#[account(zero_copy(unsafe))]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug, InitSpace)]
+pub struct ReferralRegistry {
+    pub referrers: [Referrer; 32],
+    pub count: u8,
+    pub max_fee_share: FixedPoint, // caps fee share of every referrer
+    pub bump: u8,
+}
+
+#[zero_copy(unsafe)]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug, InitSpace)]
+pub struct Referrer {
+    pub owner: Pubkey, // owner of the referral token account
+    pub fee_share: FixedPoint,
+}
+
+account_size!(ReferralRegistry);
+
+impl ReferralRegistry {
+    pub fn validate_max_fee_share(max_fee_share: FixedPoint) -> Result<()> {
+        require!(
+            max_fee_share <= FixedPoint::new(MAX_FEE_SHARE),
+            ErrorCode::InvalidReferralFee
+        );
+        Ok(())
+    }
+
+    fn position(&self, owner: Pubkey) -> Option<usize> {
+        self.referrers[..self.count as usize]
+            .iter()
+            .position(|referrer| referrer.owner == owner)
+    }
+
+    // share of the swap fee going to a registered referrer
+    pub fn fee_share(&self, owner: Pubkey) -> Option<FixedPoint> {
+        let index = self.position(owner)?;
+        Some(self.referrers[index].fee_share.min(self.max_fee_share))
+    }
+
+    pub fn set_referrer(&mut self, owner: Pubkey, fee_share: FixedPoint) -> Result<()> {
+        require!(
+            fee_share <= { self.max_fee_share },
+            ErrorCode::InvalidReferralFee
+        );
+
+        let index = match self.position(owner) {
+            Some(index) => index,
+            None => {
+                require!(
+                    (self.count as usize) < MAX_REFERRERS,
+                    ErrorCode::ReferralRegistryFull
+                );
+                self.count += 1;
+                self.count as usize - 1
+            }
+        };
+        self.referrers[index] = Referrer { owner, fee_share };
+
+        Ok(())
+    }
+
+    pub fn remove_referrer(&mut self, owner: Pubkey) -> Result<()> {
+        let index = self.position(owner).ok_or(ErrorCode::ReferrerNotFound)?;
+
+        // last referrer takes place of the removed one
+        self.count -= 1;
+        self.referrers[index] = self.referrers[self.count as usize];
+        self.referrers[self.count as usize] = Referrer::default();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_referrer() {
+        let mut registry = ReferralRegistry {
+            max_fee_share: FixedPoint::from_scale(2, 1),
+            ..Default::default()
+        };
+        let owner = Pubkey::new_unique();
+
+        // add
+        registry
+            .set_referrer(owner, FixedPoint::from_scale(1, 1))
+            .unwrap();
+        assert_eq!({ registry.count }, 1);
+        assert_eq!(
+            registry.fee_share(owner),
+            Some(FixedPoint::from_scale(1, 1))
+        );
+        // update
+        registry
+            .set_referrer(owner, FixedPoint::from_scale(2, 1))
+            .unwrap();
+        assert_eq!({ registry.count }, 1);
+        assert_eq!(
+            registry.fee_share(owner),
+            Some(FixedPoint::from_scale(2, 1))
+        );
+        // over the cap
+        let result = registry.set_referrer(owner, FixedPoint::from_scale(3, 1));
+        assert!(result.is_err());
+        // unknown owner
+        assert_eq!(registry.fee_share(Pubkey::new_unique()), None);
+        // full
+        for _ in 1..MAX_REFERRERS {
+            registry
+                .set_referrer(Pubkey::new_unique(), FixedPoint::from_scale(1, 1))
+                .unwrap();
+        }
+        let result = registry.set_referrer(Pubkey::new_unique(), FixedPoint::from_scale(1, 1));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_max_fee_share() {
+        ReferralRegistry::validate_max_fee_share(FixedPoint::new(0)).unwrap();
+        ReferralRegistry::validate_max_fee_share(FixedPoint::new(MAX_FEE_SHARE)).unwrap();
+        let result = ReferralRegistry::validate_max_fee_share(FixedPoint::new(MAX_FEE_SHARE + 1));
+        assert!(result.is_err());
+        let result = ReferralRegistry::validate_max_fee_share(FixedPoint::from_integer(1));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_fee_share_capped() {
+        let mut registry = ReferralRegistry {
+            max_fee_share: FixedPoint::from_scale(2, 1),
+            ..Default::default()
+        };
+        let owner = Pubkey::new_unique();
+        registry
+            .set_referrer(owner, FixedPoint::from_scale(2, 1))
+            .unwrap();
+
+        // lowered cap applies to already registered referrers
+        registry.max_fee_share = FixedPoint::from_scale(1, 1);
+        assert_eq!(
+            registry.fee_share(owner),
+            Some(FixedPoint::from_scale(1, 1))
+        );
+    }
+
+    #[test]
+    fn test_remove_referrer() {
+        let mut registry = ReferralRegistry {
+            max_fee_share: FixedPoint::from_scale(2, 1),
+            ..Default::default()
+        };
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        let third = Pubkey::new_unique();
+        registry
+            .set_referrer(first, FixedPoint::from_scale(1, 1))
+            .unwrap();
+        registry
+            .set_referrer(second, FixedPoint::from_scale(15, 2))
+            .unwrap();
+        registry
+            .set_referrer(third, FixedPoint::from_scale(2, 1))
+            .unwrap();
+
+        registry.remove_referrer(first).unwrap();
+        assert_eq!({ registry.count }, 2);
+        assert_eq!(registry.fee_share(first), None);
+        assert_eq!(
+            registry.fee_share(second),
+            Some(FixedPoint::from_scale(15, 2))
+        );
+        assert_eq!(
+            registry.fee_share(third),
+            Some(FixedPoint::from_scale(2, 1))
+        );
+        assert_eq!(registry.referrers[2], Referrer::default());
+
+        // not registered
+        let result = registry.remove_referrer(first);
+        assert!(result.is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::account_size;
+use std::mem::offset_of;
 
 #[account(zero_copy(unsafe))]
 #[repr(packed)]
@@ -10,6 +11,24 @@ pub struct State {
     pub nonce: u8,
     pub authority: Pubkey,
     pub bump: u8,
+    pub pending_admin: Pubkey,
 }
 
 account_size!(State);
+
+impl State {
+    // states created before the admin transfer end right before pending_admin
+    pub const LEGACY_LEN: usize = 8 + offset_of!(State, pending_admin);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_len() {
+        // admin, nonce, authority and bump
+        assert_eq!(State::LEGACY_LEN, 8 + 32 + 1 + 32 + 1);
+        assert_eq!(State::LEN, State::LEGACY_LEN + 32);
+    }
+}
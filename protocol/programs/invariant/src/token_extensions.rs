@@ -0,0 +1,57 @@
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as Token2022Mint;
+
+// extensions that keep transferred amounts predictable
+const SUPPORTED_MINT_EXTENSIONS: [ExtensionType; 3] = [
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+];
+
+// mints of the legacy token program have no extensions
+pub fn has_supported_extensions(mint: &AccountInfo) -> Result<bool> {
+    if *mint.owner != token_2022::ID {
+        return Ok(true);
+    }
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+    Ok(mint
+        .get_extension_types()?
+        .iter()
+        .all(|extension| SUPPORTED_MINT_EXTENSIONS.contains(extension)))
+}
+
+fn get_transfer_fee_config(mint: &AccountInfo) -> Result<Option<TransferFeeConfig>> {
+    if *mint.owner != token_2022::ID {
+        return Ok(None);
+    }
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+    Ok(mint.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+// part of the amount withheld by the transfer fee extension
+pub fn get_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    match get_transfer_fee_config(mint)? {
+        Some(config) => config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(error!(ErrorCode::TransferFeeOverflow)),
+        None => Ok(0),
+    }
+}
+
+// fee that has to be sent on top of the amount for all of it to arrive
+pub fn get_transfer_inverse_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    match get_transfer_fee_config(mint)? {
+        Some(config) => config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(error!(ErrorCode::TransferFeeOverflow)),
+        None => Ok(0),
+    }
+}
@@ -3,6 +3,7 @@ use std::cell::RefMut;
 use std::convert::TryInto;
 
 use crate::math::calculate_price_sqrt;
+use crate::structs::oracle::Oracle;
 use crate::structs::pool::Pool;
 use crate::structs::tick::Tick;
 use crate::structs::tickmap::Tickmap;
@@ -114,6 +115,38 @@ pub fn cross_tick(tick: &mut RefMut<Tick>, pool: &mut Pool, current_timestamp: u
     Ok(())
 }
 
+// oracle is passed in remaining accounts, pools without it are not affected
+pub fn record_oracle<'info>(
+    pool: &Pool,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    current_timestamp: u64,
+    required: bool,
+) -> Result<()> {
+    if !pool.oracle_initialized {
+        return Ok(());
+    }
+    match remaining_accounts
+        .iter()
+        .find(|account| *account.key == pool.oracle_address)
+    {
+        Some(account) => {
+            let (mut oracle, mut extension) = Oracle::load_with_extension_mut(account)?;
+            oracle.record(
+                &mut extension,
+                current_timestamp,
+                pool.sqrt_price,
+                pool.current_tick_index,
+                pool.seconds_per_liquidity_at(current_timestamp),
+            );
+            Ok(())
+        }
+        None => match required {
+            true => Err(ErrorCode::InvalidOracle.into()),
+            false => Ok(()),
+        },
+    }
+}
+
 pub fn get_current_timestamp() -> u64 {
     Clock::get().unwrap().unix_timestamp.try_into().unwrap()
 }
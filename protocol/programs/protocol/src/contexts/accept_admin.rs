@@ -0,0 +1,23 @@
+use crate::states::{DerivedAccountIdentifier, State};
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptAdminCtx<'info> {
+    #[account(mut,
+        seeds = [State::IDENT],
+        bump = state.load()?.bump,
+        constraint = state.load()?.pending_admin == new_admin.key() @ InvalidAuthority
+    )]
+    pub state: AccountLoader<'info, State>,
+    pub new_admin: Signer<'info>,
+}
+
+impl AcceptAdminCtx<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        let state = &mut self.state.load_mut()?;
+        state.admin = self.new_admin.key();
+        state.pending_admin = Pubkey::default();
+        Ok(())
+    }
+}
@@ -1,10 +1,18 @@
 use std::cell::RefMut;
 use std::u32;
 
-use crate::math::{compute_lp_share_change, ComputeLpShareChangeResult};
+use crate::events::{BurnLpTokenEvent, ZapOutEvent};
+use crate::math::{
+    compute_lp_share_change, compute_performance_fee, compute_protocol_fee,
+    ComputeLpShareChangeResult, SharePrice, TickRange,
+};
+use crate::results::BurnLpTokenResult;
 use crate::states::{DerivedAccountIdentifier, LpPool, State, LP_TOKEN_IDENT};
 use crate::{decimals::*, try_from};
-use crate::{get_signer, ErrorCode::*};
+use crate::{
+    get_signer,
+    ErrorCode::{self, *},
+};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_2022::{Burn, Token2022};
@@ -13,14 +21,38 @@ use anchor_spl::{
     token::{self},
     token_2022,
 };
-use invariant::cpi::accounts::ChangeLiquidity;
+use invariant::cpi::accounts::{ChangeLiquidity, ClaimFee, Swap};
 use invariant::decimals::Liquidity as InvLiquidity;
 use invariant::structs::PositionList;
+use invariant::token_extensions::{get_transfer_fee, get_transfer_inverse_fee};
 use invariant::{
     cpi::accounts::RemovePosition,
     structs::{Pool, Position},
 };
 
+// withdrawn tokens reach the reserve reduced by the transfer fee, which the user bears,
+// re-deposited tokens are pulled with the fee on top, which the leftovers cover
+fn settle_transfer_fee(
+    mint: &AccountInfo,
+    reserve_before: u64,
+    reserve_after: u64,
+    transfer: TokenAmount,
+) -> Result<(TokenAmount, TokenAmount)> {
+    match reserve_after.checked_sub(reserve_before) {
+        Some(withdrawn) => {
+            let withheld = get_transfer_inverse_fee(mint, withdrawn)?;
+            Ok((
+                TokenAmount::new(transfer.0.saturating_sub(withheld)),
+                TokenAmount::new(0),
+            ))
+        }
+        None => {
+            let pulled = reserve_before - reserve_after;
+            Ok((transfer, TokenAmount::new(get_transfer_fee(mint, pulled)?)))
+        }
+    }
+}
+
 #[derive(Accounts)]
 pub struct BurnLpTokenCtx<'info> {
     #[account(mut)]
@@ -35,7 +67,7 @@ pub struct BurnLpTokenCtx<'info> {
     #[account(constraint = &state.load()?.program_authority == program_authority.key @ InvalidAuthority)]
     pub program_authority: AccountInfo<'info>,
     #[account(mut,
-        seeds = [LpPool::IDENT, token_x.key().as_ref(), token_y.key().as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes()],
+        seeds = [LpPool::IDENT, token_x.key().as_ref(), token_y.key().as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes(), &lp_pool.load()?.initial_range_seed()],
         bump=lp_pool.load()?.bump,
     )]
     pub lp_pool: AccountLoader<'info, LpPool>,
@@ -44,7 +76,7 @@ pub struct BurnLpTokenCtx<'info> {
     pub last_position_lp_pool: AccountLoader<'info, LpPool>,
     #[account(mut,
         // validated in validate_token_lp
-        // seeds = [LP_TOKEN_IDENT, token_x.key().as_ref(), token_y.key().as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes()],
+        // seeds = [LP_TOKEN_IDENT, token_x.key().as_ref(), token_y.key().as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes(), &lp_pool.load()?.initial_range_seed()],
         // bump=lp_pool.load()?.token_bump,
     )]
     pub token_lp: Box<InterfaceAccount<'info, Mint>>,
@@ -92,6 +124,9 @@ pub struct BurnLpTokenCtx<'info> {
         // constraint = pool.load()?.fee.v == lp_pool.load()?.fee.v
     )]
     pub pool: AccountLoader<'info, Pool>,
+    /// CHECK: passed to Invariant, required once the pool has an oracle
+    #[account(mut, constraint = oracle.key() == pool.load()?.oracle_address @ InvalidOracle)]
+    pub oracle: Option<UncheckedAccount<'info>>,
     #[account(mut,
         seeds = [b"positionlistv1", program_authority.key().as_ref()],
         seeds::program = invariant::ID,
@@ -125,6 +160,10 @@ pub struct BurnLpTokenCtx<'info> {
 }
 
 impl<'info> BurnLpTokenCtx<'info> {
+    fn oracle_accounts(&self) -> Vec<AccountInfo<'info>> {
+        self.oracle.iter().map(|o| o.to_account_info()).collect()
+    }
+
     pub fn burn_lp(&self) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
         CpiContext::new(
             self.token_program.to_account_info(),
@@ -209,6 +248,30 @@ impl<'info> BurnLpTokenCtx<'info> {
                 token_y_program: self.token_y_program.to_account_info(),
             },
         )
+        .with_remaining_accounts(self.oracle_accounts())
+    }
+
+    pub fn claim_fee(&self) -> CpiContext<'_, '_, '_, 'info, ClaimFee<'info>> {
+        CpiContext::new(
+            self.inv_program.to_account_info(),
+            ClaimFee {
+                state: self.inv_state.to_account_info(),
+                pool: self.pool.to_account_info(),
+                position: self.position.to_account_info(),
+                lower_tick: self.lower_tick.to_account_info(),
+                upper_tick: self.upper_tick.to_account_info(),
+                owner: self.program_authority.to_account_info(),
+                token_x: self.token_x.to_account_info(),
+                token_y: self.token_y.to_account_info(),
+                account_x: self.reserve_x.to_account_info(),
+                account_y: self.reserve_y.to_account_info(),
+                reserve_x: self.inv_reserve_x.to_account_info(),
+                reserve_y: self.inv_reserve_y.to_account_info(),
+                program_authority: self.inv_program_authority.to_account_info(),
+                token_x_program: self.token_x_program.to_account_info(),
+                token_y_program: self.token_y_program.to_account_info(),
+            },
+        )
     }
 
     pub fn change_liquidity(&self) -> CpiContext<'_, '_, '_, 'info, ChangeLiquidity<'info>> {
@@ -233,6 +296,31 @@ impl<'info> BurnLpTokenCtx<'info> {
                 token_y_program: self.token_y_program.to_account_info(),
             },
         )
+        .with_remaining_accounts(self.oracle_accounts())
+    }
+
+    pub fn swap(&self, ticks: &[AccountInfo<'info>]) -> CpiContext<'_, '_, '_, 'info, Swap<'info>> {
+        CpiContext::new(
+            self.inv_program.to_account_info(),
+            Swap {
+                state: self.inv_state.to_account_info(),
+                pool: self.pool.to_account_info(),
+                tickmap: self.tickmap.to_account_info(),
+                token_x: self.token_x.to_account_info(),
+                token_y: self.token_y.to_account_info(),
+                account_x: self.account_x.to_account_info(),
+                account_y: self.account_y.to_account_info(),
+                reserve_x: self.inv_reserve_x.to_account_info(),
+                reserve_y: self.inv_reserve_y.to_account_info(),
+                owner: self.owner.to_account_info(),
+                program_authority: self.inv_program_authority.to_account_info(),
+                token_x_program: self.token_x_program.to_account_info(),
+                token_y_program: self.token_y_program.to_account_info(),
+                referral: None,
+                referral_registry: None,
+            },
+        )
+        .with_remaining_accounts([ticks, &self.oracle_accounts()].concat())
     }
 
     pub fn validate_pool(&self) -> Result<()> {
@@ -255,6 +343,7 @@ impl<'info> BurnLpTokenCtx<'info> {
             token_y.as_ref(),
             &lp_pool.fee.v.to_le_bytes(),
             &lp_pool.tick_spacing.to_le_bytes(),
+            &lp_pool.initial_range_seed(),
         ];
         let (pubkey, token_bump) = Pubkey::find_program_address(&seeds, &crate::ID);
         require_keys_eq!(pubkey, self.token_lp.key());
@@ -300,26 +389,59 @@ impl<'info> BurnLpTokenCtx<'info> {
         Ok(())
     }
 
-    pub fn process(&mut self, liquidity_delta: Liquidity) -> Result<()> {
+    pub fn process(
+        &mut self,
+        liquidity_delta: Liquidity,
+        min_amount_x: TokenAmount,
+        min_amount_y: TokenAmount,
+    ) -> Result<BurnLpTokenResult> {
         self.validate_pool()?;
         self.validate_token_lp()?;
         self.validate_position()?;
 
         let position = *try_from!(AccountLoader::<Position>, &self.position)?.load()?;
+        let current_liquidity = Liquidity::new(position.liquidity.v);
+        let lower_tick_index = position.lower_tick_index;
+        let upper_tick_index = position.upper_tick_index;
+        let signer: &[&[&[u8]]] = get_signer!(self.state.load()?.bump_authority);
+
+        // tokens_owed is only refreshed when the position is touched,
+        // so the claimed fee is measured as the change of the reserves
+        let balance_x_before = self.reserve_x.amount;
+        let balance_y_before = self.reserve_y.amount;
+        invariant::cpi::claim_fee(
+            self.claim_fee().with_signer(signer),
+            self.lp_pool.load()?.position_index,
+            lower_tick_index,
+            upper_tick_index,
+        )?;
+        self.reserve_x.reload()?;
+        self.reserve_y.reload()?;
+        let claimed_x = TokenAmount::new(self.reserve_x.amount - balance_x_before);
+        let claimed_y = TokenAmount::new(self.reserve_y.amount - balance_y_before);
 
         let pool: Pool = *self.pool.load()?;
         let mut lp_pool: RefMut<LpPool> = self.lp_pool.load_mut()?;
         let current_tick_index = pool.current_tick_index;
-        let fee_x = TokenAmount::from_decimal(position.tokens_owed_x);
-        let fee_y = TokenAmount::from_decimal(position.tokens_owed_y);
 
-        let current_liquidity = Liquidity::new(position.liquidity.v);
+        let performance_fee = lp_pool.performance_fee;
+        let (harvested_x, performance_fee_x) = compute_performance_fee(claimed_x, performance_fee)
+            .map_err(|_| InvalidPerformanceFee)?;
+        let (harvested_y, performance_fee_y) = compute_performance_fee(claimed_y, performance_fee)
+            .map_err(|_| InvalidPerformanceFee)?;
+        lp_pool.fee_protocol_token_x = lp_pool
+            .fee_protocol_token_x
+            .checked_add(performance_fee_x.0)
+            .unwrap();
+        lp_pool.fee_protocol_token_y = lp_pool
+            .fee_protocol_token_y
+            .checked_add(performance_fee_y.0)
+            .unwrap();
 
-        let lower_tick_index = position.lower_tick_index;
-        let upper_tick_index = position.upper_tick_index;
-
-        let accumulated_x = TokenAmount::new(lp_pool.leftover_x) + fee_x;
-        let accumulated_y = TokenAmount::new(lp_pool.leftover_y) + fee_y;
+        let token_x = self.token_x.to_account_info();
+        let token_y = self.token_y.to_account_info();
+        let accumulated_x = TokenAmount::new(lp_pool.leftover_x) + harvested_x;
+        let accumulated_y = TokenAmount::new(lp_pool.leftover_y) + harvested_y;
 
         let ComputeLpShareChangeResult {
             liquidity_change,
@@ -328,12 +450,17 @@ impl<'info> BurnLpTokenCtx<'info> {
             leftover_amounts,
         } = compute_lp_share_change(
             false,
-            TokenAmount(self.token_lp.supply),
+            SharePrice {
+                lp_token_supply: TokenAmount(self.token_lp.supply),
+                liquidity: current_liquidity,
+                liquidity_per_lp_token: lp_pool.liquidity_per_lp_token,
+            },
             liquidity_delta,
-            current_liquidity,
-            accumulated_x,
-            accumulated_y,
-            lp_pool.tick_spacing,
+            (accumulated_x, accumulated_y),
+            TickRange {
+                lower_tick: lp_pool.lower_tick_index,
+                upper_tick: lp_pool.upper_tick_index,
+            },
             current_tick_index,
             Price::new(pool.sqrt_price.v),
         )
@@ -354,9 +481,26 @@ impl<'info> BurnLpTokenCtx<'info> {
             lp_pool.leftover_y = leftover_amounts.1.get();
         }
 
-        let (transfer_x, transfer_y) = transferred_amounts;
+        // fee stays in the reserves until withdrawn by the admin
+        let burn_fee = lp_pool.burn_fee;
+        let burn_fee_x = compute_protocol_fee(transferred_amounts.0, burn_fee);
+        let burn_fee_y = compute_protocol_fee(transferred_amounts.1, burn_fee);
+        lp_pool.fee_protocol_token_x = lp_pool
+            .fee_protocol_token_x
+            .checked_add(burn_fee_x.0)
+            .unwrap();
+        lp_pool.fee_protocol_token_y = lp_pool
+            .fee_protocol_token_y
+            .checked_add(burn_fee_y.0)
+            .unwrap();
+
+        let (transfer_x, transfer_y) = (
+            transferred_amounts.0 - burn_fee_x,
+            transferred_amounts.1 - burn_fee_y,
+        );
+        let reserve_x_before = self.reserve_x.amount;
+        let reserve_y_before = self.reserve_y.amount;
 
-        let signer: &[&[&[u8]]] = get_signer!(self.state.load()?.bump_authority);
         // burn lp token
         token_2022::burn(self.burn_lp().with_signer(signer), lp_token_change.get())?;
         if remove_position {
@@ -384,6 +528,29 @@ impl<'info> BurnLpTokenCtx<'info> {
             )?;
         }
 
+        self.reserve_x.reload()?;
+        self.reserve_y.reload()?;
+        let (transfer_x, leftover_fee_x) = settle_transfer_fee(
+            &token_x,
+            reserve_x_before,
+            self.reserve_x.amount,
+            transfer_x,
+        )?;
+        let (transfer_y, leftover_fee_y) = settle_transfer_fee(
+            &token_y,
+            reserve_y_before,
+            self.reserve_y.amount,
+            transfer_y,
+        )?;
+        lp_pool.leftover_x = lp_pool.leftover_x.saturating_sub(leftover_fee_x.0);
+        lp_pool.leftover_y = lp_pool.leftover_y.saturating_sub(leftover_fee_y.0);
+
+        // amounts received by the user
+        let amount_x = transfer_x - TokenAmount::new(get_transfer_fee(&token_x, transfer_x.0)?);
+        let amount_y = transfer_y - TokenAmount::new(get_transfer_fee(&token_y, transfer_y.0)?);
+        require!(amount_x >= min_amount_x, ErrorCode::AmountBelowMin);
+        require!(amount_y >= min_amount_y, ErrorCode::AmountBelowMin);
+
         match self.token_x_program.key() {
             token_2022::ID => token_2022::transfer_checked(
                 self.withdraw_x_2022().with_signer(signer),
@@ -404,6 +571,99 @@ impl<'info> BurnLpTokenCtx<'info> {
             _ => return Err(InvalidTokenProgram.into()),
         };
 
-        Ok(())
+        emit!(BurnLpTokenEvent {
+            lp_pool: self.lp_pool.key(),
+            owner: self.owner.key(),
+            liquidity: liquidity_change.l.v,
+            lp_token_amount: lp_token_change.get(),
+            amount_x: amount_x.0,
+            amount_y: amount_y.0,
+            burn_fee_x: burn_fee_x.0,
+            burn_fee_y: burn_fee_y.0,
+        });
+
+        Ok(BurnLpTokenResult {
+            amount_x: amount_x.0,
+            amount_y: amount_y.0,
+            lp_token_amount: lp_token_change.get(),
+            liquidity: liquidity_change.l.v,
+        })
+    }
+
+    pub fn process_zap_out(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        liquidity_delta: Liquidity,
+        x_to_y: bool,
+        sqrt_price_limit: u128,
+        min_amount_out: TokenAmount,
+    ) -> Result<BurnLpTokenResult> {
+        let balance_x_before = self.account_x.amount;
+        let balance_y_before = self.account_y.amount;
+
+        let burned = self.process(liquidity_delta, TokenAmount::new(0), TokenAmount::new(0))?;
+
+        self.account_x.reload()?;
+        self.account_y.reload()?;
+        // only the withdrawn part of the unwanted token is swapped
+        let swap_amount = match x_to_y {
+            true => self.account_x.amount - balance_x_before,
+            false => self.account_y.amount - balance_y_before,
+        };
+
+        let (swap_amount_in, swap_amount_out) = if swap_amount == 0 {
+            (0, 0)
+        } else {
+            let (withdrawn_x, withdrawn_y) = (self.account_x.amount, self.account_y.amount);
+            // transfer fee of the input is sent on top of the swapped amount
+            let mint_in = match x_to_y {
+                true => self.token_x.to_account_info(),
+                false => self.token_y.to_account_info(),
+            };
+            let max_amount_in = swap_amount + get_transfer_inverse_fee(&mint_in, swap_amount)?;
+            invariant::cpi::swap(
+                self.swap(remaining_accounts),
+                x_to_y,
+                swap_amount,
+                true,
+                sqrt_price_limit,
+                max_amount_in,
+                0,
+            )?;
+            self.account_x.reload()?;
+            self.account_y.reload()?;
+            match x_to_y {
+                true => (
+                    withdrawn_x - self.account_x.amount,
+                    self.account_y.amount - withdrawn_y,
+                ),
+                false => (
+                    withdrawn_y - self.account_y.amount,
+                    self.account_x.amount - withdrawn_x,
+                ),
+            }
+        };
+
+        let amount_out = match x_to_y {
+            true => self.account_y.amount - balance_y_before,
+            false => self.account_x.amount - balance_x_before,
+        };
+        require!(
+            TokenAmount::new(amount_out) >= min_amount_out,
+            ErrorCode::AmountBelowMin
+        );
+
+        emit!(ZapOutEvent {
+            lp_pool: self.lp_pool.key(),
+            owner: self.owner.key(),
+            x_to_y,
+            lp_token_amount: burned.lp_token_amount,
+            liquidity: burned.liquidity,
+            swap_amount_in,
+            swap_amount_out,
+            amount_out,
+        });
+
+        Ok(burned)
     }
 }
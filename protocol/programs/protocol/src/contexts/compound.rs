@@ -0,0 +1,240 @@
+use crate::decimals::{Price, TokenAmount};
+use crate::math::{
+    calculate_amount_delta, compute_max_liquidity_for_amounts, compute_performance_fee,
+};
+use crate::states::{DerivedAccountIdentifier, LpPool, State};
+use crate::{
+    get_signer,
+    ErrorCode::{self, *},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount as ITokenAccount, TokenInterface};
+use decimal::Decimal;
+use invariant::cpi::accounts::{ChangeLiquidity, ClaimFee};
+use invariant::decimals::Liquidity as InvLiquidity;
+use invariant::structs::{Pool, Position};
+use invariant::token_extensions::{get_transfer_fee, get_transfer_inverse_fee};
+
+const ADD: bool = true;
+
+#[derive(Accounts)]
+pub struct CompoundCtx<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [State::IDENT],
+        bump = state.load()?.bump
+    )]
+    pub state: AccountLoader<'info, State>,
+    /// CHECK: cached from the state account
+    #[account(mut)]
+    #[account(constraint = &state.load()?.program_authority == program_authority.key @ InvalidAuthority)]
+    pub program_authority: AccountInfo<'info>,
+    #[account(mut,
+        seeds = [LpPool::IDENT, token_x.key().as_ref(), token_y.key().as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes(), &lp_pool.load()?.initial_range_seed()],
+        bump=lp_pool.load()?.bump,
+    )]
+    pub lp_pool: AccountLoader<'info, LpPool>,
+    #[account(mut,
+        associated_token::mint = token_x,
+        associated_token::authority = program_authority,
+        associated_token::token_program = token_x_program,
+    )]
+    pub reserve_x: Box<InterfaceAccount<'info, ITokenAccount>>,
+    #[account(mut,
+        associated_token::mint = token_y,
+        associated_token::authority = program_authority,
+        associated_token::token_program = token_y_program,
+    )]
+    pub reserve_y: Box<InterfaceAccount<'info, ITokenAccount>>,
+    /// INVARIANT
+    /// CHECK: passed to Invariant
+    pub inv_program: UncheckedAccount<'info>,
+    /// CHECK: passed to Invariant
+    pub inv_state: UncheckedAccount<'info>,
+    /// CHECK: invariant_program_authority is the authority of the Invariant program
+    pub inv_program_authority: UncheckedAccount<'info>,
+    /// CHECK: validated in the handler
+    #[account(mut)]
+    pub position: AccountLoader<'info, Position>,
+    #[account(mut,
+        // validated in the handler!
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    /// CHECK: passed to Invariant
+    #[account(mut)]
+    pub lower_tick: UncheckedAccount<'info>,
+    /// CHECK: passed to Invariant
+    #[account(mut)]
+    pub upper_tick: UncheckedAccount<'info>,
+    pub token_x: Box<InterfaceAccount<'info, Mint>>,
+    pub token_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub inv_reserve_x: Box<InterfaceAccount<'info, ITokenAccount>>,
+    #[account(mut)]
+    pub inv_reserve_y: Box<InterfaceAccount<'info, ITokenAccount>>,
+    pub token_x_program: Interface<'info, TokenInterface>,
+    pub token_y_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CompoundCtx<'info> {
+    pub fn claim_fee(&self) -> CpiContext<'_, '_, '_, 'info, ClaimFee<'info>> {
+        CpiContext::new(
+            self.inv_program.to_account_info(),
+            ClaimFee {
+                state: self.inv_state.to_account_info(),
+                pool: self.pool.to_account_info(),
+                position: self.position.to_account_info(),
+                lower_tick: self.lower_tick.to_account_info(),
+                upper_tick: self.upper_tick.to_account_info(),
+                owner: self.program_authority.to_account_info(),
+                token_x: self.token_x.to_account_info(),
+                token_y: self.token_y.to_account_info(),
+                account_x: self.reserve_x.to_account_info(),
+                account_y: self.reserve_y.to_account_info(),
+                reserve_x: self.inv_reserve_x.to_account_info(),
+                reserve_y: self.inv_reserve_y.to_account_info(),
+                program_authority: self.inv_program_authority.to_account_info(),
+                token_x_program: self.token_x_program.to_account_info(),
+                token_y_program: self.token_y_program.to_account_info(),
+            },
+        )
+    }
+
+    pub fn change_liquidity(&self) -> CpiContext<'_, '_, '_, 'info, ChangeLiquidity<'info>> {
+        CpiContext::new(
+            self.inv_program.to_account_info(),
+            ChangeLiquidity {
+                state: self.inv_state.to_account_info(),
+                program_authority: self.inv_program_authority.to_account_info(),
+                owner: self.program_authority.to_account_info(),
+                payer: self.payer.to_account_info(),
+                position: self.position.to_account_info(),
+                pool: self.pool.to_account_info(),
+                lower_tick: self.lower_tick.to_account_info(),
+                upper_tick: self.upper_tick.to_account_info(),
+                token_x: self.token_x.to_account_info(),
+                token_y: self.token_y.to_account_info(),
+                account_x: self.reserve_x.to_account_info(),
+                account_y: self.reserve_y.to_account_info(),
+                reserve_x: self.inv_reserve_x.to_account_info(),
+                reserve_y: self.inv_reserve_y.to_account_info(),
+                token_x_program: self.token_x_program.to_account_info(),
+                token_y_program: self.token_y_program.to_account_info(),
+            },
+        )
+    }
+
+    pub fn validate_pool(&self) -> Result<()> {
+        let lp_pool = &self.lp_pool.load()?;
+        let pool = &self.pool.load()?;
+        require_keys_eq!(pool.token_x, self.token_x.key());
+        require_keys_eq!(pool.token_y, self.token_y.key());
+        require_eq!(pool.fee.v, lp_pool.fee.v);
+        require_eq!(pool.tick_spacing, lp_pool.tick_spacing);
+        Ok(())
+    }
+
+    pub fn validate_position(&self) -> Result<()> {
+        let lp_pool = &self.lp_pool.load()?;
+        require!(lp_pool.position_exists, ErrorCode::NothingToCompound);
+
+        let seeds = [
+            b"positionv1",
+            self.program_authority.key.as_ref(),
+            &lp_pool.position_index.to_le_bytes(),
+        ];
+        let (position_key, position_bump) = Pubkey::find_program_address(&seeds, &invariant::ID);
+        require_keys_eq!(position_key, self.position.key());
+        require_eq!(position_bump, self.position.load()?.bump);
+
+        Ok(())
+    }
+
+    pub fn process(&mut self) -> Result<()> {
+        self.validate_pool()?;
+        self.validate_position()?;
+
+        let signer: &[&[&[u8]]] = get_signer!(self.state.load()?.bump_authority);
+        let (position_index, lower_tick_index, upper_tick_index) = {
+            let lp_pool = self.lp_pool.load()?;
+            (
+                lp_pool.position_index,
+                lp_pool.lower_tick_index,
+                lp_pool.upper_tick_index,
+            )
+        };
+
+        // tokens_owed is only refreshed when the position is touched,
+        // so the fee is measured as the change of the reserves
+        let balance_x_before = self.reserve_x.amount;
+        let balance_y_before = self.reserve_y.amount;
+        invariant::cpi::claim_fee(
+            self.claim_fee().with_signer(signer),
+            position_index,
+            lower_tick_index,
+            upper_tick_index,
+        )?;
+        self.reserve_x.reload()?;
+        self.reserve_y.reload()?;
+        let fee_x = TokenAmount::new(self.reserve_x.amount - balance_x_before);
+        let fee_y = TokenAmount::new(self.reserve_y.amount - balance_y_before);
+
+        let pool = *self.pool.load()?;
+        let lp_pool = &mut self.lp_pool.load_mut()?;
+        let current_sqrt_price = Price::new(pool.sqrt_price.v);
+
+        let performance_fee = lp_pool.performance_fee;
+        let (harvested_x, performance_fee_x) =
+            compute_performance_fee(fee_x, performance_fee).map_err(|_| InvalidPerformanceFee)?;
+        let (harvested_y, performance_fee_y) =
+            compute_performance_fee(fee_y, performance_fee).map_err(|_| InvalidPerformanceFee)?;
+        let accumulated_x = TokenAmount::new(lp_pool.leftover_x) + harvested_x;
+        let accumulated_y = TokenAmount::new(lp_pool.leftover_y) + harvested_y;
+
+        // invariant pulls the deposit with the transfer fee on top
+        let token_x = self.token_x.to_account_info();
+        let token_y = self.token_y.to_account_info();
+        let liquidity = compute_max_liquidity_for_amounts(
+            accumulated_x - TokenAmount::new(get_transfer_fee(&token_x, accumulated_x.0)?),
+            accumulated_y - TokenAmount::new(get_transfer_fee(&token_y, accumulated_y.0)?),
+            lower_tick_index,
+            upper_tick_index,
+            pool.current_tick_index,
+            current_sqrt_price,
+        )
+        .map_err(|_| NothingToCompound)?;
+
+        let (deposited_x, deposited_y) = calculate_amount_delta(
+            current_sqrt_price,
+            liquidity,
+            ADD,
+            pool.current_tick_index,
+            lower_tick_index,
+            upper_tick_index,
+        )
+        .unwrap();
+        let pulled_x = deposited_x.0 + get_transfer_inverse_fee(&token_x, deposited_x.0)?;
+        let pulled_y = deposited_y.0 + get_transfer_inverse_fee(&token_y, deposited_y.0)?;
+        lp_pool.leftover_x = accumulated_x.0.saturating_sub(pulled_x);
+        lp_pool.leftover_y = accumulated_y.0.saturating_sub(pulled_y);
+        lp_pool.fee_protocol_token_x = lp_pool
+            .fee_protocol_token_x
+            .checked_add(performance_fee_x.0)
+            .unwrap();
+        lp_pool.fee_protocol_token_y = lp_pool
+            .fee_protocol_token_y
+            .checked_add(performance_fee_y.0)
+            .unwrap();
+
+        // no LP tokens are minted, so the value of every share grows
+        invariant::cpi::change_liquidity(
+            self.change_liquidity().with_signer(signer),
+            position_index,
+            InvLiquidity::new(liquidity.v),
+            ADD,
+            pool.sqrt_price,
+            pool.sqrt_price,
+        )
+    }
+}
@@ -27,6 +27,8 @@ impl InitCtx<'_> {
             counter: 0,
             bump_authority,
             bump,
+            paused: false,
+            pending_admin: Pubkey::default(),
         };
         Ok(())
     }
@@ -1,15 +1,25 @@
+use crate::get_signer;
+use crate::math::{get_max_tick, get_min_tick, MAX_LP_TOKEN_DECIMALS};
+use crate::metadata::{get_token_symbol, lp_token_name, lp_token_symbol};
 use crate::states::{DerivedAccountIdentifier, LpPool, INVARIANT_POOL_IDENT, LP_TOKEN_IDENT};
-use crate::ErrorCode::*;
+use crate::ErrorCode::{self, *};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::system_program::{create_account, CreateAccount};
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022::spl_token_2022::extension::{metadata_pointer, ExtensionType};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as Token2022Mint;
+use anchor_spl::token_2022::{initialize_mint2, InitializeMint2, Token2022};
 use anchor_spl::token_interface::Mint;
 use decimal::Decimal;
 use invariant::structs::Pool;
+use invariant::token_extensions::has_supported_extensions;
+use spl_token_metadata_interface::state::TokenMetadata;
 
 use crate::states::State;
 
 #[derive(Accounts)]
+#[instruction(lower_tick_index: i32, upper_tick_index: i32)]
 pub struct InitPoolCtx<'info> {
     #[account(
         seeds = [State::IDENT],
@@ -21,20 +31,18 @@ pub struct InitPoolCtx<'info> {
     pub program_authority: AccountInfo<'info>,
 
     #[account(init,
-        seeds = [LpPool::IDENT, token_x.key().as_ref(), token_y.key().as_ref(), &pool.load()?.fee.v.to_le_bytes(), &pool.load()?.tick_spacing.to_le_bytes()],
+        seeds = [LpPool::IDENT, token_x.key().as_ref(), token_y.key().as_ref(), &pool.load()?.fee.v.to_le_bytes(), &pool.load()?.tick_spacing.to_le_bytes(), &LpPool::range_seed(lower_tick_index, upper_tick_index, pool.load()?.tick_spacing)],
         space = LpPool::LEN,
         bump,
         payer = payer
     )]
     pub lp_pool: AccountLoader<'info, LpPool>,
-    #[account(init,
-        seeds = [LP_TOKEN_IDENT, token_x.key().as_ref(), token_y.key().as_ref(), &pool.load()?.fee.v.to_le_bytes(), &pool.load()?.tick_spacing.to_le_bytes()],
+    /// CHECK: created with the metadata extensions in the handler
+    #[account(mut,
+        seeds = [LP_TOKEN_IDENT, token_x.key().as_ref(), token_y.key().as_ref(), &pool.load()?.fee.v.to_le_bytes(), &pool.load()?.tick_spacing.to_le_bytes(), &LpPool::range_seed(lower_tick_index, upper_tick_index, pool.load()?.tick_spacing)],
         bump,
-        payer = payer,
-        mint::decimals = 6,
-        mint::authority = program_authority,
-        mint::token_program = token_program)]
-    pub token_lp: InterfaceAccount<'info, Mint>,
+    )]
+    pub token_lp: UncheckedAccount<'info>,
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
@@ -45,13 +53,162 @@ pub struct InitPoolCtx<'info> {
     pub pool: AccountLoader<'info, Pool>,
     pub token_x: InterfaceAccount<'info, Mint>,
     pub token_y: InterfaceAccount<'info, Mint>,
+    /// CHECK: Metaplex metadata of token_x, validated in the handler
+    pub token_x_metadata: Option<UncheckedAccount<'info>>,
+    /// CHECK: Metaplex metadata of token_y, validated in the handler
+    pub token_y_metadata: Option<UncheckedAccount<'info>>,
     pub token_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 impl InitPoolCtx<'_> {
-    pub fn process(&mut self, token_bump: u8, bump: u8) -> Result<()> {
+    pub fn validate_ticks(&self, lower_tick_index: i32, upper_tick_index: i32) -> Result<()> {
+        let tick_spacing = self.pool.load()?.tick_spacing;
+        require!(
+            lower_tick_index < upper_tick_index,
+            ErrorCode::InvalidTickRange
+        );
+        require!(
+            lower_tick_index >= get_min_tick(tick_spacing)
+                && upper_tick_index <= get_max_tick(tick_spacing),
+            ErrorCode::InvalidTickRange
+        );
+        require!(
+            lower_tick_index % tick_spacing as i32 == 0
+                && upper_tick_index % tick_spacing as i32 == 0,
+            ErrorCode::InvalidTickRange
+        );
+        Ok(())
+    }
+
+    // LP mint points to its own token metadata, named after the pair and the fee tier
+    pub fn init_token_lp(
+        &self,
+        lower_tick_index: i32,
+        upper_tick_index: i32,
+        lp_token_decimals: u8,
+        token_bump: u8,
+    ) -> Result<()> {
+        let (fee, tick_spacing) = {
+            let pool = self.pool.load()?;
+            (pool.fee.v, pool.tick_spacing)
+        };
+        let token_x = self.token_x.key();
+        let token_y = self.token_y.key();
+        let token_lp = self.token_lp.key();
+        let program_authority = self.program_authority.key();
+
+        let symbol_x = get_token_symbol(
+            &self.token_x.to_account_info(),
+            self.token_x_metadata.as_deref(),
+        )?;
+        let symbol_y = get_token_symbol(
+            &self.token_y.to_account_info(),
+            self.token_y_metadata.as_deref(),
+        )?;
+        let metadata = TokenMetadata {
+            name: lp_token_name(&symbol_x, &symbol_y, fee),
+            symbol: lp_token_symbol(&symbol_x, &symbol_y),
+            ..Default::default()
+        };
+
+        // metadata is written after the mint is initialized, rent has to cover it upfront
+        let space = ExtensionType::try_calculate_account_len::<Token2022Mint>(&[
+            ExtensionType::MetadataPointer,
+        ])?;
+        let lamports = Rent::get()?.minimum_balance(space + metadata.tlv_size_of()?);
+        let token_lp_seeds: &[&[u8]] = &[
+            LP_TOKEN_IDENT,
+            token_x.as_ref(),
+            token_y.as_ref(),
+            &fee.to_le_bytes(),
+            &tick_spacing.to_le_bytes(),
+            &LpPool::range_seed(lower_tick_index, upper_tick_index, tick_spacing),
+            &[token_bump],
+        ];
+        create_account(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                CreateAccount {
+                    from: self.payer.to_account_info(),
+                    to: self.token_lp.to_account_info(),
+                },
+                &[token_lp_seeds],
+            ),
+            lamports,
+            space as u64,
+            &Token2022::id(),
+        )?;
+
+        invoke(
+            &metadata_pointer::instruction::initialize(
+                &Token2022::id(),
+                &token_lp,
+                Some(program_authority),
+                Some(token_lp),
+            )?,
+            &[self.token_lp.to_account_info()],
+        )?;
+        initialize_mint2(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: self.token_lp.to_account_info(),
+                },
+            ),
+            lp_token_decimals,
+            &program_authority,
+            None,
+        )?;
+        invoke_signed(
+            &spl_token_metadata_interface::instruction::initialize(
+                &Token2022::id(),
+                &token_lp,
+                &program_authority,
+                &token_lp,
+                &program_authority,
+                metadata.name,
+                metadata.symbol,
+                metadata.uri,
+            ),
+            &[
+                self.token_lp.to_account_info(),
+                self.program_authority.to_account_info(),
+                self.token_program.to_account_info(),
+            ],
+            get_signer!(self.state.load()?.bump_authority),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn process(
+        &mut self,
+        lower_tick_index: i32,
+        upper_tick_index: i32,
+        lp_token_decimals: u8,
+        liquidity_per_lp_token: u64,
+        token_bump: u8,
+        bump: u8,
+    ) -> Result<()> {
+        self.validate_ticks(lower_tick_index, upper_tick_index)?;
+        require!(
+            lp_token_decimals <= MAX_LP_TOKEN_DECIMALS && liquidity_per_lp_token > 0,
+            ErrorCode::InvalidLpTokenConfig
+        );
+        require!(
+            has_supported_extensions(&self.token_x.to_account_info())?
+                && has_supported_extensions(&self.token_y.to_account_info())?,
+            ErrorCode::UnsupportedMintExtension
+        );
+        self.init_token_lp(
+            lower_tick_index,
+            upper_tick_index,
+            lp_token_decimals,
+            token_bump,
+        )?;
+
         let token_x = self.token_x.key();
         let token_y = self.token_y.key();
         let lp_pool = &mut self.lp_pool.load_init()?;
@@ -68,6 +225,14 @@ impl InitPoolCtx<'_> {
             fee: crate::decimals::FixedPoint::new(pool.fee.v),
             token_bump,
             bump,
+            version: LpPool::VERSION,
+            initial_lower_tick_index: lower_tick_index,
+            initial_upper_tick_index: upper_tick_index,
+            lp_token_decimals,
+            liquidity_per_lp_token,
+            lower_tick_index,
+            upper_tick_index,
+            ..Default::default()
         };
 
         Ok(())
@@ -0,0 +1,116 @@
+use crate::decimals::FixedPoint;
+use crate::math::{get_max_tick, get_min_tick, ONE_LP_TOKEN};
+use crate::states::LpPool;
+use crate::{try_from, ErrorCode};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+// decimals of LP tokens minted before they were configurable
+const LEGACY_LP_TOKEN_DECIMALS: u8 = 6;
+
+// LpPool before versioning, its field order was left to the compiler
+#[zero_copy(unsafe)]
+#[repr(packed)]
+#[derive(Default)]
+pub(crate) struct LegacyLpPool {
+    pub position_index: u32,
+    pub position_exists: bool,
+    pub leftover_x: u64,
+    pub leftover_y: u64,
+    pub token_x: Pubkey,
+    pub token_y: Pubkey,
+    pub tick_spacing: u16,
+    pub fee: FixedPoint,
+    pub token_bump: u8,
+    pub bump: u8,
+}
+
+unsafe impl bytemuck::Pod for LegacyLpPool {}
+unsafe impl bytemuck::Zeroable for LegacyLpPool {}
+
+#[derive(Accounts)]
+pub struct MigrateLpPoolCtx<'info> {
+    /// CHECK: might be in the legacy layout, loaded after the reallocation
+    #[account(mut, owner = crate::ID)]
+    pub lp_pool: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateLpPoolCtx<'_> {
+    fn version(&self) -> Result<u8> {
+        let data = self.lp_pool.try_borrow_data()?;
+        if data.len() < LpPool::LEGACY_LEN || data[..8] != LpPool::DISCRIMINATOR {
+            return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        Ok(match data.len() {
+            LpPool::LEGACY_LEN => 0,
+            _ => data[LpPool::LEGACY_LEN],
+        })
+    }
+
+    pub fn process(&mut self) -> Result<()> {
+        let version = self.version()?;
+        require!(version < LpPool::VERSION, ErrorCode::AlreadyMigrated);
+        let legacy = match version {
+            0 => Some(bytemuck::pod_read_unaligned::<LegacyLpPool>(
+                &self.lp_pool.try_borrow_data()?[8..LpPool::LEGACY_LEN],
+            )),
+            _ => None,
+        };
+
+        let space = LpPool::LEN;
+        if self.lp_pool.data_len() < space {
+            let lamports = Rent::get()?
+                .minimum_balance(space)
+                .saturating_sub(self.lp_pool.lamports());
+            if lamports != 0 {
+                transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        Transfer {
+                            from: self.payer.to_account_info(),
+                            to: self.lp_pool.to_account_info(),
+                        },
+                    ),
+                    lamports,
+                )?;
+            }
+            self.lp_pool.realloc(space, true)?;
+        }
+
+        // legacy fields are rewritten in declaration order, legacy pools were always full range
+        if let Some(legacy) = legacy {
+            self.lp_pool.try_borrow_mut_data()?[8..].fill(0);
+            let lp_pool_loader = try_from!(AccountLoader::<LpPool>, &self.lp_pool)?;
+            let lp_pool = &mut lp_pool_loader.load_mut()?;
+            let tick_spacing = legacy.tick_spacing;
+            **lp_pool = LpPool {
+                position_index: legacy.position_index,
+                position_exists: legacy.position_exists,
+                leftover_x: legacy.leftover_x,
+                leftover_y: legacy.leftover_y,
+                token_x: legacy.token_x,
+                token_y: legacy.token_y,
+                tick_spacing,
+                fee: legacy.fee,
+                token_bump: legacy.token_bump,
+                bump: legacy.bump,
+                initial_lower_tick_index: get_min_tick(tick_spacing),
+                initial_upper_tick_index: get_max_tick(tick_spacing),
+                lower_tick_index: get_min_tick(tick_spacing),
+                upper_tick_index: get_max_tick(tick_spacing),
+                lp_token_decimals: LEGACY_LP_TOKEN_DECIMALS,
+                liquidity_per_lp_token: ONE_LP_TOKEN,
+                ..Default::default()
+            };
+        }
+
+        let lp_pool_loader = try_from!(AccountLoader::<LpPool>, &self.lp_pool)?;
+        lp_pool_loader.load_mut()?.version = LpPool::VERSION;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use crate::states::{DerivedAccountIdentifier, State};
+use crate::{try_from, ErrorCode};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+// State before pausing and the admin transfer, its field order was left to the compiler
+#[zero_copy(unsafe)]
+#[repr(packed)]
+#[derive(Default)]
+pub(crate) struct LegacyState {
+    pub admin: Pubkey,
+    pub program_authority: Pubkey,
+    pub counter: u8,
+    pub bump: u8,
+    pub bump_authority: u8,
+}
+
+unsafe impl bytemuck::Pod for LegacyState {}
+unsafe impl bytemuck::Zeroable for LegacyState {}
+
+#[derive(Accounts)]
+pub struct MigrateStateCtx<'info> {
+    /// CHECK: might be in the legacy layout, validated in the handler
+    #[account(mut, owner = crate::ID, seeds = [State::IDENT], bump)]
+    pub state: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateStateCtx<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        let legacy = {
+            let data = self.state.try_borrow_data()?;
+            if data.len() < State::LEGACY_LEN || data[..8] != State::DISCRIMINATOR {
+                return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+            }
+            require_eq!(data.len(), State::LEGACY_LEN, ErrorCode::AlreadyMigrated);
+            bytemuck::pod_read_unaligned::<LegacyState>(&data[8..State::LEGACY_LEN])
+        };
+        require!(
+            legacy.admin == self.admin.key(),
+            ErrorCode::InvalidAuthority
+        );
+
+        let space = State::LEN;
+        let lamports = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(self.state.lamports());
+        if lamports != 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.admin.to_account_info(),
+                        to: self.state.to_account_info(),
+                    },
+                ),
+                lamports,
+            )?;
+        }
+        self.state.realloc(space, true)?;
+
+        // legacy fields are rewritten in declaration order, the state is not paused
+        // and has no pending admin
+        self.state.try_borrow_mut_data()?[8..].fill(0);
+        let state_loader = try_from!(AccountLoader::<State>, &self.state)?;
+        *state_loader.load_mut()? = State {
+            admin: legacy.admin,
+            program_authority: legacy.program_authority,
+            counter: legacy.counter,
+            bump: legacy.bump,
+            bump_authority: legacy.bump_authority,
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+}
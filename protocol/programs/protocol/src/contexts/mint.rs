@@ -1,7 +1,15 @@
-use crate::decimals::{Liquidity, Price, TokenAmount};
-use crate::math::{compute_lp_share_change, get_max_tick, get_min_tick};
+use crate::decimals::{FixedPoint, Liquidity, Price, TokenAmount};
+use crate::events::{MintLpTokenEvent, ZapInEvent};
+use crate::math::{
+    compute_lp_share_change, compute_max_liquidity_for_amounts, compute_performance_fee,
+    compute_protocol_fee, compute_zap_in_swap_amount, SharePrice, TickRange,
+};
+use crate::results::MintLpTokenResult;
 use crate::states::{DerivedAccountIdentifier, LpPool, State, LP_TOKEN_IDENT};
-use crate::{get_signer, ErrorCode::*};
+use crate::{
+    get_signer,
+    ErrorCode::{self, *},
+};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_2022::{mint_to, MintTo, Token2022};
@@ -10,10 +18,11 @@ use anchor_spl::{
     token::{self},
     token_2022,
 };
-use decimal::{BetweenDecimals, Decimal};
-use invariant::cpi::accounts::{ChangeLiquidity, ClaimFee};
+use decimal::Decimal;
+use invariant::cpi::accounts::{ChangeLiquidity, ClaimFee, Swap};
 use invariant::decimals::{Liquidity as InvLiquidity, Price as InvPrice};
 use invariant::structs::PositionList;
+use invariant::token_extensions::{get_transfer_fee, get_transfer_inverse_fee};
 use invariant::{
     cpi::accounts::{CreatePosition, CreateTick},
     structs::{Pool, Position},
@@ -28,6 +37,14 @@ macro_rules! try_from {
 
 const ADD: bool = true;
 
+// amount left after the deposit to the reserve and the transfer to invariant
+fn net_of_transfer_fees(mint: &AccountInfo, amount: TokenAmount) -> Result<TokenAmount> {
+    let deposited = amount.0 - get_transfer_fee(mint, amount.0)?;
+    Ok(TokenAmount::new(
+        deposited - get_transfer_fee(mint, deposited)?,
+    ))
+}
+
 #[derive(Accounts)]
 pub struct MintLpTokenCtx<'info> {
     #[account(mut)]
@@ -42,13 +59,13 @@ pub struct MintLpTokenCtx<'info> {
     #[account(constraint = &state.load()?.program_authority == program_authority.key @ InvalidAuthority)]
     pub program_authority: AccountInfo<'info>,
     #[account(mut,
-        seeds = [LpPool::IDENT, token_x.key().as_ref(), token_y.key().as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes()],
+        seeds = [LpPool::IDENT, token_x.key().as_ref(), token_y.key().as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes(), &lp_pool.load()?.initial_range_seed()],
         bump=lp_pool.load()?.bump,
     )]
     pub lp_pool: AccountLoader<'info, LpPool>,
     #[account(mut,
         // validated in the handler!
-        // seeds = [LP_TOKEN_IDENT, token_x.key().as_ref(), token_y.key().as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes()],
+        // seeds = [LP_TOKEN_IDENT, token_x.key().as_ref(), token_y.key().as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes(), &lp_pool.load()?.initial_range_seed()],
         // bump=lp_pool.load()?.token_bump,
     )]
     pub token_lp: Box<InterfaceAccount<'info, Mint>>,
@@ -93,6 +110,9 @@ pub struct MintLpTokenCtx<'info> {
         // constraint = pool.load()?.fee.v == lp_pool.load()?.fee.v
     )]
     pub pool: AccountLoader<'info, Pool>,
+    /// CHECK: passed to Invariant, required once the pool has an oracle
+    #[account(mut, constraint = oracle.key() == pool.load()?.oracle_address @ InvalidOracle)]
+    pub oracle: Option<UncheckedAccount<'info>>,
     /// CHECK: passed to Invariant
     #[account(mut)]
     pub position_list: AccountLoader<'info, PositionList>,
@@ -124,6 +144,10 @@ pub struct MintLpTokenCtx<'info> {
 }
 
 impl<'info> MintLpTokenCtx<'info> {
+    fn oracle_accounts(&self) -> Vec<AccountInfo<'info>> {
+        self.oracle.iter().map(|o| o.to_account_info()).collect()
+    }
+
     pub fn mint_lp(&self) -> CpiContext<'_, '_, '_, 'info, MintTo<'info>> {
         CpiContext::new(
             self.token_program.to_account_info(),
@@ -230,6 +254,7 @@ impl<'info> MintLpTokenCtx<'info> {
                 token_y_program: self.token_y_program.to_account_info(),
             },
         )
+        .with_remaining_accounts(self.oracle_accounts())
     }
 
     pub fn create_position(&self) -> CpiContext<'_, '_, '_, 'info, CreatePosition<'info>> {
@@ -258,6 +283,7 @@ impl<'info> MintLpTokenCtx<'info> {
                 system_program: self.system_program.to_account_info(),
             },
         )
+        .with_remaining_accounts(self.oracle_accounts())
     }
 
     pub fn create_tick(
@@ -282,6 +308,30 @@ impl<'info> MintLpTokenCtx<'info> {
         )
     }
 
+    pub fn swap(&self, ticks: &[AccountInfo<'info>]) -> CpiContext<'_, '_, '_, 'info, Swap<'info>> {
+        CpiContext::new(
+            self.inv_program.to_account_info(),
+            Swap {
+                state: self.inv_state.to_account_info(),
+                pool: self.pool.to_account_info(),
+                tickmap: self.tickmap.to_account_info(),
+                token_x: self.token_x.to_account_info(),
+                token_y: self.token_y.to_account_info(),
+                account_x: self.account_x.to_account_info(),
+                account_y: self.account_y.to_account_info(),
+                reserve_x: self.inv_reserve_x.to_account_info(),
+                reserve_y: self.inv_reserve_y.to_account_info(),
+                owner: self.owner.to_account_info(),
+                program_authority: self.inv_program_authority.to_account_info(),
+                token_x_program: self.token_x_program.to_account_info(),
+                token_y_program: self.token_y_program.to_account_info(),
+                referral: None,
+                referral_registry: None,
+            },
+        )
+        .with_remaining_accounts([ticks, &self.oracle_accounts()].concat())
+    }
+
     pub fn validate_pool(&self) -> Result<()> {
         let lp_pool = &self.lp_pool.load()?;
         let pool = &self.pool.load()?;
@@ -302,6 +352,7 @@ impl<'info> MintLpTokenCtx<'info> {
             token_y.as_ref(),
             &lp_pool.fee.v.to_le_bytes(),
             &lp_pool.tick_spacing.to_le_bytes(),
+            &lp_pool.initial_range_seed(),
         ];
         let (pubkey, token_bump) = Pubkey::find_program_address(&seeds, &crate::ID);
         require_keys_eq!(pubkey, self.token_lp.key());
@@ -312,8 +363,8 @@ impl<'info> MintLpTokenCtx<'info> {
     pub fn validate_position(&self) -> Result<()> {
         let lp_pool = &self.lp_pool.load()?;
         if lp_pool.position_exists {
-            let upper_tick_index = get_max_tick(lp_pool.tick_spacing);
-            let lower_tick_index = get_min_tick(lp_pool.tick_spacing);
+            let upper_tick_index = lp_pool.upper_tick_index;
+            let lower_tick_index = lp_pool.lower_tick_index;
             let position_loader = try_from!(AccountLoader::<Position>, &self.position)?;
             let position = position_loader.load()?;
             require_eq!(position.upper_tick_index, upper_tick_index);
@@ -332,11 +383,54 @@ impl<'info> MintLpTokenCtx<'info> {
         Ok(())
     }
 
-    pub fn process(&mut self, liquidity: Liquidity) -> Result<()> {
+    pub fn process(
+        &mut self,
+        liquidity: Liquidity,
+        max_amount_x: TokenAmount,
+        max_amount_y: TokenAmount,
+    ) -> Result<MintLpTokenResult> {
         self.validate_pool()?;
         self.validate_token_lp()?;
         self.validate_position()?;
 
+        let (position_exists, position_index, lower_tick_index, upper_tick_index) = {
+            let lp_pool = self.lp_pool.load()?;
+            require!(
+                !self.state.load()?.paused && !lp_pool.paused,
+                ErrorCode::Paused
+            );
+            (
+                lp_pool.position_exists,
+                lp_pool.position_index,
+                lp_pool.lower_tick_index,
+                lp_pool.upper_tick_index,
+            )
+        };
+        let signer: &[&[&[u8]]] = get_signer!(self.state.load()?.bump_authority);
+
+        // tokens_owed is only refreshed when the position is touched,
+        // so the claimed fee is measured as the change of the reserves
+        let (claimed_x, claimed_y, current_liquidity) = if position_exists {
+            let balance_x_before = self.reserve_x.amount;
+            let balance_y_before = self.reserve_y.amount;
+            invariant::cpi::claim_fee(
+                self.claim_fee().with_signer(signer),
+                position_index,
+                lower_tick_index,
+                upper_tick_index,
+            )?;
+            self.reserve_x.reload()?;
+            self.reserve_y.reload()?;
+            let position = *try_from!(AccountLoader::<Position>, &self.position)?.load()?;
+            (
+                TokenAmount::new(self.reserve_x.amount - balance_x_before),
+                TokenAmount::new(self.reserve_y.amount - balance_y_before),
+                Liquidity::new(position.liquidity.v),
+            )
+        } else {
+            (TokenAmount::new(0), TokenAmount::new(0), Liquidity::new(0))
+        };
+
         let lp_pool = &mut self.lp_pool.load_mut()?;
         let Pool {
             sqrt_price,
@@ -344,36 +438,84 @@ impl<'info> MintLpTokenCtx<'info> {
             ..
         } = *self.pool.load()?;
         let current_sqrt_price = Price::new(sqrt_price.v);
-        let upper_tick_index = get_max_tick(lp_pool.tick_spacing);
-        let lower_tick_index = get_min_tick(lp_pool.tick_spacing);
 
-        let (unclaimed_fee_x, unclaimed_fee_y, current_liquidity) = if lp_pool.position_exists {
-            let position = *try_from!(AccountLoader::<Position>, &self.position)?.load()?;
-            let tokens_owed_x = TokenAmount::from_decimal(position.tokens_owed_x);
-            let tokens_owed_y = TokenAmount::from_decimal(position.tokens_owed_y);
-            let liquidity = Liquidity::new(position.liquidity.v);
-            (tokens_owed_x, tokens_owed_y, liquidity)
-        } else {
-            (TokenAmount::new(0), TokenAmount::new(0), Liquidity::new(0))
-        };
+        let performance_fee = lp_pool.performance_fee;
+        let (harvested_x, performance_fee_x) = compute_performance_fee(claimed_x, performance_fee)
+            .map_err(|_| InvalidPerformanceFee)?;
+        let (harvested_y, performance_fee_y) = compute_performance_fee(claimed_y, performance_fee)
+            .map_err(|_| InvalidPerformanceFee)?;
+        lp_pool.fee_protocol_token_x = lp_pool
+            .fee_protocol_token_x
+            .checked_add(performance_fee_x.0)
+            .unwrap();
+        lp_pool.fee_protocol_token_y = lp_pool
+            .fee_protocol_token_y
+            .checked_add(performance_fee_y.0)
+            .unwrap();
+
+        let token_x = self.token_x.to_account_info();
+        let token_y = self.token_y.to_account_info();
+        let accumulated_x = TokenAmount::new(lp_pool.leftover_x) + harvested_x;
+        let accumulated_y = TokenAmount::new(lp_pool.leftover_y) + harvested_y;
 
         let shares = compute_lp_share_change(
             ADD,
-            TokenAmount::new(self.token_lp.supply),
+            SharePrice {
+                lp_token_supply: TokenAmount::new(self.token_lp.supply),
+                liquidity: current_liquidity,
+                liquidity_per_lp_token: lp_pool.liquidity_per_lp_token,
+            },
             liquidity,
-            current_liquidity,
-            TokenAmount::new(lp_pool.leftover_x) + unclaimed_fee_x,
-            TokenAmount::new(lp_pool.leftover_y) + unclaimed_fee_y,
-            lp_pool.tick_spacing,
+            (accumulated_x, accumulated_y),
+            TickRange {
+                lower_tick: lp_pool.lower_tick_index,
+                upper_tick: lp_pool.upper_tick_index,
+            },
             current_tick_index,
             current_sqrt_price,
         )
         .unwrap();
 
-        let (deposited_x, deposited_y) = shares.transferred_amounts;
+        let liquidity_cap = lp_pool.liquidity_cap;
+        require!(
+            liquidity_cap.is_zero()
+                || current_liquidity + shares.liquidity_change.l <= liquidity_cap,
+            ErrorCode::LiquidityCapExceeded
+        );
+
+        let (transferred_x, transferred_y) = shares.transferred_amounts;
         let (leftover_x, leftover_y) = shares.leftover_amounts;
-        lp_pool.leftover_x = leftover_x.0;
-        lp_pool.leftover_y = leftover_y.0;
+
+        // invariant pulls the re-deposited accumulated tokens together with the user's part,
+        // the user covers the transfer fee of their part and the leftovers cover the rest
+        let pulled_x = accumulated_x - leftover_x + transferred_x;
+        let pulled_y = accumulated_y - leftover_y + transferred_y;
+        let pull_fee_x = get_transfer_inverse_fee(&token_x, transferred_x.0)?;
+        let pull_fee_y = get_transfer_inverse_fee(&token_y, transferred_y.0)?;
+        let leftover_fee_x =
+            get_transfer_inverse_fee(&token_x, pulled_x.0)?.saturating_sub(pull_fee_x);
+        let leftover_fee_y =
+            get_transfer_inverse_fee(&token_y, pulled_y.0)?.saturating_sub(pull_fee_y);
+
+        let mint_fee = lp_pool.mint_fee;
+        let fee_x = compute_protocol_fee(transferred_x, mint_fee);
+        let fee_y = compute_protocol_fee(transferred_y, mint_fee);
+        let deposited_x = transferred_x + fee_x + TokenAmount::new(pull_fee_x);
+        let deposited_y = transferred_y + fee_y + TokenAmount::new(pull_fee_y);
+        // deposit has to reach the reserves in full
+        let deposited_x =
+            deposited_x + TokenAmount::new(get_transfer_inverse_fee(&token_x, deposited_x.0)?);
+        let deposited_y =
+            deposited_y + TokenAmount::new(get_transfer_inverse_fee(&token_y, deposited_y.0)?);
+        require!(deposited_x <= max_amount_x, ErrorCode::AmountExceedsMax);
+        require!(deposited_y <= max_amount_y, ErrorCode::AmountExceedsMax);
+
+        // fee stays in the reserves until withdrawn by the admin
+        lp_pool.fee_protocol_token_x = lp_pool.fee_protocol_token_x.checked_add(fee_x.0).unwrap();
+        lp_pool.fee_protocol_token_y = lp_pool.fee_protocol_token_y.checked_add(fee_y.0).unwrap();
+
+        lp_pool.leftover_x = leftover_x.0.saturating_sub(leftover_fee_x);
+        lp_pool.leftover_y = leftover_y.0.saturating_sub(leftover_fee_y);
 
         match self.token_x_program.key() {
             token_2022::ID => token_2022::transfer_checked(
@@ -396,20 +538,8 @@ impl<'info> MintLpTokenCtx<'info> {
         };
 
         // update or open a position
-        let signer: &[&[&[u8]]] = get_signer!(self.state.load()?.bump_authority);
-
         let added_liquidity = shares.liquidity_change.l.v;
-        if lp_pool.position_exists {
-            // TODO gas optimization: only claim fee if it will affect liquidity
-            if unclaimed_fee_x + unclaimed_fee_y > TokenAmount::new(0) {
-                invariant::cpi::claim_fee(
-                    self.claim_fee().with_signer(signer),
-                    lp_pool.position_index,
-                    lower_tick_index,
-                    upper_tick_index,
-                )?;
-            }
-
+        if position_exists {
             invariant::cpi::change_liquidity(
                 self.change_liquidity().with_signer(signer),
                 lp_pool.position_index,
@@ -430,10 +560,147 @@ impl<'info> MintLpTokenCtx<'info> {
                 InvPrice::new(sqrt_price.v),
             )?;
         }
+
+        let lp_token_amount = shares.lp_token_change.unwrap_or_default().0;
+        emit!(MintLpTokenEvent {
+            lp_pool: self.lp_pool.key(),
+            owner: self.owner.key(),
+            liquidity: added_liquidity,
+            lp_token_amount,
+            amount_x: deposited_x.0,
+            amount_y: deposited_y.0,
+            mint_fee_x: fee_x.0,
+            mint_fee_y: fee_y.0,
+        });
+
         // mint LP tokens for user
-        mint_to(
-            self.mint_lp().with_signer(signer),
-            shares.lp_token_change.unwrap_or_default().0,
+        mint_to(self.mint_lp().with_signer(signer), lp_token_amount)?;
+
+        Ok(MintLpTokenResult {
+            amount_x: deposited_x.0,
+            amount_y: deposited_y.0,
+            lp_token_amount,
+            liquidity: added_liquidity,
+        })
+    }
+
+    pub fn process_by_amounts(
+        &mut self,
+        amount_x: TokenAmount,
+        amount_y: TokenAmount,
+    ) -> Result<MintLpTokenResult> {
+        let (lower_tick_index, upper_tick_index, mint_fee) = {
+            let lp_pool = self.lp_pool.load()?;
+            (
+                lp_pool.lower_tick_index,
+                lp_pool.upper_tick_index,
+                lp_pool.mint_fee,
+            )
+        };
+        let (current_tick_index, sqrt_price) = {
+            let pool = self.pool.load()?;
+            (pool.current_tick_index, pool.sqrt_price)
+        };
+        // part of the amounts covers the transfer fees and the mint fee
+        let available_x = net_of_transfer_fees(&self.token_x.to_account_info(), amount_x)?;
+        let available_y = net_of_transfer_fees(&self.token_y.to_account_info(), amount_y)?;
+        let liquidity = compute_max_liquidity_for_amounts(
+            available_x - compute_protocol_fee(available_x, mint_fee),
+            available_y - compute_protocol_fee(available_y, mint_fee),
+            lower_tick_index,
+            upper_tick_index,
+            current_tick_index,
+            Price::new(sqrt_price.v),
         )
+        .map_err(|_| AmountsTooSmall)?;
+
+        // unused part of the deposit stays in the user's accounts
+        self.process(liquidity, amount_x, amount_y)
+    }
+
+    pub fn process_zap_in(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        x_to_y: bool,
+        amount: TokenAmount,
+        sqrt_price_limit: u128,
+        min_lp_token_out: TokenAmount,
+    ) -> Result<MintLpTokenResult> {
+        self.validate_pool()?;
+
+        let swap_amount = {
+            let lp_pool = self.lp_pool.load()?;
+            let pool = self.pool.load()?;
+            compute_zap_in_swap_amount(
+                amount,
+                x_to_y,
+                Liquidity::new(pool.liquidity.v),
+                Price::new(pool.sqrt_price.v),
+                FixedPoint::new(pool.fee.v),
+                lp_pool.lower_tick_index,
+                lp_pool.upper_tick_index,
+            )
+            .map_err(|_| InvalidZapAmount)?
+        };
+
+        let (swap_amount_in, swap_amount_out) = if swap_amount.is_zero() {
+            (TokenAmount::new(0), TokenAmount::new(0))
+        } else {
+            let balance_x_before = self.account_x.amount;
+            let balance_y_before = self.account_y.amount;
+
+            // transfer fee of the input is sent on top of the swapped amount
+            let mint_in = match x_to_y {
+                true => self.token_x.to_account_info(),
+                false => self.token_y.to_account_info(),
+            };
+            let max_amount_in = swap_amount.0 + get_transfer_inverse_fee(&mint_in, swap_amount.0)?;
+            invariant::cpi::swap(
+                self.swap(remaining_accounts),
+                x_to_y,
+                swap_amount.0,
+                true,
+                sqrt_price_limit,
+                max_amount_in,
+                0,
+            )?;
+
+            self.account_x.reload()?;
+            self.account_y.reload()?;
+            match x_to_y {
+                true => (
+                    TokenAmount::new(balance_x_before - self.account_x.amount),
+                    TokenAmount::new(self.account_y.amount - balance_y_before),
+                ),
+                false => (
+                    TokenAmount::new(balance_y_before - self.account_y.amount),
+                    TokenAmount::new(self.account_x.amount - balance_x_before),
+                ),
+            }
+        };
+
+        // only the zapped amount and the swap output can be deposited
+        let (amount_x, amount_y) = match x_to_y {
+            true => (amount - swap_amount_in, swap_amount_out),
+            false => (swap_amount_out, amount - swap_amount_in),
+        };
+        let minted = self.process_by_amounts(amount_x, amount_y)?;
+        require!(
+            TokenAmount::new(minted.lp_token_amount) >= min_lp_token_out,
+            ErrorCode::AmountBelowMin
+        );
+
+        emit!(ZapInEvent {
+            lp_pool: self.lp_pool.key(),
+            owner: self.owner.key(),
+            x_to_y,
+            amount: amount.0,
+            swap_amount_in: swap_amount_in.0,
+            swap_amount_out: swap_amount_out.0,
+            lp_token_amount: minted.lp_token_amount,
+            liquidity: minted.liquidity,
+        });
+
+        Ok(minted)
     }
 }
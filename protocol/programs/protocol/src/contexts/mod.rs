@@ -1,9 +1,33 @@
+mod accept_admin;
 mod burn;
+mod compound;
 mod init;
 mod init_pool;
+mod migrate_lp_pool;
+mod migrate_state;
 mod mint;
+mod propose_admin;
+mod rebalance;
+mod set_lp_pool_fees;
+mod set_lp_pool_limits;
+mod set_lp_token_uri;
+mod set_paused;
+mod set_rebalance_config;
+mod withdraw_lp_protocol_fees;
 
+pub use accept_admin::*;
 pub use burn::*;
+pub use compound::*;
 pub use init::*;
 pub use init_pool::*;
+pub use migrate_lp_pool::*;
+pub use migrate_state::*;
 pub use mint::*;
+pub use propose_admin::*;
+pub use rebalance::*;
+pub use set_lp_pool_fees::*;
+pub use set_lp_pool_limits::*;
+pub use set_lp_token_uri::*;
+pub use set_paused::*;
+pub use set_rebalance_config::*;
+pub use withdraw_lp_protocol_fees::*;
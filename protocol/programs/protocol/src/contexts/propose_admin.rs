@@ -0,0 +1,22 @@
+use crate::states::{DerivedAccountIdentifier, State};
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposeAdminCtx<'info> {
+    #[account(mut,
+        seeds = [State::IDENT],
+        bump = state.load()?.bump,
+        constraint = state.load()?.admin == admin.key() @ InvalidAuthority
+    )]
+    pub state: AccountLoader<'info, State>,
+    pub admin: Signer<'info>,
+}
+
+impl ProposeAdminCtx<'_> {
+    pub fn process(&mut self, new_admin: Pubkey) -> Result<()> {
+        // takes effect once accepted by the new admin
+        self.state.load_mut()?.pending_admin = new_admin;
+        Ok(())
+    }
+}
@@ -0,0 +1,511 @@
+use std::cell::RefMut;
+
+use crate::decimals::{FixedPoint, Liquidity, Price, TokenAmount};
+use crate::math::{
+    calculate_amount_delta, compute_max_liquidity_for_amounts, compute_performance_fee,
+    compute_rebalance_range, compute_sqrt_price_limit, compute_zap_in_swap_amount,
+};
+use crate::states::{DerivedAccountIdentifier, LpPool, State};
+use crate::{
+    get_signer, try_from,
+    ErrorCode::{self, *},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount as ITokenAccount, TokenInterface};
+use decimal::{BigOps, Decimal};
+use invariant::cpi::accounts::{ClaimFee, CreatePosition, RemovePosition, Swap};
+use invariant::decimals::{Liquidity as InvLiquidity, Price as InvPrice};
+use invariant::math::calculate_twap_tick;
+use invariant::structs::{Oracle, Pool, Position, PositionList};
+use invariant::token_extensions::{get_transfer_fee, get_transfer_inverse_fee};
+
+const ADD: bool = true;
+
+#[derive(Accounts)]
+pub struct RebalanceCtx<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    #[account(
+        seeds = [State::IDENT],
+        bump = state.load()?.bump
+    )]
+    pub state: AccountLoader<'info, State>,
+    /// CHECK: cached from the state account
+    #[account(mut)]
+    #[account(constraint = &state.load()?.program_authority == program_authority.key @ InvalidAuthority)]
+    pub program_authority: AccountInfo<'info>,
+    #[account(mut,
+        seeds = [LpPool::IDENT, token_x.key().as_ref(), token_y.key().as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes(), &lp_pool.load()?.initial_range_seed()],
+        bump = lp_pool.load()?.bump,
+        constraint = lp_pool.load()?.keeper == keeper.key() @ InvalidKeeper
+    )]
+    pub lp_pool: AccountLoader<'info, LpPool>,
+    // validated right before removing the position
+    #[account(mut)]
+    pub last_position_lp_pool: AccountLoader<'info, LpPool>,
+    #[account(mut,
+        associated_token::mint = token_x,
+        associated_token::authority = program_authority,
+        associated_token::token_program = token_x_program,
+    )]
+    pub reserve_x: Box<InterfaceAccount<'info, ITokenAccount>>,
+    #[account(mut,
+        associated_token::mint = token_y,
+        associated_token::authority = program_authority,
+        associated_token::token_program = token_y_program,
+    )]
+    pub reserve_y: Box<InterfaceAccount<'info, ITokenAccount>>,
+    /// INVARIANT
+    /// CHECK: passed to Invariant
+    pub inv_program: UncheckedAccount<'info>,
+    /// CHECK: passed to Invariant
+    pub inv_state: UncheckedAccount<'info>,
+    /// CHECK: invariant_program_authority is the authority of the Invariant program
+    pub inv_program_authority: UncheckedAccount<'info>,
+    /// CHECK: might not exist, explicit check in the handler
+    #[account(mut)]
+    pub position: AccountInfo<'info>,
+    /// CHECK: might not exist, validated right before removing the position
+    #[account(mut)]
+    pub last_position: AccountInfo<'info>,
+    /// CHECK: initialized by Invariant
+    #[account(mut)]
+    pub new_position: UncheckedAccount<'info>,
+    #[account(mut,
+        // validated in the handler!
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    /// CHECK: loaded with extension in the handler
+    #[account(mut, constraint = oracle.key() == pool.load()?.oracle_address @ InvalidOracle)]
+    pub oracle: UncheckedAccount<'info>,
+    #[account(mut,
+        seeds = [b"positionlistv1", program_authority.key().as_ref()],
+        seeds::program = invariant::ID,
+        bump = position_list.load()?.bump )]
+    pub position_list: AccountLoader<'info, PositionList>,
+    /// CHECK: passed to Invariant
+    #[account(mut)]
+    pub lower_tick: UncheckedAccount<'info>,
+    /// CHECK: passed to Invariant
+    #[account(mut)]
+    pub upper_tick: UncheckedAccount<'info>,
+    /// CHECK: passed to Invariant
+    #[account(mut)]
+    pub new_lower_tick: UncheckedAccount<'info>,
+    /// CHECK: passed to Invariant
+    #[account(mut)]
+    pub new_upper_tick: UncheckedAccount<'info>,
+    /// CHECK: passed to Invariant
+    #[account(mut)]
+    pub tickmap: UncheckedAccount<'info>,
+    pub token_x: Box<InterfaceAccount<'info, Mint>>,
+    pub token_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub inv_reserve_x: Box<InterfaceAccount<'info, ITokenAccount>>,
+    #[account(mut)]
+    pub inv_reserve_y: Box<InterfaceAccount<'info, ITokenAccount>>,
+    pub token_x_program: Interface<'info, TokenInterface>,
+    pub token_y_program: Interface<'info, TokenInterface>,
+    /// CHECK: passed to Invariant
+    pub rent: UncheckedAccount<'info>,
+    /// CHECK: no inits here, passed to Invariant
+    pub system_program: UncheckedAccount<'info>,
+}
+
+impl<'info> RebalanceCtx<'info> {
+    pub fn claim_fee(&self) -> CpiContext<'_, '_, '_, 'info, ClaimFee<'info>> {
+        CpiContext::new(
+            self.inv_program.to_account_info(),
+            ClaimFee {
+                state: self.inv_state.to_account_info(),
+                pool: self.pool.to_account_info(),
+                position: self.position.to_account_info(),
+                lower_tick: self.lower_tick.to_account_info(),
+                upper_tick: self.upper_tick.to_account_info(),
+                owner: self.program_authority.to_account_info(),
+                token_x: self.token_x.to_account_info(),
+                token_y: self.token_y.to_account_info(),
+                account_x: self.reserve_x.to_account_info(),
+                account_y: self.reserve_y.to_account_info(),
+                reserve_x: self.inv_reserve_x.to_account_info(),
+                reserve_y: self.inv_reserve_y.to_account_info(),
+                program_authority: self.inv_program_authority.to_account_info(),
+                token_x_program: self.token_x_program.to_account_info(),
+                token_y_program: self.token_y_program.to_account_info(),
+            },
+        )
+    }
+
+    pub fn remove_position(&self) -> CpiContext<'_, '_, '_, 'info, RemovePosition<'info>> {
+        CpiContext::new(
+            self.inv_program.to_account_info(),
+            RemovePosition {
+                state: self.inv_state.to_account_info(),
+                program_authority: self.inv_program_authority.to_account_info(),
+                payer: self.keeper.to_account_info(),
+                owner: self.program_authority.to_account_info(),
+                removed_position: self.position.to_account_info(),
+                position_list: self.position_list.to_account_info(),
+                last_position: self.last_position.to_account_info(),
+                pool: self.pool.to_account_info(),
+                tickmap: self.tickmap.to_account_info(),
+                lower_tick: self.lower_tick.to_account_info(),
+                upper_tick: self.upper_tick.to_account_info(),
+                token_x: self.token_x.to_account_info(),
+                token_y: self.token_y.to_account_info(),
+                account_x: self.reserve_x.to_account_info(),
+                account_y: self.reserve_y.to_account_info(),
+                reserve_x: self.inv_reserve_x.to_account_info(),
+                reserve_y: self.inv_reserve_y.to_account_info(),
+                token_x_program: self.token_x_program.to_account_info(),
+                token_y_program: self.token_y_program.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(vec![self.oracle.to_account_info()])
+    }
+
+    pub fn swap(&self, ticks: &[AccountInfo<'info>]) -> CpiContext<'_, '_, '_, 'info, Swap<'info>> {
+        CpiContext::new(
+            self.inv_program.to_account_info(),
+            Swap {
+                state: self.inv_state.to_account_info(),
+                pool: self.pool.to_account_info(),
+                tickmap: self.tickmap.to_account_info(),
+                token_x: self.token_x.to_account_info(),
+                token_y: self.token_y.to_account_info(),
+                account_x: self.reserve_x.to_account_info(),
+                account_y: self.reserve_y.to_account_info(),
+                reserve_x: self.inv_reserve_x.to_account_info(),
+                reserve_y: self.inv_reserve_y.to_account_info(),
+                owner: self.program_authority.to_account_info(),
+                program_authority: self.inv_program_authority.to_account_info(),
+                token_x_program: self.token_x_program.to_account_info(),
+                token_y_program: self.token_y_program.to_account_info(),
+                referral: None,
+                referral_registry: None,
+            },
+        )
+        .with_remaining_accounts([ticks, &[self.oracle.to_account_info()]].concat())
+    }
+
+    pub fn create_position(&self) -> CpiContext<'_, '_, '_, 'info, CreatePosition<'info>> {
+        CpiContext::new(
+            self.inv_program.to_account_info(),
+            CreatePosition {
+                state: self.inv_state.to_account_info(),
+                position: self.new_position.to_account_info(),
+                pool: self.pool.to_account_info(),
+                position_list: self.position_list.to_account_info(),
+                payer: self.keeper.to_account_info(),
+                owner: self.program_authority.to_account_info(),
+                lower_tick: self.new_lower_tick.to_account_info(),
+                upper_tick: self.new_upper_tick.to_account_info(),
+                tickmap: self.tickmap.to_account_info(),
+                token_x: self.token_x.to_account_info(),
+                token_y: self.token_y.to_account_info(),
+                account_x: self.reserve_x.to_account_info(),
+                account_y: self.reserve_y.to_account_info(),
+                reserve_x: self.inv_reserve_x.to_account_info(),
+                reserve_y: self.inv_reserve_y.to_account_info(),
+                program_authority: self.inv_program_authority.to_account_info(),
+                token_x_program: self.token_x_program.to_account_info(),
+                token_y_program: self.token_y_program.to_account_info(),
+                rent: self.rent.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(vec![self.oracle.to_account_info()])
+    }
+
+    pub fn validate_pool(&self) -> Result<()> {
+        let lp_pool = &self.lp_pool.load()?;
+        let pool = &self.pool.load()?;
+        require_keys_eq!(pool.token_x, self.token_x.key());
+        require_keys_eq!(pool.token_y, self.token_y.key());
+        require_eq!(pool.fee.v, lp_pool.fee.v);
+        require_eq!(pool.tick_spacing, lp_pool.tick_spacing);
+        Ok(())
+    }
+
+    pub fn validate_position(&self) -> Result<()> {
+        let lp_pool = &self.lp_pool.load()?;
+
+        let seeds = [
+            b"positionv1",
+            self.program_authority.key.as_ref(),
+            &lp_pool.position_index.to_le_bytes(),
+        ];
+        let (position_key, position_bump) = Pubkey::find_program_address(&seeds, &invariant::ID);
+        require_keys_eq!(position_key, self.position.key());
+        require_eq!(
+            position_bump,
+            try_from!(AccountLoader::<Position>, &self.position)?
+                .load()?
+                .bump
+        );
+
+        Ok(())
+    }
+
+    pub fn validate_last_position(&self) -> Result<()> {
+        if self.last_position_lp_pool.key() != self.lp_pool.key() {
+            require_eq!(
+                self.last_position_lp_pool.load()?.position_index,
+                self.position_list.load()?.head - 1
+            );
+            let seeds = [
+                b"positionv1",
+                self.program_authority.key.as_ref(),
+                &self
+                    .last_position_lp_pool
+                    .load()?
+                    .position_index
+                    .to_le_bytes(),
+            ];
+            let (position_key, position_bump) =
+                Pubkey::find_program_address(&seeds, &invariant::ID);
+            require_keys_eq!(position_key, self.last_position.key());
+            require_eq!(
+                position_bump,
+                try_from!(AccountLoader::<Position>, &self.last_position)?
+                    .load()?
+                    .bump
+            );
+        }
+        Ok(())
+    }
+
+    // average tick over the last twap_window seconds, can't be moved within a single transaction
+    pub fn twap_tick(&self, current_timestamp: u64, twap_window: u32) -> Result<i32> {
+        let pool = self.pool.load()?;
+        let (oracle, extension) = Oracle::load_with_extension(&self.oracle)?;
+        let seconds_per_liquidity = pool.seconds_per_liquidity_at(current_timestamp);
+        let observe = |seconds_ago: u64| {
+            oracle.observe(
+                &extension,
+                current_timestamp,
+                seconds_ago,
+                pool.sqrt_price,
+                pool.current_tick_index,
+                seconds_per_liquidity,
+            )
+        };
+        Ok(calculate_twap_tick(
+            &observe(twap_window as u64)?,
+            &observe(0)?,
+        ))
+    }
+
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        self.validate_pool()?;
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let start_tick_index = self.pool.load()?.current_tick_index;
+        let twap_window = self.lp_pool.load()?.twap_window;
+        let twap_tick_index = self.twap_tick(current_timestamp as u64, twap_window)?;
+        let mut lp_pool: RefMut<LpPool> = self.lp_pool.load_mut()?;
+        require!(
+            current_timestamp >= lp_pool.last_rebalance_timestamp + lp_pool.rebalance_cooldown,
+            ErrorCode::RebalanceCooldown
+        );
+        require!(
+            (start_tick_index - twap_tick_index).abs() <= lp_pool.max_tick_deviation,
+            ErrorCode::TickDeviationExceeded
+        );
+
+        let (lower_tick_index, upper_tick_index) =
+            compute_rebalance_range(twap_tick_index, lp_pool.tick_spacing, lp_pool.range_width);
+        lp_pool.last_rebalance_timestamp = current_timestamp;
+
+        // nothing is deposited, only the range is moved
+        if !lp_pool.position_exists {
+            lp_pool.lower_tick_index = lower_tick_index;
+            lp_pool.upper_tick_index = upper_tick_index;
+            return Ok(());
+        }
+        drop(lp_pool);
+        self.validate_position()?;
+
+        let signer: &[&[&[u8]]] = get_signer!(self.state.load()?.bump_authority);
+        let mut lp_pool: RefMut<LpPool> = self.lp_pool.load_mut()?;
+
+        // withdraw the whole position, the fee is claimed first to charge
+        // the performance fee on what was actually harvested
+        let (amount_x, amount_y) = {
+            let position = *try_from!(AccountLoader::<Position>, &self.position)?.load()?;
+            let balance_x_before = self.reserve_x.amount;
+            let balance_y_before = self.reserve_y.amount;
+
+            invariant::cpi::claim_fee(
+                self.claim_fee().with_signer(signer),
+                lp_pool.position_index,
+                position.lower_tick_index,
+                position.upper_tick_index,
+            )?;
+            self.reserve_x.reload()?;
+            self.reserve_y.reload()?;
+            let claimed_x = TokenAmount::new(self.reserve_x.amount - balance_x_before);
+            let claimed_y = TokenAmount::new(self.reserve_y.amount - balance_y_before);
+
+            self.validate_last_position()?;
+            invariant::cpi::remove_position(
+                self.remove_position().with_signer(signer),
+                lp_pool.position_index,
+                position.lower_tick_index,
+                position.upper_tick_index,
+            )?;
+            if self.last_position_lp_pool.key() != self.lp_pool.key() {
+                self.last_position_lp_pool.load_mut()?.position_index = lp_pool.position_index;
+            }
+            lp_pool.position_index = u32::MAX;
+            lp_pool.position_exists = false;
+
+            let performance_fee = lp_pool.performance_fee;
+            let (_, performance_fee_x) = compute_performance_fee(claimed_x, performance_fee)
+                .map_err(|_| InvalidPerformanceFee)?;
+            let (_, performance_fee_y) = compute_performance_fee(claimed_y, performance_fee)
+                .map_err(|_| InvalidPerformanceFee)?;
+            lp_pool.fee_protocol_token_x = lp_pool
+                .fee_protocol_token_x
+                .checked_add(performance_fee_x.0)
+                .unwrap();
+            lp_pool.fee_protocol_token_y = lp_pool
+                .fee_protocol_token_y
+                .checked_add(performance_fee_y.0)
+                .unwrap();
+
+            self.reserve_x.reload()?;
+            self.reserve_y.reload()?;
+            (
+                TokenAmount::new(lp_pool.leftover_x + self.reserve_x.amount - balance_x_before)
+                    - performance_fee_x,
+                TokenAmount::new(lp_pool.leftover_y + self.reserve_y.amount - balance_y_before)
+                    - performance_fee_y,
+            )
+        };
+
+        // swap the excess of one token so that both fit the new range
+        let (amount_x, amount_y) = {
+            let pool = *self.pool.load()?;
+            let sqrt_price = Price::new(pool.sqrt_price.v);
+            let (required_x, required_y) = match compute_max_liquidity_for_amounts(
+                amount_x,
+                amount_y,
+                lower_tick_index,
+                upper_tick_index,
+                pool.current_tick_index,
+                sqrt_price,
+            ) {
+                Ok(liquidity) => calculate_amount_delta(
+                    sqrt_price,
+                    liquidity,
+                    ADD,
+                    pool.current_tick_index,
+                    lower_tick_index,
+                    upper_tick_index,
+                )
+                .unwrap(),
+                Err(_) => (TokenAmount::new(0), TokenAmount::new(0)),
+            };
+            let excess_x = amount_x - required_x;
+            let excess_y = amount_y - required_y;
+
+            // one of the tokens is used up to the rounding, the other one is swapped
+            let x_to_y = excess_x.big_mul(sqrt_price).big_mul(sqrt_price) > excess_y;
+            let swap_amount = compute_zap_in_swap_amount(
+                if x_to_y { excess_x } else { excess_y },
+                x_to_y,
+                Liquidity::new(pool.liquidity.v),
+                sqrt_price,
+                FixedPoint::new(pool.fee.v),
+                lower_tick_index,
+                upper_tick_index,
+            )
+            .unwrap_or_default();
+
+            if swap_amount.is_zero() {
+                (amount_x, amount_y)
+            } else {
+                let balance_x_before = self.reserve_x.amount;
+                let balance_y_before = self.reserve_y.amount;
+
+                // transfer fee of the input is sent on top of the swapped amount
+                let mint_in = match x_to_y {
+                    true => self.token_x.to_account_info(),
+                    false => self.token_y.to_account_info(),
+                };
+                let max_amount_in =
+                    swap_amount.get() + get_transfer_inverse_fee(&mint_in, swap_amount.get())?;
+                // price can't be pushed further than the configured deviation from the average
+                invariant::cpi::swap(
+                    self.swap(remaining_accounts).with_signer(signer),
+                    x_to_y,
+                    swap_amount.get(),
+                    true,
+                    compute_sqrt_price_limit(twap_tick_index, x_to_y, lp_pool.max_tick_deviation)
+                        .get(),
+                    max_amount_in,
+                    0,
+                )?;
+
+                self.reserve_x.reload()?;
+                self.reserve_y.reload()?;
+                match x_to_y {
+                    true => (
+                        amount_x - TokenAmount::new(balance_x_before - self.reserve_x.amount),
+                        amount_y + TokenAmount::new(self.reserve_y.amount - balance_y_before),
+                    ),
+                    false => (
+                        amount_x + TokenAmount::new(self.reserve_x.amount - balance_x_before),
+                        amount_y - TokenAmount::new(balance_y_before - self.reserve_y.amount),
+                    ),
+                }
+            }
+        };
+
+        // open a position in the new range
+        let pool = *self.pool.load()?;
+        let sqrt_price = Price::new(pool.sqrt_price.v);
+        lp_pool.lower_tick_index = lower_tick_index;
+        lp_pool.upper_tick_index = upper_tick_index;
+
+        // withdrawn and swapped amounts are measured in the reserves, so they are already
+        // net of the transfer fee, invariant pulls the deposit with the fee on top
+        let token_x = self.token_x.to_account_info();
+        let token_y = self.token_y.to_account_info();
+        // lp tokens stay outstanding, so the pool can't be left without a position
+        let liquidity = compute_max_liquidity_for_amounts(
+            amount_x - TokenAmount::new(get_transfer_fee(&token_x, amount_x.0)?),
+            amount_y - TokenAmount::new(get_transfer_fee(&token_y, amount_y.0)?),
+            lower_tick_index,
+            upper_tick_index,
+            pool.current_tick_index,
+            sqrt_price,
+        )
+        .map_err(|_| AmountsTooSmall)?;
+        let (deposited_x, deposited_y) = calculate_amount_delta(
+            sqrt_price,
+            liquidity,
+            ADD,
+            pool.current_tick_index,
+            lower_tick_index,
+            upper_tick_index,
+        )
+        .unwrap();
+        let pulled_x = deposited_x.0 + get_transfer_inverse_fee(&token_x, deposited_x.0)?;
+        let pulled_y = deposited_y.0 + get_transfer_inverse_fee(&token_y, deposited_y.0)?;
+        lp_pool.leftover_x = amount_x.get().saturating_sub(pulled_x);
+        lp_pool.leftover_y = amount_y.get().saturating_sub(pulled_y);
+
+        lp_pool.position_index = self.position_list.load()?.head;
+        lp_pool.position_exists = true;
+        invariant::cpi::create_position(
+            self.create_position().with_signer(signer),
+            lower_tick_index,
+            upper_tick_index,
+            InvLiquidity::new(liquidity.v),
+            InvPrice::new(pool.sqrt_price.v),
+            InvPrice::new(pool.sqrt_price.v),
+        )
+    }
+}
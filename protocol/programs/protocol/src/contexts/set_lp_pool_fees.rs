@@ -0,0 +1,47 @@
+use crate::decimals::FixedPoint;
+use crate::math::MAX_PERFORMANCE_FEE;
+use crate::states::{DerivedAccountIdentifier, LpPool, State};
+use crate::ErrorCode::{self, *};
+use anchor_lang::prelude::*;
+use decimal::{Decimal, Factories};
+
+#[derive(Accounts)]
+pub struct SetLpPoolFeesCtx<'info> {
+    #[account(
+        seeds = [State::IDENT],
+        bump = state.load()?.bump,
+        constraint = state.load()?.admin == admin.key() @ InvalidAuthority
+    )]
+    pub state: AccountLoader<'info, State>,
+    #[account(mut,
+        seeds = [LpPool::IDENT, lp_pool.load()?.token_x.as_ref(), lp_pool.load()?.token_y.as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes(), &lp_pool.load()?.initial_range_seed()],
+        bump = lp_pool.load()?.bump,
+    )]
+    pub lp_pool: AccountLoader<'info, LpPool>,
+    pub admin: Signer<'info>,
+}
+
+impl SetLpPoolFeesCtx<'_> {
+    pub fn process(
+        &mut self,
+        mint_fee: FixedPoint,
+        burn_fee: FixedPoint,
+        performance_fee: FixedPoint,
+    ) -> Result<()> {
+        require!(
+            mint_fee < FixedPoint::from_integer(1) && burn_fee < FixedPoint::from_integer(1),
+            ErrorCode::InvalidFee
+        );
+        require!(
+            performance_fee <= FixedPoint::new(MAX_PERFORMANCE_FEE),
+            ErrorCode::InvalidPerformanceFee
+        );
+
+        let lp_pool = &mut self.lp_pool.load_mut()?;
+        lp_pool.mint_fee = mint_fee;
+        lp_pool.burn_fee = burn_fee;
+        lp_pool.performance_fee = performance_fee;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use crate::decimals::Liquidity;
+use crate::states::{DerivedAccountIdentifier, LpPool, State};
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetLpPoolLimitsCtx<'info> {
+    #[account(
+        seeds = [State::IDENT],
+        bump = state.load()?.bump,
+        constraint = state.load()?.admin == admin.key() @ InvalidAuthority
+    )]
+    pub state: AccountLoader<'info, State>,
+    #[account(mut,
+        seeds = [LpPool::IDENT, lp_pool.load()?.token_x.as_ref(), lp_pool.load()?.token_y.as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes(), &lp_pool.load()?.initial_range_seed()],
+        bump = lp_pool.load()?.bump,
+    )]
+    pub lp_pool: AccountLoader<'info, LpPool>,
+    pub admin: Signer<'info>,
+}
+
+impl SetLpPoolLimitsCtx<'_> {
+    pub fn process(&mut self, paused: bool, liquidity_cap: Liquidity) -> Result<()> {
+        let lp_pool = &mut self.lp_pool.load_mut()?;
+        lp_pool.paused = paused;
+        lp_pool.liquidity_cap = liquidity_cap;
+        Ok(())
+    }
+}
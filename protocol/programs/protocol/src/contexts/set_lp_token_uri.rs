@@ -0,0 +1,87 @@
+use crate::get_signer;
+use crate::states::{DerivedAccountIdentifier, LpPool, State, LP_TOKEN_IDENT};
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as Token2022Mint;
+use anchor_spl::token_2022::Token2022;
+use spl_token_metadata_interface::state::{Field, TokenMetadata};
+
+#[derive(Accounts)]
+pub struct SetLpTokenUriCtx<'info> {
+    #[account(
+        seeds = [State::IDENT],
+        bump = state.load()?.bump,
+        constraint = state.load()?.admin == admin.key() @ InvalidAuthority
+    )]
+    pub state: AccountLoader<'info, State>,
+    /// CHECK: cached from the state account
+    #[account(constraint = &state.load()?.program_authority == program_authority.key @ InvalidAuthority)]
+    pub program_authority: AccountInfo<'info>,
+    #[account(
+        seeds = [LpPool::IDENT, lp_pool.load()?.token_x.as_ref(), lp_pool.load()?.token_y.as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes(), &lp_pool.load()?.initial_range_seed()],
+        bump = lp_pool.load()?.bump,
+    )]
+    pub lp_pool: AccountLoader<'info, LpPool>,
+    /// CHECK: LP mint with the token metadata extension
+    #[account(mut,
+        seeds = [LP_TOKEN_IDENT, lp_pool.load()?.token_x.as_ref(), lp_pool.load()?.token_y.as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes(), &lp_pool.load()?.initial_range_seed()],
+        bump = lp_pool.load()?.token_bump,
+    )]
+    pub token_lp: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SetLpTokenUriCtx<'_> {
+    pub fn process(&mut self, uri: String) -> Result<()> {
+        // token program reallocates the mint, admin covers the rent of a longer uri
+        let space = {
+            let data = self.token_lp.try_borrow_data()?;
+            let mint = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+            let mut metadata = mint.get_variable_len_extension::<TokenMetadata>()?;
+            let current_size = metadata.tlv_size_of()?;
+            metadata.uri = uri.clone();
+            (data.len() + metadata.tlv_size_of()?).saturating_sub(current_size)
+        };
+        let rent = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(self.token_lp.lamports());
+        if rent > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.admin.to_account_info(),
+                        to: self.token_lp.to_account_info(),
+                    },
+                ),
+                rent,
+            )?;
+        }
+
+        invoke_signed(
+            &spl_token_metadata_interface::instruction::update_field(
+                &Token2022::id(),
+                self.token_lp.key,
+                self.program_authority.key,
+                Field::Uri,
+                uri,
+            ),
+            &[
+                self.token_lp.to_account_info(),
+                self.program_authority.to_account_info(),
+                self.token_program.to_account_info(),
+            ],
+            get_signer!(self.state.load()?.bump_authority),
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+use crate::states::{DerivedAccountIdentifier, State};
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPausedCtx<'info> {
+    #[account(mut,
+        seeds = [State::IDENT],
+        bump = state.load()?.bump,
+        constraint = state.load()?.admin == admin.key() @ InvalidAuthority
+    )]
+    pub state: AccountLoader<'info, State>,
+    pub admin: Signer<'info>,
+}
+
+impl SetPausedCtx<'_> {
+    pub fn process(&mut self, paused: bool) -> Result<()> {
+        self.state.load_mut()?.paused = paused;
+        Ok(())
+    }
+}
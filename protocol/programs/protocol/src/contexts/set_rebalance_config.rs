@@ -0,0 +1,56 @@
+use crate::math::{get_max_tick, get_min_tick};
+use crate::states::{DerivedAccountIdentifier, LpPool, State};
+use crate::ErrorCode::{self, *};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRebalanceConfigCtx<'info> {
+    #[account(
+        seeds = [State::IDENT],
+        bump = state.load()?.bump,
+        constraint = state.load()?.admin == admin.key() @ InvalidAuthority
+    )]
+    pub state: AccountLoader<'info, State>,
+    #[account(mut,
+        seeds = [LpPool::IDENT, lp_pool.load()?.token_x.as_ref(), lp_pool.load()?.token_y.as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes(), &lp_pool.load()?.initial_range_seed()],
+        bump = lp_pool.load()?.bump,
+    )]
+    pub lp_pool: AccountLoader<'info, LpPool>,
+    pub admin: Signer<'info>,
+}
+
+impl SetRebalanceConfigCtx<'_> {
+    pub fn process(
+        &mut self,
+        keeper: Pubkey,
+        range_width: i32,
+        rebalance_cooldown: i64,
+        max_tick_deviation: i32,
+        twap_window: u32,
+    ) -> Result<()> {
+        let lp_pool = &mut self.lp_pool.load_mut()?;
+        let tick_spacing = lp_pool.tick_spacing;
+
+        // default keeper turns the pool back into a static one
+        if keeper != Pubkey::default() {
+            require!(
+                range_width > 0
+                    && range_width % tick_spacing as i32 == 0
+                    && range_width <= get_max_tick(tick_spacing) - get_min_tick(tick_spacing),
+                ErrorCode::InvalidRebalanceConfig
+            );
+            require!(
+                rebalance_cooldown >= 0 && max_tick_deviation > 0 && twap_window > 0,
+                ErrorCode::InvalidRebalanceConfig
+            );
+        }
+
+        lp_pool.keeper = keeper;
+        lp_pool.range_width = range_width;
+        lp_pool.rebalance_cooldown = rebalance_cooldown;
+        lp_pool.max_tick_deviation = max_tick_deviation;
+        lp_pool.twap_window = twap_window;
+
+        Ok(())
+    }
+}
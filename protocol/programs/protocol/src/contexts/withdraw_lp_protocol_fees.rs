@@ -0,0 +1,143 @@
+use crate::events::WithdrawLpProtocolFeesEvent;
+use crate::states::{DerivedAccountIdentifier, LpPool, State};
+use crate::{get_signer, ErrorCode::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount as ITokenAccount, TokenInterface};
+use anchor_spl::{
+    token::{self},
+    token_2022,
+};
+
+#[derive(Accounts)]
+pub struct WithdrawLpProtocolFeesCtx<'info> {
+    #[account(
+        seeds = [State::IDENT],
+        bump = state.load()?.bump,
+        constraint = state.load()?.admin == admin.key() @ InvalidAuthority
+    )]
+    pub state: AccountLoader<'info, State>,
+    /// CHECK: cached from the state account
+    #[account(constraint = &state.load()?.program_authority == program_authority.key @ InvalidAuthority)]
+    pub program_authority: AccountInfo<'info>,
+    #[account(mut,
+        seeds = [LpPool::IDENT, token_x.key().as_ref(), token_y.key().as_ref(), &lp_pool.load()?.fee.v.to_le_bytes(), &lp_pool.load()?.tick_spacing.to_le_bytes(), &lp_pool.load()?.initial_range_seed()],
+        bump = lp_pool.load()?.bump,
+    )]
+    pub lp_pool: AccountLoader<'info, LpPool>,
+    #[account(mut,
+        associated_token::mint = token_x,
+        associated_token::authority = program_authority,
+        associated_token::token_program = token_x_program,
+    )]
+    pub reserve_x: Box<InterfaceAccount<'info, ITokenAccount>>,
+    #[account(mut,
+        associated_token::mint = token_y,
+        associated_token::authority = program_authority,
+        associated_token::token_program = token_y_program,
+    )]
+    pub reserve_y: Box<InterfaceAccount<'info, ITokenAccount>>,
+    #[account(mut,
+        constraint = account_x.mint == token_x.key() @ InvalidMint,
+        token::token_program = token_x_program,
+    )]
+    pub account_x: Box<InterfaceAccount<'info, ITokenAccount>>,
+    #[account(mut,
+        constraint = account_y.mint == token_y.key() @ InvalidMint,
+        token::token_program = token_y_program,
+    )]
+    pub account_y: Box<InterfaceAccount<'info, ITokenAccount>>,
+    pub token_x: Box<InterfaceAccount<'info, Mint>>,
+    pub token_y: Box<InterfaceAccount<'info, Mint>>,
+    pub admin: Signer<'info>,
+    pub token_x_program: Interface<'info, TokenInterface>,
+    pub token_y_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawLpProtocolFeesCtx<'info> {
+    pub fn withdraw_x(&self) -> CpiContext<'_, '_, '_, 'info, token::Transfer<'info>> {
+        CpiContext::new(
+            self.token_x_program.to_account_info(),
+            token::Transfer {
+                from: self.reserve_x.to_account_info(),
+                to: self.account_x.to_account_info(),
+                authority: self.program_authority.to_account_info(),
+            },
+        )
+    }
+    pub fn withdraw_x_2022(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, token_2022::TransferChecked<'info>> {
+        CpiContext::new(
+            self.token_x_program.to_account_info(),
+            token_2022::TransferChecked {
+                to: self.account_x.to_account_info(),
+                from: self.reserve_x.to_account_info(),
+                mint: self.token_x.to_account_info(),
+                authority: self.program_authority.to_account_info(),
+            },
+        )
+    }
+
+    pub fn withdraw_y(&self) -> CpiContext<'_, '_, '_, 'info, token::Transfer<'info>> {
+        CpiContext::new(
+            self.token_y_program.to_account_info(),
+            token::Transfer {
+                from: self.reserve_y.to_account_info(),
+                to: self.account_y.to_account_info(),
+                authority: self.program_authority.to_account_info(),
+            },
+        )
+    }
+    pub fn withdraw_y_2022(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, token_2022::TransferChecked<'info>> {
+        CpiContext::new(
+            self.token_y_program.to_account_info(),
+            token_2022::TransferChecked {
+                to: self.account_y.to_account_info(),
+                mint: self.token_y.to_account_info(),
+                from: self.reserve_y.to_account_info(),
+                authority: self.program_authority.to_account_info(),
+            },
+        )
+    }
+
+    pub fn process(&mut self) -> Result<()> {
+        let lp_pool = &mut self.lp_pool.load_mut()?;
+        let fee_x = lp_pool.fee_protocol_token_x;
+        let fee_y = lp_pool.fee_protocol_token_y;
+        lp_pool.fee_protocol_token_x = 0;
+        lp_pool.fee_protocol_token_y = 0;
+
+        let signer: &[&[&[u8]]] = get_signer!(self.state.load()?.bump_authority);
+
+        match self.token_x_program.key() {
+            token_2022::ID => token_2022::transfer_checked(
+                self.withdraw_x_2022().with_signer(signer),
+                fee_x,
+                self.token_x.decimals,
+            )?,
+            token::ID => token::transfer(self.withdraw_x().with_signer(signer), fee_x)?,
+            _ => return Err(InvalidTokenProgram.into()),
+        };
+
+        match self.token_y_program.key() {
+            token_2022::ID => token_2022::transfer_checked(
+                self.withdraw_y_2022().with_signer(signer),
+                fee_y,
+                self.token_y.decimals,
+            )?,
+            token::ID => token::transfer(self.withdraw_y().with_signer(signer), fee_y)?,
+            _ => return Err(InvalidTokenProgram.into()),
+        };
+
+        emit!(WithdrawLpProtocolFeesEvent {
+            lp_pool: self.lp_pool.key(),
+            admin: self.admin.key(),
+            amount_x: fee_x,
+            amount_y: fee_y,
+        });
+
+        Ok(())
+    }
+}
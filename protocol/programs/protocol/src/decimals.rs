@@ -21,7 +21,15 @@ pub struct Price {
 #[decimal(6)]
 #[zero_copy]
 #[derive(
-    Default, std::fmt::Debug, PartialEq, Eq, PartialOrd, Ord, AnchorSerialize, AnchorDeserialize,
+    Default,
+    std::fmt::Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    AnchorSerialize,
+    AnchorDeserialize,
+    InitSpace,
 )]
 pub struct Liquidity {
     pub v: u128,
@@ -18,13 +18,51 @@ pub enum ErrorCode {
     InvalidTokenProgram = 5, //0x131 (305)
     #[msg("Error originated from compute_lp_share_change")]
     InvalidShares = 6, //0x132 (306)
+    #[msg("Required token amount exceeds the provided maximum")]
+    AmountExceedsMax = 7, //0x133 (307)
+    #[msg("Returned token amount is below the provided minimum")]
+    AmountBelowMin = 8, //0x134 (308)
+    #[msg("Accumulated tokens are too small to be compounded")]
+    NothingToCompound = 9, //0x135 (309)
+    #[msg("Invalid lower or upper tick of the LP pool range")]
+    InvalidTickRange = 10, //0x136 (310)
+    #[msg("Invalid configuration of the managed range")]
+    InvalidRebalanceConfig = 11, //0x137 (311)
+    #[msg("LP pool is not managed or keeper is different than expected")]
+    InvalidKeeper = 12, //0x138 (312)
+    #[msg("Rebalance cooldown has not passed yet")]
+    RebalanceCooldown = 13, //0x139 (313)
+    #[msg("Fee has to be lower than 100%")]
+    InvalidFee = 14, //0x13A (314)
+    #[msg("Performance fee exceeds the maximum")]
+    InvalidPerformanceFee = 15, //0x13B (315)
+    #[msg("Minting is paused")]
+    Paused = 16, //0x13C (316)
+    #[msg("Liquidity of the position would exceed the cap")]
+    LiquidityCapExceeded = 17, //0x13D (317)
+    #[msg("Mint has an unsupported token extension")]
+    UnsupportedMintExtension = 18, //0x13E (318)
+    #[msg("Invalid LP token decimals or initial liquidity per LP token")]
+    InvalidLpTokenConfig = 19, //0x13F (319)
+    #[msg("Account is already in the current layout")]
+    AlreadyMigrated = 20, //0x140 (320)
+    #[msg("Provided oracle is different than expected")]
+    InvalidOracle = 21, //0x141 (321)
+    #[msg("Current tick deviates too much from the time weighted average")]
+    TickDeviationExceeded = 22, //0x142 (322)
+    #[msg("Provided amounts are too small to provide liquidity")]
+    AmountsTooSmall = 23, //0x143 (323)
+    #[msg("Failed to compute the amount swapped by the zap")]
+    InvalidZapAmount = 24, //0x144 (324)
+    #[msg("Provided metadata account is different than expected")]
+    InvalidMetadata = 25, //0x145 (325)
 }
 
 impl TryInto<ErrorCode> for u32 {
     type Error = (); // Error if u32 is out of range
 
     fn try_into(self) -> std::result::Result<ErrorCode, ()> {
-        if (300..=320).contains(&self) {
+        if (300..=325).contains(&self) {
             Ok(unsafe { std::mem::transmute(self - 300) })
         } else {
             Err(())
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct MintLpTokenEvent {
+    pub lp_pool: Pubkey,
+    pub owner: Pubkey,
+    pub liquidity: u128,
+    pub lp_token_amount: u64,
+    pub amount_x: u64, // mint fee included
+    pub amount_y: u64,
+    pub mint_fee_x: u64,
+    pub mint_fee_y: u64,
+}
+
+#[event]
+pub struct ZapInEvent {
+    pub lp_pool: Pubkey,
+    pub owner: Pubkey,
+    pub x_to_y: bool,
+    pub amount: u64,
+    pub swap_amount_in: u64, // transfer fee included
+    pub swap_amount_out: u64,
+    pub lp_token_amount: u64,
+    pub liquidity: u128,
+}
+
+#[event]
+pub struct BurnLpTokenEvent {
+    pub lp_pool: Pubkey,
+    pub owner: Pubkey,
+    pub liquidity: u128,
+    pub lp_token_amount: u64,
+    pub amount_x: u64, // burn fee excluded
+    pub amount_y: u64,
+    pub burn_fee_x: u64,
+    pub burn_fee_y: u64,
+}
+
+#[event]
+pub struct ZapOutEvent {
+    pub lp_pool: Pubkey,
+    pub owner: Pubkey,
+    pub x_to_y: bool,
+    pub lp_token_amount: u64,
+    pub liquidity: u128,
+    pub swap_amount_in: u64, // transfer fee included
+    pub swap_amount_out: u64,
+    pub amount_out: u64, // withdrawn and swapped
+}
+
+#[event]
+pub struct WithdrawLpProtocolFeesEvent {
+    pub lp_pool: Pubkey,
+    pub admin: Pubkey,
+    pub amount_x: u64, // transfer fee included
+    pub amount_y: u64,
+}
@@ -1,7 +1,10 @@
 mod contexts;
 pub mod decimals;
 mod errors;
+pub mod events;
 pub mod math;
+pub mod metadata;
+pub mod results;
 pub mod states;
 pub mod utils;
 
@@ -26,7 +29,8 @@ macro_rules! get_signer {
 #[program]
 pub mod protocol {
 
-    use decimals::Liquidity;
+    use decimals::{FixedPoint, Liquidity, TokenAmount};
+    use results::{BurnLpTokenResult, MintLpTokenResult};
 
     use super::*;
 
@@ -35,17 +39,163 @@ pub mod protocol {
         ctx.accounts.process(bump, bump_authority)
     }
 
-    pub fn init_lp_pool(ctx: Context<InitPoolCtx>) -> Result<()> {
+    pub fn init_lp_pool(
+        ctx: Context<InitPoolCtx>,
+        lower_tick_index: i32,
+        upper_tick_index: i32,
+        lp_token_decimals: u8,
+        liquidity_per_lp_token: u64,
+    ) -> Result<()> {
         let token_bump = ctx.bumps.token_lp;
         let bump = ctx.bumps.lp_pool;
-        ctx.accounts.process(token_bump, bump)
+        ctx.accounts.process(
+            lower_tick_index,
+            upper_tick_index,
+            lp_token_decimals,
+            liquidity_per_lp_token,
+            token_bump,
+            bump,
+        )
     }
 
-    pub fn mint_lp_token(ctx: Context<MintLpTokenCtx>, liquidity: u128) -> Result<()> {
-        ctx.accounts.process(Liquidity::new(liquidity))
+    pub fn migrate_lp_pool(ctx: Context<MigrateLpPoolCtx>) -> Result<()> {
+        ctx.accounts.process()
     }
 
-    pub fn burn_lp_token(ctx: Context<BurnLpTokenCtx>, liquidity: u128) -> Result<()> {
-        ctx.accounts.process(Liquidity::new(liquidity))
+    pub fn migrate_state(ctx: Context<MigrateStateCtx>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn mint_lp_token(
+        ctx: Context<MintLpTokenCtx>,
+        liquidity: u128,
+        max_amount_x: u64,
+        max_amount_y: u64,
+    ) -> Result<MintLpTokenResult> {
+        ctx.accounts.process(
+            Liquidity::new(liquidity),
+            TokenAmount::new(max_amount_x),
+            TokenAmount::new(max_amount_y),
+        )
+    }
+
+    pub fn mint_lp_token_by_amounts(
+        ctx: Context<MintLpTokenCtx>,
+        amount_x: u64,
+        amount_y: u64,
+    ) -> Result<MintLpTokenResult> {
+        ctx.accounts
+            .process_by_amounts(TokenAmount::new(amount_x), TokenAmount::new(amount_y))
+    }
+
+    pub fn zap_in<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintLpTokenCtx<'info>>,
+        x_to_y: bool,
+        amount: u64,
+        sqrt_price_limit: u128,
+        min_lp_token_out: u64,
+    ) -> Result<MintLpTokenResult> {
+        ctx.accounts.process_zap_in(
+            ctx.remaining_accounts,
+            x_to_y,
+            TokenAmount::new(amount),
+            sqrt_price_limit,
+            TokenAmount::new(min_lp_token_out),
+        )
+    }
+
+    pub fn burn_lp_token(
+        ctx: Context<BurnLpTokenCtx>,
+        liquidity: u128,
+        min_amount_x: u64,
+        min_amount_y: u64,
+    ) -> Result<BurnLpTokenResult> {
+        ctx.accounts.process(
+            Liquidity::new(liquidity),
+            TokenAmount::new(min_amount_x),
+            TokenAmount::new(min_amount_y),
+        )
+    }
+
+    pub fn compound(ctx: Context<CompoundCtx>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn zap_out<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BurnLpTokenCtx<'info>>,
+        liquidity: u128,
+        x_to_y: bool,
+        sqrt_price_limit: u128,
+        min_amount_out: u64,
+    ) -> Result<BurnLpTokenResult> {
+        ctx.accounts.process_zap_out(
+            ctx.remaining_accounts,
+            Liquidity::new(liquidity),
+            x_to_y,
+            sqrt_price_limit,
+            TokenAmount::new(min_amount_out),
+        )
+    }
+
+    pub fn set_rebalance_config(
+        ctx: Context<SetRebalanceConfigCtx>,
+        keeper: Pubkey,
+        range_width: i32,
+        rebalance_cooldown: i64,
+        max_tick_deviation: i32,
+        twap_window: u32,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            keeper,
+            range_width,
+            rebalance_cooldown,
+            max_tick_deviation,
+            twap_window,
+        )
+    }
+
+    pub fn rebalance<'info>(ctx: Context<'_, '_, 'info, 'info, RebalanceCtx<'info>>) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    pub fn set_lp_pool_fees(
+        ctx: Context<SetLpPoolFeesCtx>,
+        mint_fee: u128,
+        burn_fee: u128,
+        performance_fee: u128,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            FixedPoint::new(mint_fee),
+            FixedPoint::new(burn_fee),
+            FixedPoint::new(performance_fee),
+        )
+    }
+
+    pub fn withdraw_lp_protocol_fees(ctx: Context<WithdrawLpProtocolFeesCtx>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn set_paused(ctx: Context<SetPausedCtx>, paused: bool) -> Result<()> {
+        ctx.accounts.process(paused)
+    }
+
+    pub fn set_lp_pool_limits(
+        ctx: Context<SetLpPoolLimitsCtx>,
+        paused: bool,
+        liquidity_cap: u128,
+    ) -> Result<()> {
+        ctx.accounts.process(paused, Liquidity::new(liquidity_cap))
+    }
+
+    pub fn set_lp_token_uri(ctx: Context<SetLpTokenUriCtx>, uri: String) -> Result<()> {
+        ctx.accounts.process(uri)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdminCtx>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.process(new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdminCtx>) -> Result<()> {
+        ctx.accounts.process()
     }
 }
@@ -10,8 +10,11 @@ const MAX_TICK: i32 = 221_818;
 pub const TICK_LIMIT: i32 = 44_364;
 pub const LOG2_MAX_FULL_RANGE_LIQUIDITY: u32 = 85;
 pub const LOG2_MAX_TOKEN_ACCURACY: u32 = 64;
-pub const ONE_LP_TOKEN: u64 = 2_u64 // starting point for the price of the token in the pool
+const MAX_LIQUIDITY_FOR_AMOUNTS_ITERATIONS: u8 = 8;
+pub const MAX_PERFORMANCE_FEE: u128 = 300_000_000_000; // 30%
+pub const ONE_LP_TOKEN: u64 = 2_u64 // default starting point for the price of the token in the pool
     .pow(LOG2_MAX_FULL_RANGE_LIQUIDITY - LOG2_MAX_TOKEN_ACCURACY);
+pub const MAX_LP_TOKEN_DECIMALS: u8 = 9;
 
 #[derive(Debug)]
 pub struct LiquidityResult {
@@ -32,6 +35,20 @@ pub struct LiquidityChange {
     pub add: bool,
 }
 
+// ticks of the position backing the LP tokens
+#[derive(Debug, Clone, Copy)]
+pub struct TickRange {
+    pub lower_tick: i32,
+    pub upper_tick: i32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SharePrice {
+    pub lp_token_supply: TokenAmount,
+    pub liquidity: Liquidity,        // of the position backing the supply
+    pub liquidity_per_lp_token: u64, // used while the supply is zero
+}
+
 #[derive(Debug, Clone)]
 pub struct ComputeLpShareChangeResult {
     pub liquidity_change: LiquidityChange,
@@ -128,6 +145,45 @@ pub fn get_max_liquidity(
     let lower_sqrt_price = calculate_sqrt_price(lower_tick);
     let upper_sqrt_price = calculate_sqrt_price(upper_tick);
 
+    // price below the range, position consists of token x only
+    if current_sqrt_price <= lower_sqrt_price {
+        let result_by_x = ok_or_mark_trace!(get_liquidity_by_x_sqrt_price(
+            x,
+            lower_sqrt_price,
+            upper_sqrt_price,
+            lower_sqrt_price,
+            rounding_up,
+        ))?;
+        return Ok(LiquidityResult {
+            x,
+            y: TokenAmount::new(0),
+            l: result_by_x.l,
+        });
+    }
+
+    // price above the range, position consists of token y only
+    if current_sqrt_price >= upper_sqrt_price {
+        let sqrt_price_diff = upper_sqrt_price
+            .checked_sub(lower_sqrt_price)
+            .map_err(|_| err!("Underflow while calculating sqrt price difference"))?;
+        let liquidity = Liquidity::new(
+            (U192::from(y.get())
+                .checked_mul(U192::from(Price::from_integer(1).get()))
+                .ok_or_else(|| err!(TrackableError::MUL))?
+                .checked_mul(U192::from(Liquidity::from_integer(1).get()))
+                .ok_or_else(|| err!(TrackableError::MUL))?
+                .checked_div(U192::from(sqrt_price_diff.get()))
+                .ok_or_else(|| err!(TrackableError::DIV))?)
+            .try_into()
+            .map_err(|_| err!("Overflow while calculating liquidity"))?,
+        );
+        return Ok(LiquidityResult {
+            x: TokenAmount::new(0),
+            y,
+            l: liquidity,
+        });
+    }
+
     let result_by_y = ok_or_mark_trace!(get_liquidity_by_y_sqrt_price(
         y,
         lower_sqrt_price,
@@ -533,13 +589,17 @@ pub fn get_min_tick(tick_spacing: u16) -> i32 {
 
 pub fn liquidity_to_lp_token_amount(
     lp_token_supply: TokenAmount,
+    liquidity_per_lp_token: u64,
     current_liquidity: Liquidity,
     liquidity_delta: Liquidity,
     rounding_up: bool,
 ) -> TrackableResult<TokenAmount> {
     if current_liquidity.get() == 0 {
         return Ok(TokenAmount::new(
-            (liquidity_delta.get() / ONE_LP_TOKEN as u128)
+            liquidity_delta
+                .get()
+                .checked_div(liquidity_per_lp_token as u128)
+                .ok_or(err!(TrackableError::DIV))?
                 .try_into()
                 .map_err(|_| err!("Conversion to LpToken failed"))?,
         ));
@@ -581,12 +641,15 @@ pub fn liquidity_to_lp_token_amount(
 
 pub fn lp_token_amount_to_liquidity(
     lp_token_supply: TokenAmount,
+    liquidity_per_lp_token: u64,
     current_liquidity: Liquidity,
     lp_token_amount_delta: TokenAmount,
 ) -> TrackableResult<Liquidity> {
     if lp_token_supply.get() == 0 {
         return Ok(Liquidity::new(
-            (lp_token_amount_delta.get() * ONE_LP_TOKEN) as u128,
+            (lp_token_amount_delta.get() as u128)
+                .checked_mul(liquidity_per_lp_token as u128)
+                .ok_or(err!(TrackableError::MUL))?,
         ));
     }
 
@@ -636,25 +699,249 @@ pub fn compute_max_liquidity_position(
     ));
 }
 
+// largest liquidity that can be deposited into the range without exceeding the provided amounts
+pub fn compute_max_liquidity_for_amounts(
+    amount_x: TokenAmount,
+    amount_y: TokenAmount,
+    lower_tick: i32,
+    upper_tick: i32,
+    current_tick_index: i32,
+    current_sqrt_price: Price,
+) -> TrackableResult<Liquidity> {
+    let (mut usable_x, mut usable_y) = (amount_x, amount_y);
+    // deposits are rounded up, so the liquidity computed from the amounts might cost slightly more
+    for _ in 0..MAX_LIQUIDITY_FOR_AMOUNTS_ITERATIONS {
+        let (required_x, required_y, liquidity) =
+            ok_or_mark_trace!(compute_max_liquidity_position(
+                usable_x,
+                usable_y,
+                lower_tick,
+                upper_tick,
+                current_tick_index,
+                current_sqrt_price,
+            ))?;
+
+        if required_x <= amount_x && required_y <= amount_y {
+            if liquidity.is_zero() {
+                return Err(err!("Amounts too small to provide liquidity"));
+            }
+            return Ok(liquidity);
+        }
+
+        usable_x = usable_x
+            .checked_sub(required_x.checked_sub(amount_x).unwrap_or_default())
+            .unwrap_or_default();
+        usable_y = usable_y
+            .checked_sub(required_y.checked_sub(amount_y).unwrap_or_default())
+            .unwrap_or_default();
+    }
+
+    Err(err!("Failed to fit liquidity within the provided amounts"))
+}
+
+// estimates a swap that does not leave the current range of liquidity, returns amount out and the price after
+pub fn simulate_swap_in_range(
+    amount_in: TokenAmount,
+    x_to_y: bool,
+    liquidity: Liquidity,
+    current_sqrt_price: Price,
+    fee: FixedPoint,
+) -> TrackableResult<(TokenAmount, Price)> {
+    if liquidity.is_zero() {
+        return Err(err!("Pool has no active liquidity"));
+    }
+    let fee_amount = TokenAmount::from_decimal_up(amount_in.big_mul_up(fee));
+    let amount_after_fee = amount_in
+        .checked_sub(fee_amount)
+        .map_err(|_| err!(TrackableError::SUB))?;
+
+    if x_to_y {
+        // sqrt_price' = L * sqrt_price / (L + x * sqrt_price)
+        let denominator = U256::from(liquidity.get())
+            .checked_add(
+                U256::from(amount_after_fee.get())
+                    .checked_mul(U256::from(current_sqrt_price.get()))
+                    .ok_or_else(|| err!(TrackableError::MUL))?
+                    .checked_div(U256::from(Price::one::<u128>() / Liquidity::one::<u128>()))
+                    .ok_or_else(|| err!(TrackableError::DIV))?,
+            )
+            .ok_or_else(|| err!(TrackableError::ADD))?;
+        let next_sqrt_price = Price::new(
+            U256::from(liquidity.get())
+                .checked_mul(U256::from(current_sqrt_price.get()))
+                .ok_or_else(|| err!(TrackableError::MUL))?
+                .checked_add(
+                    denominator
+                        .checked_sub(U256::from(1))
+                        .ok_or_else(|| err!(TrackableError::SUB))?,
+                )
+                .ok_or_else(|| err!(TrackableError::ADD))?
+                .checked_div(denominator)
+                .ok_or_else(|| err!(TrackableError::DIV))?
+                .try_into()
+                .map_err(|_| err!(TrackableError::cast::<Price>().as_str()))?,
+        );
+        let amount_out = get_delta_y(next_sqrt_price, current_sqrt_price, liquidity, false)
+            .ok_or_else(|| err!("Failed to calculate amount out"))?;
+        Ok((amount_out, next_sqrt_price))
+    } else {
+        // sqrt_price' = sqrt_price + y / L
+        let sqrt_price_delta = Price::new(
+            U256::from(amount_after_fee.get())
+                .checked_mul(U256::from(Price::one::<u128>()))
+                .ok_or_else(|| err!(TrackableError::MUL))?
+                .checked_mul(U256::from(Liquidity::one::<u128>()))
+                .ok_or_else(|| err!(TrackableError::MUL))?
+                .checked_div(U256::from(liquidity.get()))
+                .ok_or_else(|| err!(TrackableError::DIV))?
+                .try_into()
+                .map_err(|_| err!(TrackableError::cast::<Price>().as_str()))?,
+        );
+        let next_sqrt_price = current_sqrt_price
+            .checked_add(sqrt_price_delta)
+            .map_err(|_| err!(TrackableError::ADD))?;
+        let amount_out = get_delta_x(current_sqrt_price, next_sqrt_price, liquidity, false)
+            .ok_or_else(|| err!("Failed to calculate amount out"))?;
+        Ok((amount_out, next_sqrt_price))
+    }
+}
+
+// part of a single token deposit that should be swapped so that both sides fit the position range
+pub fn compute_zap_in_swap_amount(
+    amount: TokenAmount,
+    x_to_y: bool,
+    pool_liquidity: Liquidity,
+    current_sqrt_price: Price,
+    fee: FixedPoint,
+    lower_tick: i32,
+    upper_tick: i32,
+) -> TrackableResult<TokenAmount> {
+    let lower_sqrt_price = calculate_sqrt_price(lower_tick);
+    let upper_sqrt_price = calculate_sqrt_price(upper_tick);
+
+    // out of range positions consist of a single token
+    if current_sqrt_price <= lower_sqrt_price {
+        return Ok(if x_to_y { TokenAmount::new(0) } else { amount });
+    }
+    if current_sqrt_price >= upper_sqrt_price {
+        return Ok(if x_to_y { amount } else { TokenAmount::new(0) });
+    }
+
+    let mut low = 0u64;
+    let mut high = amount.get();
+    while low < high {
+        let swap_amount = TokenAmount::new(low + (high - low) / 2);
+        let (amount_out, next_sqrt_price) = ok_or_mark_trace!(simulate_swap_in_range(
+            swap_amount,
+            x_to_y,
+            pool_liquidity,
+            current_sqrt_price,
+            fee,
+        ))?;
+        let remaining = amount - swap_amount;
+
+        let required = if x_to_y {
+            ok_or_mark_trace!(get_liquidity_by_x_sqrt_price(
+                remaining,
+                lower_sqrt_price,
+                upper_sqrt_price,
+                next_sqrt_price,
+                true,
+            ))?
+        } else {
+            ok_or_mark_trace!(get_liquidity_by_y_sqrt_price(
+                remaining,
+                lower_sqrt_price,
+                upper_sqrt_price,
+                next_sqrt_price,
+                true,
+            ))?
+        };
+
+        if amount_out < required.amount {
+            low = swap_amount.get() + 1;
+        } else {
+            high = swap_amount.get();
+        }
+    }
+
+    Ok(TokenAmount::new(low))
+}
+
+// fee charged by the protocol on deposited or withdrawn tokens
+pub fn compute_protocol_fee(amount: TokenAmount, fee: FixedPoint) -> TokenAmount {
+    TokenAmount::from_decimal_up(amount.big_mul_up(fee))
+}
+
+// splits fees harvested from the position into the part of share holders and the protocol
+pub fn compute_performance_fee(
+    harvested: TokenAmount,
+    performance_fee: FixedPoint,
+) -> TrackableResult<(TokenAmount, TokenAmount)> {
+    if performance_fee > FixedPoint::new(MAX_PERFORMANCE_FEE) {
+        return Err(err!("Performance fee exceeds the maximum"));
+    }
+    // rounded down, so holders never pay more than the configured rate
+    let protocol_fee = TokenAmount::from_decimal(harvested.big_mul(performance_fee));
+    let holders_fee = harvested
+        .checked_sub(protocol_fee)
+        .map_err(|_| err!(TrackableError::SUB))?;
+    Ok((holders_fee, protocol_fee))
+}
+
+// range of the given width centred around the current tick, aligned to the tick spacing
+pub fn compute_rebalance_range(
+    current_tick_index: i32,
+    tick_spacing: u16,
+    range_width: i32,
+) -> (i32, i32) {
+    let tick_spacing = tick_spacing as i32;
+    let min_tick = get_min_tick(tick_spacing as u16);
+    let max_tick = get_max_tick(tick_spacing as u16);
+
+    let lower_tick = (current_tick_index - range_width / 2).div_euclid(tick_spacing) * tick_spacing;
+    let lower_tick = lower_tick.max(min_tick).min(max_tick - range_width);
+    (lower_tick, lower_tick + range_width)
+}
+
+// price limit that keeps a swap within max_tick_deviation ticks from the reference tick
+pub fn compute_sqrt_price_limit(
+    current_tick_index: i32,
+    x_to_y: bool,
+    max_tick_deviation: i32,
+) -> Price {
+    let limit_tick = match x_to_y {
+        true => current_tick_index - max_tick_deviation,
+        false => current_tick_index + max_tick_deviation,
+    };
+    calculate_price_sqrt(limit_tick.clamp(-MAX_TICK, MAX_TICK))
+}
+
 pub fn compute_lp_share_change(
     provide_liquidity: bool,
-    lp_token_supply: TokenAmount,
+    share_price: SharePrice,
     liquidity_delta: Liquidity,
-    liquidity_before: Liquidity,
-    accumulated_x: TokenAmount, // unclaimed fee + reserve (leftovers)
-    accumulated_y: TokenAmount, // unclaimed fee + reserve (leftovers)
-    tick_spacing: u16,
+    accumulated_amounts: (TokenAmount, TokenAmount), // unclaimed fee + reserve (leftovers)
+    range: TickRange,
     current_tick_index: i32,
     current_sqrt_price: Price,
 ) -> TrackableResult<ComputeLpShareChangeResult> {
-    let max_tick = get_max_tick(tick_spacing);
-    let min_tick = get_min_tick(tick_spacing);
+    let SharePrice {
+        lp_token_supply,
+        liquidity: liquidity_before,
+        liquidity_per_lp_token,
+    } = share_price;
+    let TickRange {
+        lower_tick,
+        upper_tick,
+    } = range;
+    let (accumulated_x, accumulated_y) = accumulated_amounts;
     let (accumulated_usable_x, accumulated_usable_y, accumulated_liquidity) =
         compute_max_liquidity_position(
             accumulated_x,
             accumulated_y,
-            min_tick,
-            max_tick,
+            lower_tick,
+            upper_tick,
             current_tick_index,
             current_sqrt_price,
         )?;
@@ -700,13 +987,14 @@ pub fn compute_lp_share_change(
         liquidity_delta,
         provide_liquidity,
         current_tick_index,
-        min_tick,
-        max_tick,
+        lower_tick,
+        upper_tick,
     )
     .map_err(|_| err!("Failed to calculate transferred token amount"))?;
 
     let lp_token_change = liquidity_to_lp_token_amount(
         lp_token_supply,
+        liquidity_per_lp_token,
         liquidity_before + accumulated_liquidity,
         liquidity_delta,
         !provide_liquidity,
@@ -739,6 +1027,7 @@ mod test {
     fn test_liquidity_to_lp_token_amount() {
         let result = liquidity_to_lp_token_amount(
             TokenAmount::new(100),
+            ONE_LP_TOKEN,
             Liquidity::new(1000),
             Liquidity::new(10),
             false,
@@ -748,6 +1037,7 @@ mod test {
 
         let result = lp_token_amount_to_liquidity(
             TokenAmount::new(101),
+            ONE_LP_TOKEN,
             Liquidity::new(1010),
             TokenAmount::new(1),
         )
@@ -761,13 +1051,19 @@ mod test {
         let current_liquidity = Liquidity::new(2_u128.pow(84) + 1);
         let init_supply = TokenAmount::new(2_u64.pow(63) + 1);
 
-        let lp_tokens_minted =
-            liquidity_to_lp_token_amount(init_supply, current_liquidity, liquidity_delta, false)
-                .unwrap();
+        let lp_tokens_minted = liquidity_to_lp_token_amount(
+            init_supply,
+            ONE_LP_TOKEN,
+            current_liquidity,
+            liquidity_delta,
+            false,
+        )
+        .unwrap();
         assert_eq!(lp_tokens_minted, TokenAmount::new(4611686018427387904));
 
         let new_liquidity_delta = lp_token_amount_to_liquidity(
             init_supply + lp_tokens_minted,
+            ONE_LP_TOKEN,
             current_liquidity + liquidity_delta,
             lp_tokens_minted,
         )
@@ -781,6 +1077,7 @@ mod test {
 
         let new_lp_tokens_minted = liquidity_to_lp_token_amount(
             init_supply + lp_tokens_minted,
+            ONE_LP_TOKEN,
             current_liquidity + liquidity_delta,
             new_liquidity_delta,
             true,
@@ -806,12 +1103,17 @@ mod test {
 
             let val = compute_lp_share_change(
                 true,
-                TokenAmount(0),
+                SharePrice {
+                    lp_token_supply: TokenAmount(0),
+                    liquidity: Liquidity::new(0),
+                    liquidity_per_lp_token: ONE_LP_TOKEN,
+                },
                 delta_liquidity,
-                Liquidity::new(0),
-                TokenAmount::new(0),
-                TokenAmount::new(0),
-                1,
+                (TokenAmount::new(0), TokenAmount::new(0)),
+                TickRange {
+                    lower_tick: -get_max_tick(1),
+                    upper_tick: get_max_tick(1),
+                },
                 0,
                 Price::from_integer(1),
             )
@@ -847,6 +1149,7 @@ mod test {
 
             let token_supply = liquidity_to_lp_token_amount(
                 TokenAmount::new(0),
+                ONE_LP_TOKEN,
                 Liquidity::new(0),
                 current_liquidity.l,
                 false,
@@ -855,12 +1158,17 @@ mod test {
 
             let val = compute_lp_share_change(
                 true,
-                token_supply,
+                SharePrice {
+                    lp_token_supply: token_supply,
+                    liquidity: Liquidity::new(0),
+                    liquidity_per_lp_token: ONE_LP_TOKEN,
+                },
                 delta_liquidity,
-                Liquidity::new(0),
-                TokenAmount::new(1000),
-                TokenAmount::new(1000),
-                1,
+                (TokenAmount::new(1000), TokenAmount::new(1000)),
+                TickRange {
+                    lower_tick: -get_max_tick(1),
+                    upper_tick: get_max_tick(1),
+                },
                 0,
                 Price::from_integer(1),
             )
@@ -895,6 +1203,7 @@ mod test {
 
             let token_supply = liquidity_to_lp_token_amount(
                 TokenAmount::new(0),
+                ONE_LP_TOKEN,
                 Liquidity::new(0),
                 current_liquidity.l,
                 false,
@@ -903,12 +1212,17 @@ mod test {
 
             let val = compute_lp_share_change(
                 true,
-                token_supply,
+                SharePrice {
+                    lp_token_supply: token_supply,
+                    liquidity: Liquidity::new(0),
+                    liquidity_per_lp_token: ONE_LP_TOKEN,
+                },
                 delta_liquidity,
-                Liquidity::new(0),
-                TokenAmount::new(200000000),
-                TokenAmount::new(200000000),
-                1,
+                (TokenAmount::new(200000000), TokenAmount::new(200000000)),
+                TickRange {
+                    lower_tick: -get_max_tick(1),
+                    upper_tick: get_max_tick(1),
+                },
                 0,
                 Price::from_integer(1),
             )
@@ -933,12 +1247,17 @@ mod test {
             // withdraw below 0
             compute_lp_share_change(
                 false,
-                TokenAmount::new(1),
+                SharePrice {
+                    lp_token_supply: TokenAmount::new(1),
+                    liquidity: Liquidity::new(0),
+                    liquidity_per_lp_token: ONE_LP_TOKEN,
+                },
                 Liquidity::new(1),
-                Liquidity::new(0),
-                TokenAmount::new(0),
-                TokenAmount::new(0),
-                1,
+                (TokenAmount::new(0), TokenAmount::new(0)),
+                TickRange {
+                    lower_tick: -get_max_tick(1),
+                    upper_tick: get_max_tick(1),
+                },
                 0,
                 Price::from_integer(1),
             )
@@ -957,12 +1276,17 @@ mod test {
             // withdraw below liquidity amount
             compute_lp_share_change(
                 false,
-                TokenAmount::new(1),
+                SharePrice {
+                    lp_token_supply: TokenAmount::new(1),
+                    liquidity: Liquidity::new(0),
+                    liquidity_per_lp_token: ONE_LP_TOKEN,
+                },
                 current_liquidity.l - Liquidity::new(1),
-                Liquidity::new(0),
-                TokenAmount::new(1),
-                TokenAmount::new(1),
-                1,
+                (TokenAmount::new(1), TokenAmount::new(1)),
+                TickRange {
+                    lower_tick: -get_max_tick(1),
+                    upper_tick: get_max_tick(1),
+                },
                 0,
                 Price::from_integer(1),
             )
@@ -981,12 +1305,17 @@ mod test {
             // withdraw at the exact amount
             let result = compute_lp_share_change(
                 false,
-                TokenAmount::new(217),
+                SharePrice {
+                    lp_token_supply: TokenAmount::new(217),
+                    liquidity: Liquidity::new(0),
+                    liquidity_per_lp_token: ONE_LP_TOKEN,
+                },
                 current_liquidity.l,
-                Liquidity::new(0),
-                TokenAmount::new(1000),
-                TokenAmount::new(1000),
-                1,
+                (TokenAmount::new(1000), TokenAmount::new(1000)),
+                TickRange {
+                    lower_tick: -get_max_tick(1),
+                    upper_tick: get_max_tick(1),
+                },
                 0,
                 Price::from_integer(1),
             )
@@ -996,6 +1325,600 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_liquidity_per_lp_token_extreme_ranges() {
+        let max_tick = get_max_tick(1);
+        // lower tick, upper tick, current tick
+        let ranges = [
+            (-max_tick, max_tick, 0),
+            (-max_tick, max_tick, max_tick - 1),
+            (-max_tick, -max_tick + 1, -max_tick),
+            (max_tick - 1, max_tick, max_tick - 1),
+            (max_tick - 1, max_tick, 0),
+            (-max_tick, -max_tick + 1, 0),
+        ];
+        let liquidity_delta = Liquidity::new(2_u128.pow(60) + 12345);
+
+        for liquidity_per_lp_token in [1, 1_000, ONE_LP_TOKEN, 2_u64.pow(40)] {
+            for (lower_tick, upper_tick, current_tick) in ranges {
+                let current_sqrt_price = calculate_sqrt_price(current_tick);
+
+                // first deposit sets the share price
+                let first = compute_lp_share_change(
+                    true,
+                    SharePrice {
+                        lp_token_supply: TokenAmount::new(0),
+                        liquidity: Liquidity::new(0),
+                        liquidity_per_lp_token,
+                    },
+                    liquidity_delta,
+                    (TokenAmount::new(0), TokenAmount::new(0)),
+                    TickRange {
+                        lower_tick,
+                        upper_tick,
+                    },
+                    current_tick,
+                    current_sqrt_price,
+                )
+                .unwrap();
+                let supply = first.lp_token_change.unwrap();
+                assert_eq!(
+                    supply.0 as u128,
+                    liquidity_delta.v / liquidity_per_lp_token as u128
+                );
+
+                // rounding loss stays below the liquidity of a single LP token
+                let liquidity = lp_token_amount_to_liquidity(
+                    TokenAmount::new(0),
+                    liquidity_per_lp_token,
+                    Liquidity::new(0),
+                    supply,
+                )
+                .unwrap();
+                assert!(liquidity <= liquidity_delta);
+                assert!(liquidity_delta.v - liquidity.v < liquidity_per_lp_token as u128);
+
+                // same deposit afterwards mints the same amount
+                let second = compute_lp_share_change(
+                    true,
+                    SharePrice {
+                        lp_token_supply: supply,
+                        liquidity: liquidity_delta,
+                        liquidity_per_lp_token,
+                    },
+                    liquidity_delta,
+                    (TokenAmount::new(0), TokenAmount::new(0)),
+                    TickRange {
+                        lower_tick,
+                        upper_tick,
+                    },
+                    current_tick,
+                    current_sqrt_price,
+                )
+                .unwrap();
+                assert_eq!(second.lp_token_change.unwrap(), supply);
+                assert_eq!(second.transferred_amounts, first.transferred_amounts);
+
+                // withdrawing the whole supply returns the whole liquidity
+                let withdrawn = lp_token_amount_to_liquidity(
+                    supply + supply,
+                    liquidity_per_lp_token,
+                    liquidity_delta + liquidity_delta,
+                    supply + supply,
+                )
+                .unwrap();
+                assert_eq!(withdrawn, liquidity_delta + liquidity_delta);
+            }
+        }
+
+        // share price has to be positive
+        liquidity_to_lp_token_amount(
+            TokenAmount::new(0),
+            0,
+            Liquidity::new(0),
+            liquidity_delta,
+            false,
+        )
+        .unwrap_err();
+        // LP token amount that does not fit in u64
+        liquidity_to_lp_token_amount(
+            TokenAmount::new(0),
+            1,
+            Liquidity::new(0),
+            Liquidity::new(u64::MAX as u128 + 1),
+            false,
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_compute_max_liquidity_for_amounts() {
+        let cases = [
+            (1000, 1000, -get_max_tick(1), get_max_tick(1), 0),
+            (1000, 1, -get_max_tick(1), get_max_tick(1), 0),
+            (1, 1000, -get_max_tick(1), get_max_tick(1), 0),
+            (200000000, 200000000, -get_max_tick(10), get_max_tick(10), 0),
+            (
+                500_000_000,
+                3_000_000,
+                -get_max_tick(10),
+                get_max_tick(10),
+                12_000,
+            ),
+            (
+                3_000_000,
+                500_000_000,
+                -get_max_tick(100),
+                get_max_tick(100),
+                -30_000,
+            ),
+            (
+                u64::MAX >> 24,
+                u64::MAX >> 24,
+                -get_max_tick(100),
+                get_max_tick(100),
+                0,
+            ),
+            // concentrated
+            (1_000_000, 1_000_000, -1000, 1000, 0),
+            (1_000_000, 20_000, -100, 500, 250),
+            // out of range
+            (1_000_000, 0, 1000, 2000, 0),
+            (0, 1_000_000, -2000, -1000, 0),
+        ];
+        for (amount_x, amount_y, lower_tick, upper_tick, current_tick_index) in cases {
+            let amount_x = TokenAmount::new(amount_x);
+            let amount_y = TokenAmount::new(amount_y);
+            let current_sqrt_price = calculate_sqrt_price(current_tick_index);
+
+            let liquidity = compute_max_liquidity_for_amounts(
+                amount_x,
+                amount_y,
+                lower_tick,
+                upper_tick,
+                current_tick_index,
+                current_sqrt_price,
+            )
+            .unwrap();
+
+            let (required_x, required_y) = calculate_amount_delta(
+                current_sqrt_price,
+                liquidity,
+                true,
+                current_tick_index,
+                lower_tick,
+                upper_tick,
+            )
+            .unwrap();
+            assert!(required_x <= amount_x);
+            assert!(required_y <= amount_y);
+
+            // slightly more liquidity does not fit
+            let (exceeding_x, exceeding_y) = calculate_amount_delta(
+                current_sqrt_price,
+                liquidity + Liquidity::new(liquidity.get() / 1000 + 1),
+                true,
+                current_tick_index,
+                lower_tick,
+                upper_tick,
+            )
+            .unwrap();
+            assert!(exceeding_x > amount_x || exceeding_y > amount_y);
+        }
+        {
+            compute_max_liquidity_for_amounts(
+                TokenAmount::new(0),
+                TokenAmount::new(0),
+                -get_max_tick(1),
+                get_max_tick(1),
+                0,
+                Price::from_integer(1),
+            )
+            .unwrap_err();
+        }
+        // one-sided dust can't open a position around the price, rebalance reverts on it
+        {
+            compute_max_liquidity_for_amounts(
+                TokenAmount::new(1),
+                TokenAmount::new(0),
+                -100,
+                100,
+                0,
+                Price::from_integer(1),
+            )
+            .unwrap_err();
+        }
+    }
+
+    #[test]
+    fn test_simulate_swap_in_range() {
+        let liquidity = Liquidity::from_integer(1_000_000);
+        let fee = FixedPoint::from_scale(6, 4);
+        // x to y
+        {
+            let (amount_out, next_sqrt_price) = simulate_swap_in_range(
+                TokenAmount::new(1000),
+                true,
+                liquidity,
+                Price::from_integer(1),
+                fee,
+            )
+            .unwrap();
+            assert!(next_sqrt_price < Price::from_integer(1));
+            assert_eq!(amount_out, TokenAmount::new(998));
+        }
+        // y to x
+        {
+            let (amount_out, next_sqrt_price) = simulate_swap_in_range(
+                TokenAmount::new(1000),
+                false,
+                liquidity,
+                Price::from_integer(1),
+                fee,
+            )
+            .unwrap();
+            assert!(next_sqrt_price > Price::from_integer(1));
+            assert_eq!(amount_out, TokenAmount::new(998));
+        }
+        // no liquidity
+        {
+            simulate_swap_in_range(
+                TokenAmount::new(1000),
+                true,
+                Liquidity::new(0),
+                Price::from_integer(1),
+                fee,
+            )
+            .unwrap_err();
+        }
+    }
+
+    #[test]
+    fn test_compute_zap_in_swap_amount() {
+        let tick_spacing = 10;
+        let cases = [
+            (
+                1_000_000,
+                true,
+                Liquidity::from_integer(1_000_000_000u64),
+                0,
+            ),
+            (
+                1_000_000,
+                false,
+                Liquidity::from_integer(1_000_000_000u64),
+                0,
+            ),
+            (1_000_000, true, Liquidity::from_integer(10_000_000u64), 0),
+            (1_000_000, false, Liquidity::from_integer(10_000_000u64), 0),
+            (
+                5_000_000,
+                true,
+                Liquidity::from_integer(10_000_000u64),
+                20_000,
+            ),
+            (
+                5_000_000,
+                false,
+                Liquidity::from_integer(10_000_000u64),
+                -20_000,
+            ),
+        ];
+        for (amount, x_to_y, liquidity, current_tick_index) in cases {
+            let amount = TokenAmount::new(amount);
+            let fee = FixedPoint::from_scale(3, 3);
+            let current_sqrt_price = calculate_sqrt_price(current_tick_index);
+
+            let swap_amount = compute_zap_in_swap_amount(
+                amount,
+                x_to_y,
+                liquidity,
+                current_sqrt_price,
+                fee,
+                get_min_tick(tick_spacing),
+                get_max_tick(tick_spacing),
+            )
+            .unwrap();
+            assert!(swap_amount < amount);
+
+            // the leftover of the deposit is at most a few tokens
+            let (amount_out, next_sqrt_price) =
+                simulate_swap_in_range(swap_amount, x_to_y, liquidity, current_sqrt_price, fee)
+                    .unwrap();
+            let remaining = amount - swap_amount;
+            let (amount_x, amount_y) = match x_to_y {
+                true => (remaining, amount_out),
+                false => (amount_out, remaining),
+            };
+            let next_tick_index = match x_to_y {
+                true => current_tick_index - tick_spacing as i32,
+                false => current_tick_index,
+            };
+            let deposit = compute_max_liquidity_for_amounts(
+                amount_x,
+                amount_y,
+                get_min_tick(tick_spacing),
+                get_max_tick(tick_spacing),
+                next_tick_index,
+                next_sqrt_price,
+            )
+            .unwrap();
+            let (used_x, used_y) = calculate_amount_delta(
+                next_sqrt_price,
+                deposit,
+                true,
+                next_tick_index,
+                get_min_tick(tick_spacing),
+                get_max_tick(tick_spacing),
+            )
+            .unwrap();
+            let (unused_in, unused_out) = match x_to_y {
+                true => (amount_x - used_x, amount_y - used_y),
+                false => (amount_y - used_y, amount_x - used_x),
+            };
+            assert!(unused_in.get() * 1000 <= amount.get(), "{:?}", unused_in);
+            assert!(unused_out.get() * 1000 <= amount.get(), "{:?}", unused_out);
+        }
+        // out of range positions take a single token
+        {
+            let amount = TokenAmount::new(1_000_000);
+            let liquidity = Liquidity::from_integer(1_000_000u64);
+            let fee = FixedPoint::from_scale(3, 3);
+            let current_sqrt_price = calculate_sqrt_price(0);
+
+            // above the current price, only x is deposited
+            let swap_x = compute_zap_in_swap_amount(
+                amount,
+                true,
+                liquidity,
+                current_sqrt_price,
+                fee,
+                100,
+                200,
+            )
+            .unwrap();
+            let swap_y = compute_zap_in_swap_amount(
+                amount,
+                false,
+                liquidity,
+                current_sqrt_price,
+                fee,
+                100,
+                200,
+            )
+            .unwrap();
+            assert_eq!(swap_x, TokenAmount::new(0));
+            assert_eq!(swap_y, amount);
+
+            // below the current price, only y is deposited
+            let swap_x = compute_zap_in_swap_amount(
+                amount,
+                true,
+                liquidity,
+                current_sqrt_price,
+                fee,
+                -200,
+                -100,
+            )
+            .unwrap();
+            let swap_y = compute_zap_in_swap_amount(
+                amount,
+                false,
+                liquidity,
+                current_sqrt_price,
+                fee,
+                -200,
+                -100,
+            )
+            .unwrap();
+            assert_eq!(swap_x, amount);
+            assert_eq!(swap_y, TokenAmount::new(0));
+        }
+    }
+
+    #[test]
+    fn test_compute_protocol_fee() {
+        let fee = FixedPoint::from_scale(3, 3);
+        assert_eq!(
+            compute_protocol_fee(TokenAmount::new(1_000_000), fee),
+            TokenAmount::new(3000)
+        );
+        // rounded up
+        assert_eq!(
+            compute_protocol_fee(TokenAmount::new(1001), fee),
+            TokenAmount::new(4)
+        );
+        assert_eq!(
+            compute_protocol_fee(TokenAmount::new(1), fee),
+            TokenAmount::new(1)
+        );
+        assert_eq!(
+            compute_protocol_fee(TokenAmount::new(0), fee),
+            TokenAmount::new(0)
+        );
+        assert_eq!(
+            compute_protocol_fee(TokenAmount::new(u64::MAX), FixedPoint::new(0)),
+            TokenAmount::new(0)
+        );
+    }
+
+    #[test]
+    fn test_compute_performance_fee() {
+        // basic
+        {
+            let (holders_fee, protocol_fee) =
+                compute_performance_fee(TokenAmount::new(1_000_000), FixedPoint::from_scale(1, 1))
+                    .unwrap();
+            assert_eq!(holders_fee, TokenAmount::new(900_000));
+            assert_eq!(protocol_fee, TokenAmount::new(100_000));
+        }
+        // disabled
+        {
+            let (holders_fee, protocol_fee) =
+                compute_performance_fee(TokenAmount::new(1_000_000), FixedPoint::new(0)).unwrap();
+            assert_eq!(holders_fee, TokenAmount::new(1_000_000));
+            assert_eq!(protocol_fee, TokenAmount::new(0));
+        }
+        // above the cap
+        {
+            compute_performance_fee(
+                TokenAmount::new(1_000_000),
+                FixedPoint::new(MAX_PERFORMANCE_FEE + 1),
+            )
+            .unwrap_err();
+        }
+        // holders are never diluted beyond the configured rate
+        {
+            let fees = [
+                FixedPoint::new(1),
+                FixedPoint::from_scale(1, 4),
+                FixedPoint::from_scale(3, 3),
+                FixedPoint::from_scale(1, 1),
+                FixedPoint::from_scale(257, 3),
+                FixedPoint::new(MAX_PERFORMANCE_FEE),
+            ];
+            let amounts = [0, 1, 2, 3, 7, 999, 1000, 1001, 123_456_789, u64::MAX];
+            for performance_fee in fees {
+                for amount in amounts {
+                    let harvested = TokenAmount::new(amount);
+                    let (holders_fee, protocol_fee) =
+                        compute_performance_fee(harvested, performance_fee).unwrap();
+                    assert_eq!(holders_fee + protocol_fee, harvested);
+                    assert!(protocol_fee <= harvested.big_mul(performance_fee));
+                    assert!(
+                        holders_fee
+                            >= harvested.big_mul(FixedPoint::from_integer(1) - performance_fee)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_performance_fee_dilution() {
+        let (lower_tick, upper_tick) = (-1000, 1000);
+        let sqrt_price = Price::from_integer(1);
+        let liquidity_before = get_max_liquidity(
+            TokenAmount::new(1_000_000_000),
+            TokenAmount::new(1_000_000_000),
+            lower_tick,
+            upper_tick,
+            sqrt_price,
+            true,
+        )
+        .unwrap()
+        .l;
+        let supply = liquidity_to_lp_token_amount(
+            TokenAmount::new(0),
+            ONE_LP_TOKEN,
+            Liquidity::new(0),
+            liquidity_before,
+            false,
+        )
+        .unwrap();
+        let harvested = TokenAmount::new(1_000_000);
+
+        let fees = [
+            FixedPoint::new(0),
+            FixedPoint::from_scale(1, 1),
+            FixedPoint::from_scale(257, 3),
+            FixedPoint::new(MAX_PERFORMANCE_FEE),
+        ];
+        for performance_fee in fees {
+            let (holders_fee, protocol_fee) =
+                compute_performance_fee(harvested, performance_fee).unwrap();
+            assert!(protocol_fee <= harvested.big_mul(performance_fee));
+            let (_, _, holders_liquidity) = compute_max_liquidity_position(
+                holders_fee,
+                holders_fee,
+                lower_tick,
+                upper_tick,
+                0,
+                sqrt_price,
+            )
+            .unwrap();
+
+            // a deposit right after the harvest doesn't capture the holders' part of the fee
+            let liquidity_delta = liquidity_before;
+            let shares = compute_lp_share_change(
+                true,
+                SharePrice {
+                    lp_token_supply: supply,
+                    liquidity: liquidity_before,
+                    liquidity_per_lp_token: ONE_LP_TOKEN,
+                },
+                liquidity_delta,
+                (holders_fee, holders_fee),
+                TickRange {
+                    lower_tick,
+                    upper_tick,
+                },
+                0,
+                sqrt_price,
+            )
+            .unwrap();
+            let minted = shares.lp_token_change.unwrap();
+            let liquidity_after = liquidity_before + shares.liquidity_change.l;
+            let supply_after = supply + minted;
+            let share_value = |amount: TokenAmount| {
+                Liquidity::new(
+                    liquidity_after.v * amount.get() as u128 / supply_after.get() as u128,
+                )
+            };
+
+            assert!(share_value(minted) <= liquidity_delta);
+            assert!(share_value(supply) >= liquidity_before + holders_liquidity);
+        }
+    }
+
+    #[test]
+    fn test_compute_rebalance_range() {
+        // centred
+        assert_eq!(compute_rebalance_range(0, 10, 200), (-100, 100));
+        assert_eq!(compute_rebalance_range(1234, 10, 200), (1130, 1330));
+        assert_eq!(compute_rebalance_range(-1234, 10, 200), (-1340, -1140));
+        // aligned to tick spacing
+        for current_tick_index in [-1001, -999, -1, 0, 1, 999, 1001] {
+            let (lower_tick, upper_tick) = compute_rebalance_range(current_tick_index, 100, 1000);
+            assert_eq!(lower_tick % 100, 0);
+            assert_eq!(upper_tick - lower_tick, 1000);
+            assert!(lower_tick <= current_tick_index && current_tick_index < upper_tick);
+        }
+        // clamped to the price range
+        {
+            let (lower_tick, upper_tick) = compute_rebalance_range(get_max_tick(10), 10, 200);
+            assert_eq!(
+                (lower_tick, upper_tick),
+                (get_max_tick(10) - 200, get_max_tick(10))
+            );
+            let (lower_tick, upper_tick) = compute_rebalance_range(get_min_tick(10), 10, 200);
+            assert_eq!(
+                (lower_tick, upper_tick),
+                (get_min_tick(10), get_min_tick(10) + 200)
+            );
+        }
+    }
+
+    #[test]
+    fn test_compute_sqrt_price_limit() {
+        assert_eq!(
+            compute_sqrt_price_limit(100, true, 50),
+            calculate_price_sqrt(50)
+        );
+        assert_eq!(
+            compute_sqrt_price_limit(100, false, 50),
+            calculate_price_sqrt(150)
+        );
+        assert_eq!(
+            compute_sqrt_price_limit(-MAX_TICK + 10, true, 50),
+            calculate_price_sqrt(-MAX_TICK)
+        );
+        assert_eq!(
+            compute_sqrt_price_limit(MAX_TICK - 10, false, 50),
+            calculate_price_sqrt(MAX_TICK)
+        );
+    }
+
     #[test]
     fn get_max_liquidity_full_range_limit_tick_spacing_100() {
         let max_liquidity = get_max_liquidity(
@@ -0,0 +1,164 @@
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as Token2022Mint;
+use spl_token_metadata_interface::state::TokenMetadata;
+
+pub const LP_TOKEN_NAME_PREFIX: &str = "INV-LP";
+// Metaplex token metadata program, describes mints of the legacy token program
+pub const METAPLEX_METADATA_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+const METAPLEX_METADATA_V1_KEY: u8 = 4;
+const FEE_PERCENT_SCALE: u32 = 10; // FixedPoint scale (12) minus two digits of percent
+const SHORT_ADDRESS_LEN: usize = 4;
+
+pub fn get_metaplex_metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            METAPLEX_METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &METAPLEX_METADATA_PROGRAM_ID,
+    )
+    .0
+}
+
+// key, update authority and mint are followed by the borsh encoded name and symbol
+fn parse_metaplex_symbol(data: &[u8], mint: &Pubkey) -> Option<String> {
+    if *data.first()? != METAPLEX_METADATA_V1_KEY || data.get(33..65)? != mint.as_ref() {
+        return None;
+    }
+    let mut data = data.get(65..)?;
+    let _name = String::deserialize(&mut data).ok()?;
+    let symbol = String::deserialize(&mut data).ok()?;
+    // Metaplex pads the symbol with null bytes
+    Some(symbol.trim_end_matches('\0').to_string())
+}
+
+// symbol from the token metadata extension or the Metaplex metadata account,
+// shortened address for mints without one
+pub fn get_token_symbol(mint: &AccountInfo, metadata: Option<&AccountInfo>) -> Result<String> {
+    if *mint.owner == token_2022::ID {
+        let data = mint.try_borrow_data()?;
+        let state = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+        if let Ok(metadata) = state.get_variable_len_extension::<TokenMetadata>() {
+            if !metadata.symbol.is_empty() {
+                return Ok(metadata.symbol);
+            }
+        }
+    }
+    if let Some(metadata) = metadata {
+        require_keys_eq!(
+            *metadata.key,
+            get_metaplex_metadata_address(mint.key),
+            ErrorCode::InvalidMetadata
+        );
+        require_keys_eq!(
+            *metadata.owner,
+            METAPLEX_METADATA_PROGRAM_ID,
+            ErrorCode::InvalidMetadata
+        );
+        if let Some(symbol) = parse_metaplex_symbol(&metadata.try_borrow_data()?, mint.key) {
+            if !symbol.is_empty() {
+                return Ok(symbol);
+            }
+        }
+    }
+    Ok(mint.key.to_string()[..SHORT_ADDRESS_LEN].to_string())
+}
+
+// 500000000 (0.0005) -> "0.05%"
+pub fn format_fee_percent(fee: u128) -> String {
+    let denominator = 10u128.pow(FEE_PERCENT_SCALE);
+    let fraction = format!(
+        "{:0width$}",
+        fee % denominator,
+        width = FEE_PERCENT_SCALE as usize
+    );
+    let fraction = fraction.trim_end_matches('0');
+    match fraction.is_empty() {
+        true => format!("{}%", fee / denominator),
+        false => format!("{}.{}%", fee / denominator, fraction),
+    }
+}
+
+// "INV-LP SOL/USDC 0.05%"
+pub fn lp_token_name(symbol_x: &str, symbol_y: &str, fee: u128) -> String {
+    format!(
+        "{} {}/{} {}",
+        LP_TOKEN_NAME_PREFIX,
+        symbol_x,
+        symbol_y,
+        format_fee_percent(fee)
+    )
+}
+
+// "SOL-USDC-LP"
+pub fn lp_token_symbol(symbol_x: &str, symbol_y: &str) -> String {
+    format!("{}-{}-LP", symbol_x, symbol_y)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_fee_percent() {
+        assert_eq!(format_fee_percent(0), "0%");
+        assert_eq!(format_fee_percent(100_000_000), "0.01%");
+        assert_eq!(format_fee_percent(500_000_000), "0.05%");
+        assert_eq!(format_fee_percent(3_000_000_000), "0.3%");
+        assert_eq!(format_fee_percent(10_000_000_000), "1%");
+        assert_eq!(format_fee_percent(12_500_000_000), "1.25%");
+        assert_eq!(format_fee_percent(1), "0.0000000001%");
+    }
+
+    #[test]
+    fn test_parse_metaplex_symbol() {
+        let mint = Pubkey::new_unique();
+        let metadata = |key: u8, mint: &Pubkey, symbol: &str| {
+            let mut data = vec![key];
+            data.extend_from_slice(Pubkey::new_unique().as_ref());
+            data.extend_from_slice(mint.as_ref());
+            data.extend_from_slice(&"Wrapped SOL\0\0\0".to_string().try_to_vec().unwrap());
+            data.extend_from_slice(&symbol.to_string().try_to_vec().unwrap());
+            data.extend_from_slice(&"https://".to_string().try_to_vec().unwrap());
+            data
+        };
+
+        assert_eq!(
+            parse_metaplex_symbol(&metadata(4, &mint, "SOL\0\0\0\0\0\0\0"), &mint),
+            Some("SOL".to_string())
+        );
+        assert_eq!(
+            parse_metaplex_symbol(&metadata(4, &mint, ""), &mint),
+            Some("".to_string())
+        );
+        // other account kinds and mints are ignored
+        assert_eq!(
+            parse_metaplex_symbol(&metadata(6, &mint, "SOL"), &mint),
+            None
+        );
+        assert_eq!(
+            parse_metaplex_symbol(&metadata(4, &Pubkey::new_unique(), "SOL"), &mint),
+            None
+        );
+        assert_eq!(
+            parse_metaplex_symbol(&metadata(4, &mint, "SOL")[..70], &mint),
+            None
+        );
+    }
+
+    #[test]
+    fn test_lp_token_name() {
+        assert_eq!(
+            lp_token_name("SOL", "USDC", 500_000_000),
+            "INV-LP SOL/USDC 0.05%"
+        );
+        assert_eq!(lp_token_symbol("SOL", "USDC"), "SOL-USDC-LP");
+    }
+}
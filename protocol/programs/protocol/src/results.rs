@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+// published with `set_return_data`, read back through `cpi::Return::get`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MintLpTokenResult {
+    pub amount_x: u64, // mint fee included
+    pub amount_y: u64,
+    pub lp_token_amount: u64,
+    pub liquidity: u128,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BurnLpTokenResult {
+    pub amount_x: u64, // burn fee excluded
+    pub amount_y: u64,
+    pub lp_token_amount: u64,
+    pub liquidity: u128,
+}
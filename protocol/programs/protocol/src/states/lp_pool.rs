@@ -1,4 +1,9 @@
-use crate::{decimals::FixedPoint, size};
+use crate::{
+    decimals::{FixedPoint, Liquidity},
+    math::{get_max_tick, get_min_tick},
+    size,
+};
+use std::mem::offset_of;
 
 use super::DerivedAccountIdentifier;
 use anchor_lang::prelude::*;
@@ -6,8 +11,41 @@ use anchor_lang::prelude::*;
 impl DerivedAccountIdentifier for LpPool {
     const IDENT: &'static [u8] = b"poolv1";
 }
+
+impl LpPool {
+    pub const VERSION: u8 = 1;
+    // accounts created before versioning are as long as the fields before the version
+    pub const LEGACY_LEN: usize = 8 + offset_of!(LpPool, version);
+
+    pub fn is_managed(&self) -> bool {
+        self.keeper != Pubkey::default()
+    }
+
+    // full range pools keep the address they had before custom ranges were supported
+    pub fn range_seed(lower_tick_index: i32, upper_tick_index: i32, tick_spacing: u16) -> Vec<u8> {
+        if lower_tick_index == get_min_tick(tick_spacing)
+            && upper_tick_index == get_max_tick(tick_spacing)
+        {
+            return vec![];
+        }
+        [
+            lower_tick_index.to_le_bytes(),
+            upper_tick_index.to_le_bytes(),
+        ]
+        .concat()
+    }
+
+    pub fn initial_range_seed(&self) -> Vec<u8> {
+        Self::range_seed(
+            self.initial_lower_tick_index,
+            self.initial_upper_tick_index,
+            self.tick_spacing,
+        )
+    }
+}
+// fields are laid out in declaration order, so appending them keeps the offsets of the old ones
 #[account(zero_copy(unsafe))]
-#[repr(packed)]
+#[repr(C, packed)]
 #[derive(PartialEq, Default, Debug, InitSpace)]
 pub struct LpPool {
     pub position_index: u32,
@@ -20,6 +58,88 @@ pub struct LpPool {
     pub fee: FixedPoint,
     pub token_bump: u8,
     pub bump: u8,
+    // fields below were added in version 1
+    pub version: u8,
+    // range the account address is derived from, the position range might move when managed
+    pub initial_lower_tick_index: i32,
+    pub initial_upper_tick_index: i32,
+    // managed range, disabled when keeper is the default pubkey
+    pub keeper: Pubkey,
+    pub range_width: i32,
+    pub rebalance_cooldown: i64,
+    pub last_rebalance_timestamp: i64,
+    pub max_tick_deviation: i32,
+    pub mint_fee: FixedPoint,
+    pub burn_fee: FixedPoint,
+    pub fee_protocol_token_x: u64,
+    pub fee_protocol_token_y: u64,
+    pub performance_fee: FixedPoint,
+    pub paused: bool,
+    // maximal liquidity of the position, zero when uncapped
+    pub liquidity_cap: Liquidity,
+    pub lp_token_decimals: u8,
+    // liquidity backing a single LP token while the supply is zero
+    pub liquidity_per_lp_token: u64,
+    // current range of the position, equal to the initial one unless managed
+    pub lower_tick_index: i32,
+    pub upper_tick_index: i32,
+    // seconds the tick the managed range is centred around is averaged over
+    pub twap_window: u32,
 }
 
 size!(LpPool);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_legacy_len() {
+        // position index, position exists, leftovers, tokens, tick spacing, fee and bumps
+        assert_eq!(
+            LpPool::LEGACY_LEN,
+            8 + 4 + 1 + 8 + 8 + 32 + 32 + 2 + 16 + 1 + 1
+        );
+        assert_eq!(
+            LpPool::LEGACY_LEN,
+            8 + std::mem::size_of::<crate::contexts::LegacyLpPool>()
+        );
+    }
+
+    #[test]
+    fn test_range_seed() {
+        let token_x = Pubkey::new_unique();
+        let token_y = Pubkey::new_unique();
+        let tick_spacing: u16 = 10;
+        let tick_spacing_seed = tick_spacing.to_le_bytes();
+        let address = |range_seed: &[&[u8]]| {
+            let seeds = [
+                &[
+                    LpPool::IDENT,
+                    token_x.as_ref(),
+                    token_y.as_ref(),
+                    &tick_spacing_seed,
+                ],
+                range_seed,
+            ]
+            .concat();
+            Pubkey::find_program_address(&seeds, &crate::ID).0
+        };
+
+        // full range keeps the legacy address
+        let seed = LpPool::range_seed(
+            get_min_tick(tick_spacing),
+            get_max_tick(tick_spacing),
+            tick_spacing,
+        );
+        assert!(seed.is_empty());
+        assert_eq!(address(&[&seed]), address(&[]));
+
+        // custom range is the same as both ticks passed separately
+        let seed = LpPool::range_seed(-100, 200, tick_spacing);
+        assert_eq!(
+            address(&[&seed]),
+            address(&[&(-100i32).to_le_bytes(), &200i32.to_le_bytes()])
+        );
+    }
+}
@@ -6,8 +6,14 @@ use anchor_lang::prelude::*;
 impl DerivedAccountIdentifier for State {
     const IDENT: &'static [u8] = b"PROTOCOLState";
 }
+
+impl State {
+    // states created before pausing and the admin transfer
+    pub const LEGACY_LEN: usize = 8 + std::mem::size_of::<crate::contexts::LegacyState>();
+}
+
 #[account(zero_copy(unsafe))]
-#[repr(packed)]
+#[repr(C, packed)]
 #[derive(PartialEq, Default, Debug, InitSpace)]
 pub struct State {
     pub admin: Pubkey,
@@ -15,6 +21,38 @@ pub struct State {
     pub counter: u8,
     pub bump: u8,
     pub bump_authority: u8,
+    pub paused: bool,
+    pub pending_admin: Pubkey,
 }
 
 size!(State);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::contexts::LegacyState;
+    use std::mem::{offset_of, size_of};
+
+    #[test]
+    fn test_legacy_len() {
+        // admin, program authority, counter and bumps
+        assert_eq!(State::LEGACY_LEN, 8 + 32 + 32 + 1 + 1 + 1);
+        assert_eq!(State::LEN, State::LEGACY_LEN + 1 + 32);
+    }
+
+    #[test]
+    fn test_legacy_layout() {
+        assert_eq!(size_of::<LegacyState>(), offset_of!(State, paused));
+        assert_eq!(offset_of!(LegacyState, admin), offset_of!(State, admin));
+        assert_eq!(
+            offset_of!(LegacyState, program_authority),
+            offset_of!(State, program_authority)
+        );
+        assert_eq!(offset_of!(LegacyState, counter), offset_of!(State, counter));
+        assert_eq!(offset_of!(LegacyState, bump), offset_of!(State, bump));
+        assert_eq!(
+            offset_of!(LegacyState, bump_authority),
+            offset_of!(State, bump_authority)
+        );
+    }
+}
@@ -2,6 +2,9 @@ export const PROTOCOL_STATE_SEED = "PROTOCOLState";
 export const PROTOCOL_AUTHORITY_SEED = "PROTOCOLAuthority";
 export const LP_POOL_SEED = "poolv1";
 export const LP_TOKEN_SEED = "lp_tokenv1";
+export const U64_MAX = "18446744073709551615";
+export const MIN_SQRT_PRICE = "15258932000000000000";
+export const MAX_SQRT_PRICE = "65535383934512647000000000000";
 
 const LOG2_MAX_FULL_RANGE_LIQUIDITY = 85;
 const LOG2_MAX_TOKEN_ACCURACY = 64;
@@ -81,6 +81,18 @@ export type Protocol = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "tokenXMetadata",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenYMetadata",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
@@ -97,6 +109,65 @@ export type Protocol = {
           "isSigner": false
         }
       ],
+      "args": [
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "lpTokenDecimals",
+          "type": "u8"
+        },
+        {
+          "name": "liquidityPerLpToken",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "migrateLpPool",
+      "accounts": [
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateState",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
       "args": []
     },
     {
@@ -180,6 +251,12 @@ export type Protocol = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
           "name": "positionList",
           "isMut": true,
@@ -255,11 +332,22 @@ export type Protocol = {
         {
           "name": "liquidity",
           "type": "u128"
+        },
+        {
+          "name": "maxAmountX",
+          "type": "u64"
+        },
+        {
+          "name": "maxAmountY",
+          "type": "u64"
         }
-      ]
+      ],
+      "returns": {
+        "defined": "MintLpTokenResult"
+      }
     },
     {
-      "name": "burnLpToken",
+      "name": "mintLpTokenByAmounts",
       "accounts": [
         {
           "name": "owner",
@@ -282,7 +370,174 @@ export type Protocol = {
           "isSigner": false
         },
         {
-          "name": "lastPositionLpPool",
+          "name": "tokenLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "INVARIANT"
+          ]
+        },
+        {
+          "name": "invState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgramAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenYProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amountX",
+          "type": "u64"
+        },
+        {
+          "name": "amountY",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "MintLpTokenResult"
+      }
+    },
+    {
+      "name": "zapIn",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
           "isMut": true,
           "isSigner": false
         },
@@ -340,14 +595,15 @@ export type Protocol = {
           "isSigner": false
         },
         {
-          "name": "lastPosition",
+          "name": "pool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "pool",
+          "name": "oracle",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
         },
         {
           "name": "positionList",
@@ -422,203 +678,2699 @@ export type Protocol = {
       ],
       "args": [
         {
-          "name": "liquidity",
+          "name": "xToY",
+          "type": "bool"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "sqrtPriceLimit",
           "type": "u128"
+        },
+        {
+          "name": "minLpTokenOut",
+          "type": "u64"
         }
-      ]
-    }
-  ],
-  "accounts": [
+      ],
+      "returns": {
+        "defined": "MintLpTokenResult"
+      }
+    },
     {
-      "name": "lpPool",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "positionIndex",
-            "type": "u32"
+      "name": "burnLpToken",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lastPositionLpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "INVARIANT"
+          ]
+        },
+        {
+          "name": "invState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgramAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lastPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenYProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "liquidity",
+          "type": "u128"
+        },
+        {
+          "name": "minAmountX",
+          "type": "u64"
+        },
+        {
+          "name": "minAmountY",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "BurnLpTokenResult"
+      }
+    },
+    {
+      "name": "compound",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "INVARIANT"
+          ]
+        },
+        {
+          "name": "invState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgramAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenYProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "zapOut",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lastPositionLpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "INVARIANT"
+          ]
+        },
+        {
+          "name": "invState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgramAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lastPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenYProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "liquidity",
+          "type": "u128"
+        },
+        {
+          "name": "xToY",
+          "type": "bool"
+        },
+        {
+          "name": "sqrtPriceLimit",
+          "type": "u128"
+        },
+        {
+          "name": "minAmountOut",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "BurnLpTokenResult"
+      }
+    },
+    {
+      "name": "setRebalanceConfig",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "keeper",
+          "type": "publicKey"
+        },
+        {
+          "name": "rangeWidth",
+          "type": "i32"
+        },
+        {
+          "name": "rebalanceCooldown",
+          "type": "i64"
+        },
+        {
+          "name": "maxTickDeviation",
+          "type": "i32"
+        },
+        {
+          "name": "twapWindow",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "rebalance",
+      "accounts": [
+        {
+          "name": "keeper",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lastPositionLpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "INVARIANT"
+          ]
+        },
+        {
+          "name": "invState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgramAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lastPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newLowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newUpperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenYProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setLpPoolFees",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "mintFee",
+          "type": "u128"
+        },
+        {
+          "name": "burnFee",
+          "type": "u128"
+        },
+        {
+          "name": "performanceFee",
+          "type": "u128"
+        }
+      ]
+    },
+    {
+      "name": "withdrawLpProtocolFees",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenXProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenYProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setPaused",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "paused",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setLpPoolLimits",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "paused",
+          "type": "bool"
+        },
+        {
+          "name": "liquidityCap",
+          "type": "u128"
+        }
+      ]
+    },
+    {
+      "name": "setLpTokenUri",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "uri",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "proposeAdmin",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newAdmin",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "acceptAdmin",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newAdmin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "lpPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "positionIndex",
+            "type": "u32"
+          },
+          {
+            "name": "positionExists",
+            "type": "bool"
+          },
+          {
+            "name": "leftoverX",
+            "type": "u64"
+          },
+          {
+            "name": "leftoverY",
+            "type": "u64"
+          },
+          {
+            "name": "tokenX",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenY",
+            "type": "publicKey"
+          },
+          {
+            "name": "tickSpacing",
+            "type": "u16"
+          },
+          {
+            "name": "fee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "tokenBump",
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
           },
           {
-            "name": "positionExists",
-            "type": "bool"
+            "name": "version",
+            "type": "u8"
           },
           {
-            "name": "leftoverX",
-            "type": "u64"
+            "name": "initialLowerTickIndex",
+            "type": "i32"
           },
           {
-            "name": "leftoverY",
-            "type": "u64"
+            "name": "initialUpperTickIndex",
+            "type": "i32"
           },
           {
-            "name": "tokenX",
+            "name": "keeper",
             "type": "publicKey"
           },
           {
-            "name": "tokenY",
-            "type": "publicKey"
+            "name": "rangeWidth",
+            "type": "i32"
           },
           {
-            "name": "tickSpacing",
-            "type": "u16"
+            "name": "rebalanceCooldown",
+            "type": "i64"
           },
           {
-            "name": "fee",
+            "name": "lastRebalanceTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "maxTickDeviation",
+            "type": "i32"
+          },
+          {
+            "name": "mintFee",
             "type": {
               "defined": "FixedPoint"
             }
           },
           {
-            "name": "tokenBump",
+            "name": "burnFee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "feeProtocolTokenX",
+            "type": "u64"
+          },
+          {
+            "name": "feeProtocolTokenY",
+            "type": "u64"
+          },
+          {
+            "name": "performanceFee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "paused",
+            "type": "bool"
+          },
+          {
+            "name": "liquidityCap",
+            "type": {
+              "defined": "Liquidity"
+            }
+          },
+          {
+            "name": "lpTokenDecimals",
+            "type": "u8"
+          },
+          {
+            "name": "liquidityPerLpToken",
+            "type": "u64"
+          },
+          {
+            "name": "lowerTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "upperTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "twapWindow",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "state",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "programAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "counter",
             "type": "u8"
           },
           {
             "name": "bump",
             "type": "u8"
+          },
+          {
+            "name": "bumpAuthority",
+            "type": "u8"
+          },
+          {
+            "name": "paused",
+            "type": "bool"
+          },
+          {
+            "name": "pendingAdmin",
+            "type": "publicKey"
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "MintLpTokenResult",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amountX",
+            "type": "u64"
+          },
+          {
+            "name": "amountY",
+            "type": "u64"
+          },
+          {
+            "name": "lpTokenAmount",
+            "type": "u64"
+          },
+          {
+            "name": "liquidity",
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "BurnLpTokenResult",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amountX",
+            "type": "u64"
+          },
+          {
+            "name": "amountY",
+            "type": "u64"
+          },
+          {
+            "name": "lpTokenAmount",
+            "type": "u64"
+          },
+          {
+            "name": "liquidity",
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Price",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "v",
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Liquidity",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "v",
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FeeGrowth",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "v",
+            "type": "u128"
           }
         ]
       }
     },
     {
-      "name": "state",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "admin",
-            "type": "publicKey"
-          },
-          {
-            "name": "programAuthority",
-            "type": "publicKey"
-          },
-          {
-            "name": "counter",
-            "type": "u8"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "bumpAuthority",
-            "type": "u8"
-          }
-        ]
-      }
+      "name": "FixedPoint",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "v",
+            "type": "u128"
+          }
+        ]
+      }
+    }
+  ],
+  "events": [
+    {
+      "name": "MintLpTokenEvent",
+      "fields": [
+        {
+          "name": "lpPool",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "liquidity",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "lpTokenAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amountX",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amountY",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mintFeeX",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mintFeeY",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ZapInEvent",
+      "fields": [
+        {
+          "name": "lpPool",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "xToY",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "swapAmountIn",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "swapAmountOut",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "lpTokenAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "liquidity",
+          "type": "u128",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "BurnLpTokenEvent",
+      "fields": [
+        {
+          "name": "lpPool",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "liquidity",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "lpTokenAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amountX",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amountY",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "burnFeeX",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "burnFeeY",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ZapOutEvent",
+      "fields": [
+        {
+          "name": "lpPool",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "xToY",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "lpTokenAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "liquidity",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "swapAmountIn",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "swapAmountOut",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amountOut",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "WithdrawLpProtocolFeesEvent",
+      "fields": [
+        {
+          "name": "lpPool",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "admin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amountX",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amountY",
+          "type": "u64",
+          "index": false
+        }
+      ]
     }
   ],
-  "types": [
+  "errors": [
+    {
+      "code": 6000,
+      "name": "ErrorExample",
+      "msg": "[S001] ErrorExample"
+    },
+    {
+      "code": 6001,
+      "name": "InvalidAuthority",
+      "msg": "Provided authority is different than expected"
+    },
+    {
+      "code": 6002,
+      "name": "InvalidMint",
+      "msg": "Provided mint account is different than expected"
+    },
+    {
+      "code": 6003,
+      "name": "InvalidOwner",
+      "msg": "Provided owner account is different than expected"
+    },
+    {
+      "code": 6004,
+      "name": "InvalidInvariantAuthority",
+      "msg": "Provided Invariant authority is different than expected"
+    },
+    {
+      "code": 6005,
+      "name": "InvalidTokenProgram",
+      "msg": "Provided Token Program for Token is different than expected"
+    },
+    {
+      "code": 6006,
+      "name": "InvalidShares",
+      "msg": "Error originated from compute_lp_share_change"
+    },
+    {
+      "code": 6007,
+      "name": "AmountExceedsMax",
+      "msg": "Required token amount exceeds the provided maximum"
+    },
+    {
+      "code": 6008,
+      "name": "AmountBelowMin",
+      "msg": "Returned token amount is below the provided minimum"
+    },
+    {
+      "code": 6009,
+      "name": "NothingToCompound",
+      "msg": "Accumulated tokens are too small to be compounded"
+    },
+    {
+      "code": 6010,
+      "name": "InvalidTickRange",
+      "msg": "Invalid lower or upper tick of the LP pool range"
+    },
+    {
+      "code": 6011,
+      "name": "InvalidRebalanceConfig",
+      "msg": "Invalid configuration of the managed range"
+    },
+    {
+      "code": 6012,
+      "name": "InvalidKeeper",
+      "msg": "LP pool is not managed or keeper is different than expected"
+    },
+    {
+      "code": 6013,
+      "name": "RebalanceCooldown",
+      "msg": "Rebalance cooldown has not passed yet"
+    },
+    {
+      "code": 6014,
+      "name": "InvalidFee",
+      "msg": "Fee has to be lower than 100%"
+    },
+    {
+      "code": 6015,
+      "name": "InvalidPerformanceFee",
+      "msg": "Performance fee exceeds the maximum"
+    },
+    {
+      "code": 6016,
+      "name": "Paused",
+      "msg": "Minting is paused"
+    },
+    {
+      "code": 6017,
+      "name": "LiquidityCapExceeded",
+      "msg": "Liquidity of the position would exceed the cap"
+    },
+    {
+      "code": 6018,
+      "name": "UnsupportedMintExtension",
+      "msg": "Mint has an unsupported token extension"
+    },
+    {
+      "code": 6019,
+      "name": "InvalidLpTokenConfig",
+      "msg": "Invalid LP token decimals or initial liquidity per LP token"
+    },
+    {
+      "code": 6020,
+      "name": "AlreadyMigrated",
+      "msg": "Account is already in the current layout"
+    },
+    {
+      "code": 6021,
+      "name": "InvalidOracle",
+      "msg": "Provided oracle is different than expected"
+    },
+    {
+      "code": 6022,
+      "name": "TickDeviationExceeded",
+      "msg": "Current tick deviates too much from the time weighted average"
+    },
+    {
+      "code": 6023,
+      "name": "AmountsTooSmall",
+      "msg": "Provided amounts are too small to provide liquidity"
+    },
+    {
+      "code": 6024,
+      "name": "InvalidZapAmount",
+      "msg": "Failed to compute the amount swapped by the zap"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidMetadata",
+      "msg": "Provided metadata account is different than expected"
+    }
+  ]
+};
+
+export const IDL: Protocol = {
+  "version": "0.1.0",
+  "name": "protocol",
+  "instructions": [
+    {
+      "name": "init",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "bumpAuthority",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "initLpPool",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXMetadata",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenYMetadata",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "lpTokenDecimals",
+          "type": "u8"
+        },
+        {
+          "name": "liquidityPerLpToken",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "migrateLpPool",
+      "accounts": [
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
     {
-      "name": "Price",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "v",
-            "type": "u128"
-          }
-        ]
+      "name": "migrateState",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "mintLpToken",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "INVARIANT"
+          ]
+        },
+        {
+          "name": "invState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgramAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenYProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "liquidity",
+          "type": "u128"
+        },
+        {
+          "name": "maxAmountX",
+          "type": "u64"
+        },
+        {
+          "name": "maxAmountY",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "MintLpTokenResult"
       }
     },
     {
-      "name": "Liquidity",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "v",
-            "type": "u128"
-          }
-        ]
+      "name": "mintLpTokenByAmounts",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "INVARIANT"
+          ]
+        },
+        {
+          "name": "invState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgramAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenYProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amountX",
+          "type": "u64"
+        },
+        {
+          "name": "amountY",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "MintLpTokenResult"
       }
     },
     {
-      "name": "FeeGrowth",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "v",
-            "type": "u128"
-          }
-        ]
+      "name": "zapIn",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "INVARIANT"
+          ]
+        },
+        {
+          "name": "invState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgramAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenYProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "xToY",
+          "type": "bool"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "sqrtPriceLimit",
+          "type": "u128"
+        },
+        {
+          "name": "minLpTokenOut",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "MintLpTokenResult"
       }
     },
     {
-      "name": "FixedPoint",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "v",
-            "type": "u128"
-          }
-        ]
+      "name": "burnLpToken",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lastPositionLpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "INVARIANT"
+          ]
+        },
+        {
+          "name": "invState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgramAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lastPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenYProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "liquidity",
+          "type": "u128"
+        },
+        {
+          "name": "minAmountX",
+          "type": "u64"
+        },
+        {
+          "name": "minAmountY",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "BurnLpTokenResult"
       }
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "ErrorExample",
-      "msg": "[S001] ErrorExample"
-    },
-    {
-      "code": 6001,
-      "name": "InvalidAuthority",
-      "msg": "Provided authority is different than expected"
     },
     {
-      "code": 6002,
-      "name": "InvalidMint",
-      "msg": "Provided mint account is different than expected"
-    },
-    {
-      "code": 6003,
-      "name": "InvalidOwner",
-      "msg": "Provided owner account is different than expected"
-    },
-    {
-      "code": 6004,
-      "name": "InvalidInvariantAuthority",
-      "msg": "Provided Invariant authority is different than expected"
-    },
-    {
-      "code": 6005,
-      "name": "InvalidTokenProgram",
-      "msg": "Provided Token Program for Token is different than expected"
-    },
-    {
-      "code": 6006,
-      "name": "InvalidShares",
-      "msg": "Error originated from compute_lp_share_change"
-    }
-  ]
-};
-
-export const IDL: Protocol = {
-  "version": "0.1.0",
-  "name": "protocol",
-  "instructions": [
-    {
-      "name": "init",
+      "name": "compound",
       "accounts": [
         {
-          "name": "state",
+          "name": "payer",
           "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "programAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "INVARIANT"
+          ]
+        },
+        {
+          "name": "invState",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "invProgramAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenYProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "zapOut",
+      "accounts": [
+        {
+          "name": "owner",
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lastPositionLpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountLp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgram",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "INVARIANT"
+          ]
+        },
+        {
+          "name": "invState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "invProgramAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lastPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "invReserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenYProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "rent",
           "isMut": false,
@@ -632,77 +3384,73 @@ export const IDL: Protocol = {
       ],
       "args": [
         {
-          "name": "bumpAuthority",
-          "type": "u8"
+          "name": "liquidity",
+          "type": "u128"
+        },
+        {
+          "name": "xToY",
+          "type": "bool"
+        },
+        {
+          "name": "sqrtPriceLimit",
+          "type": "u128"
+        },
+        {
+          "name": "minAmountOut",
+          "type": "u64"
         }
-      ]
+      ],
+      "returns": {
+        "defined": "BurnLpTokenResult"
+      }
     },
     {
-      "name": "initLpPool",
+      "name": "setRebalanceConfig",
       "accounts": [
         {
           "name": "state",
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "programAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "lpPool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenLp",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
-        },
+          "isSigner": true
+        }
+      ],
+      "args": [
         {
-          "name": "tokenX",
-          "isMut": false,
-          "isSigner": false
+          "name": "keeper",
+          "type": "publicKey"
         },
         {
-          "name": "tokenY",
-          "isMut": false,
-          "isSigner": false
+          "name": "rangeWidth",
+          "type": "i32"
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "rebalanceCooldown",
+          "type": "i64"
         },
         {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "maxTickDeviation",
+          "type": "i32"
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "twapWindow",
+          "type": "u32"
         }
-      ],
-      "args": []
+      ]
     },
     {
-      "name": "mintLpToken",
+      "name": "rebalance",
       "accounts": [
         {
-          "name": "owner",
+          "name": "keeper",
           "isMut": true,
           "isSigner": true
         },
@@ -722,7 +3470,7 @@ export const IDL: Protocol = {
           "isSigner": false
         },
         {
-          "name": "tokenLp",
+          "name": "lastPositionLpPool",
           "isMut": true,
           "isSigner": false
         },
@@ -736,21 +3484,6 @@ export const IDL: Protocol = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "accountLp",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "invProgram",
           "isMut": false,
@@ -774,11 +3507,26 @@ export const IDL: Protocol = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "lastPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newPosition",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "pool",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "positionList",
           "isMut": true,
@@ -795,28 +3543,28 @@ export const IDL: Protocol = {
           "isSigner": false
         },
         {
-          "name": "tickmap",
+          "name": "newLowerTick",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenX",
-          "isMut": false,
+          "name": "newUpperTick",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenY",
-          "isMut": false,
+          "name": "tickmap",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "accountX",
-          "isMut": true,
+          "name": "tokenX",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "accountY",
-          "isMut": true,
+          "name": "tokenY",
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -850,21 +3598,45 @@ export const IDL: Protocol = {
           "isSigner": false
         }
       ],
+      "args": []
+    },
+    {
+      "name": "setLpPoolFees",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "lpPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
       "args": [
         {
-          "name": "liquidity",
+          "name": "mintFee",
+          "type": "u128"
+        },
+        {
+          "name": "burnFee",
+          "type": "u128"
+        },
+        {
+          "name": "performanceFee",
           "type": "u128"
         }
       ]
     },
     {
-      "name": "burnLpToken",
+      "name": "withdrawLpProtocolFees",
       "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
         {
           "name": "state",
           "isMut": false,
@@ -872,7 +3644,7 @@ export const IDL: Protocol = {
         },
         {
           "name": "programAuthority",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -881,150 +3653,186 @@ export const IDL: Protocol = {
           "isSigner": false
         },
         {
-          "name": "lastPositionLpPool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenLp",
+          "name": "reserveX",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "reserveX",
+          "name": "reserveY",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "reserveY",
+          "name": "accountX",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "accountLp",
+          "name": "accountY",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "tokenX",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "tokenY",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "invProgram",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false,
-          "docs": [
-            "INVARIANT"
-          ]
+          "isSigner": true
         },
         {
-          "name": "invState",
+          "name": "tokenXProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "invProgramAuthority",
+          "name": "tokenYProgram",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setPaused",
+      "accounts": [
         {
-          "name": "position",
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "lastPosition",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
         {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "paused",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setLpPoolLimits",
+      "accounts": [
         {
-          "name": "positionList",
-          "isMut": true,
+          "name": "state",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "lowerTick",
+          "name": "lpPool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "upperTick",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
         {
-          "name": "tickmap",
-          "isMut": true,
-          "isSigner": false
+          "name": "paused",
+          "type": "bool"
         },
         {
-          "name": "tokenX",
+          "name": "liquidityCap",
+          "type": "u128"
+        }
+      ]
+    },
+    {
+      "name": "setLpTokenUri",
+      "accounts": [
+        {
+          "name": "state",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenY",
+          "name": "programAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "accountX",
-          "isMut": true,
+          "name": "lpPool",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "accountY",
+          "name": "tokenLp",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "invReserveX",
+          "name": "admin",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "invReserveY",
-          "isMut": true,
+          "name": "tokenProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenXProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "tokenYProgram",
-          "isMut": false,
+          "name": "uri",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "proposeAdmin",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "admin",
           "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newAdmin",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "acceptAdmin",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "newAdmin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
-      "args": [
-        {
-          "name": "liquidity",
-          "type": "u128"
-        }
-      ]
+      "args": []
     }
   ],
   "accounts": [
@@ -1074,6 +3882,94 @@ export const IDL: Protocol = {
           {
             "name": "bump",
             "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "initialLowerTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "initialUpperTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "keeper",
+            "type": "publicKey"
+          },
+          {
+            "name": "rangeWidth",
+            "type": "i32"
+          },
+          {
+            "name": "rebalanceCooldown",
+            "type": "i64"
+          },
+          {
+            "name": "lastRebalanceTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "maxTickDeviation",
+            "type": "i32"
+          },
+          {
+            "name": "mintFee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "burnFee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "feeProtocolTokenX",
+            "type": "u64"
+          },
+          {
+            "name": "feeProtocolTokenY",
+            "type": "u64"
+          },
+          {
+            "name": "performanceFee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "paused",
+            "type": "bool"
+          },
+          {
+            "name": "liquidityCap",
+            "type": {
+              "defined": "Liquidity"
+            }
+          },
+          {
+            "name": "lpTokenDecimals",
+            "type": "u8"
+          },
+          {
+            "name": "liquidityPerLpToken",
+            "type": "u64"
+          },
+          {
+            "name": "lowerTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "upperTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "twapWindow",
+            "type": "u32"
           }
         ]
       }
@@ -1102,12 +3998,68 @@ export const IDL: Protocol = {
           {
             "name": "bumpAuthority",
             "type": "u8"
+          },
+          {
+            "name": "paused",
+            "type": "bool"
+          },
+          {
+            "name": "pendingAdmin",
+            "type": "publicKey"
           }
         ]
       }
     }
   ],
   "types": [
+    {
+      "name": "MintLpTokenResult",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amountX",
+            "type": "u64"
+          },
+          {
+            "name": "amountY",
+            "type": "u64"
+          },
+          {
+            "name": "lpTokenAmount",
+            "type": "u64"
+          },
+          {
+            "name": "liquidity",
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "BurnLpTokenResult",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amountX",
+            "type": "u64"
+          },
+          {
+            "name": "amountY",
+            "type": "u64"
+          },
+          {
+            "name": "lpTokenAmount",
+            "type": "u64"
+          },
+          {
+            "name": "liquidity",
+            "type": "u128"
+          }
+        ]
+      }
+    },
     {
       "name": "Price",
       "type": {
@@ -1157,6 +4109,213 @@ export const IDL: Protocol = {
       }
     }
   ],
+  "events": [
+    {
+      "name": "MintLpTokenEvent",
+      "fields": [
+        {
+          "name": "lpPool",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "liquidity",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "lpTokenAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amountX",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amountY",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mintFeeX",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "mintFeeY",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ZapInEvent",
+      "fields": [
+        {
+          "name": "lpPool",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "xToY",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "swapAmountIn",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "swapAmountOut",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "lpTokenAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "liquidity",
+          "type": "u128",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "BurnLpTokenEvent",
+      "fields": [
+        {
+          "name": "lpPool",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "liquidity",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "lpTokenAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amountX",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amountY",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "burnFeeX",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "burnFeeY",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ZapOutEvent",
+      "fields": [
+        {
+          "name": "lpPool",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "owner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "xToY",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "lpTokenAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "liquidity",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "swapAmountIn",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "swapAmountOut",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amountOut",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "WithdrawLpProtocolFeesEvent",
+      "fields": [
+        {
+          "name": "lpPool",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "admin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amountX",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amountY",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    }
+  ],
   "errors": [
     {
       "code": 6000,
@@ -1192,6 +4351,101 @@ export const IDL: Protocol = {
       "code": 6006,
       "name": "InvalidShares",
       "msg": "Error originated from compute_lp_share_change"
+    },
+    {
+      "code": 6007,
+      "name": "AmountExceedsMax",
+      "msg": "Required token amount exceeds the provided maximum"
+    },
+    {
+      "code": 6008,
+      "name": "AmountBelowMin",
+      "msg": "Returned token amount is below the provided minimum"
+    },
+    {
+      "code": 6009,
+      "name": "NothingToCompound",
+      "msg": "Accumulated tokens are too small to be compounded"
+    },
+    {
+      "code": 6010,
+      "name": "InvalidTickRange",
+      "msg": "Invalid lower or upper tick of the LP pool range"
+    },
+    {
+      "code": 6011,
+      "name": "InvalidRebalanceConfig",
+      "msg": "Invalid configuration of the managed range"
+    },
+    {
+      "code": 6012,
+      "name": "InvalidKeeper",
+      "msg": "LP pool is not managed or keeper is different than expected"
+    },
+    {
+      "code": 6013,
+      "name": "RebalanceCooldown",
+      "msg": "Rebalance cooldown has not passed yet"
+    },
+    {
+      "code": 6014,
+      "name": "InvalidFee",
+      "msg": "Fee has to be lower than 100%"
+    },
+    {
+      "code": 6015,
+      "name": "InvalidPerformanceFee",
+      "msg": "Performance fee exceeds the maximum"
+    },
+    {
+      "code": 6016,
+      "name": "Paused",
+      "msg": "Minting is paused"
+    },
+    {
+      "code": 6017,
+      "name": "LiquidityCapExceeded",
+      "msg": "Liquidity of the position would exceed the cap"
+    },
+    {
+      "code": 6018,
+      "name": "UnsupportedMintExtension",
+      "msg": "Mint has an unsupported token extension"
+    },
+    {
+      "code": 6019,
+      "name": "InvalidLpTokenConfig",
+      "msg": "Invalid LP token decimals or initial liquidity per LP token"
+    },
+    {
+      "code": 6020,
+      "name": "AlreadyMigrated",
+      "msg": "Account is already in the current layout"
+    },
+    {
+      "code": 6021,
+      "name": "InvalidOracle",
+      "msg": "Provided oracle is different than expected"
+    },
+    {
+      "code": 6022,
+      "name": "TickDeviationExceeded",
+      "msg": "Current tick deviates too much from the time weighted average"
+    },
+    {
+      "code": 6023,
+      "name": "AmountsTooSmall",
+      "msg": "Provided amounts are too small to provide liquidity"
+    },
+    {
+      "code": 6024,
+      "name": "InvalidZapAmount",
+      "msg": "Failed to compute the amount swapped by the zap"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidMetadata",
+      "msg": "Provided metadata account is different than expected"
     }
   ]
 };
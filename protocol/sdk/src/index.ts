@@ -8,5 +8,6 @@ export {
   liquidityToLpTokenAmount,
   lpTokenAmountToLiquidity,
   computeLpShareChange,
+  computeRebalanceRange,
 } from "./math";
 export { Protocol } from "./protocol";
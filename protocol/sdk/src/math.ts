@@ -198,10 +198,11 @@ export const liquidityToLpTokenAmount = (
   lpTokenSupply: Decimal,
   currentLiquidity: Decimal,
   liquidityDelta: Decimal,
-  roundingUp: boolean
+  roundingUp: boolean,
+  liquidityPerLpToken: BN = new BN(ONE_LP_TOKEN)
 ): Decimal => {
   if (currentLiquidity.v.eq(new BN(0))) {
-    return { v: liquidityDelta.v.divn(ONE_LP_TOKEN) };
+    return { v: liquidityDelta.v.div(liquidityPerLpToken) };
   }
 
   if (roundingUp) {
@@ -218,10 +219,11 @@ export const lpTokenAmountToLiquidity = (
   lpTokenSupply: Decimal,
   currentLiquidity: Decimal,
   lpTokenAmountDelta: Decimal,
-  roundingUp: boolean
+  roundingUp: boolean,
+  liquidityPerLpToken: BN = new BN(ONE_LP_TOKEN)
 ) => {
   if (lpTokenSupply.v.eq(new BN(0))) {
-    return { v: lpTokenAmountDelta.v.muln(ONE_LP_TOKEN) };
+    return { v: lpTokenAmountDelta.v.mul(liquidityPerLpToken) };
   }
 
   if (roundingUp && lpTokenSupply.v !== new BN(0)) {
@@ -246,7 +248,8 @@ export const computeLpShareChange = (
   yBefore: Decimal,
   tickSpacing: number,
   currentTickIndex: number,
-  currentSqrtPrice: Decimal
+  currentSqrtPrice: Decimal,
+  liquidityPerLpToken: BN = new BN(ONE_LP_TOKEN)
 ): {
   positionDetails: {
     lowerTick: number;
@@ -316,7 +319,8 @@ export const computeLpShareChange = (
     lpTokenSupply,
     currentLiquidity,
     liquidityDelta,
-    !provideLiquidity
+    !provideLiquidity,
+    liquidityPerLpToken
   );
 
   if (lpTokenChange.v.eq(new BN(0))) {
@@ -334,3 +338,22 @@ export const computeLpShareChange = (
     leftoverAmounts: [{ v: leftoverX }, { v: leftoverY }],
   };
 };
+
+// range a rebalance moves the lp pool to, centered on the reference tick
+export const computeRebalanceRange = (
+  currentTickIndex: number,
+  tickSpacing: number,
+  rangeWidth: number
+): { lowerTickIndex: number; upperTickIndex: number } => {
+  const minTick = getMinTick(tickSpacing);
+  const maxTick = getMaxTick(tickSpacing);
+
+  const lowerTick =
+    Math.floor((currentTickIndex - Math.trunc(rangeWidth / 2)) / tickSpacing) *
+    tickSpacing;
+  const lowerTickIndex = Math.min(
+    Math.max(lowerTick, minTick),
+    maxTick - rangeWidth
+  );
+  return { lowerTickIndex, upperTickIndex: lowerTickIndex + rangeWidth };
+};
@@ -1,4 +1,5 @@
 import {
+  AccountMeta,
   Connection,
   Keypair,
   PublicKey,
@@ -10,18 +11,29 @@ import {
 } from "@solana/web3.js";
 import { getProtocolProgramAddress, Network } from "./network";
 import { IWallet } from "./wallet";
-import { AnchorProvider, BN, Program, utils } from "@coral-xyz/anchor";
+import {
+  AnchorProvider,
+  BN,
+  EventParser,
+  Program,
+  utils,
+} from "@coral-xyz/anchor";
 import { IDL, Protocol as ProtocolProgram } from "./idl/protocol";
 import {
   bigNumberToBuffer,
   computeUnitsInstruction,
+  rangeSeed,
   signAndSend,
 } from "./utils";
 import {
   LP_POOL_SEED,
   LP_TOKEN_SEED,
+  MAX_SQRT_PRICE,
+  MIN_SQRT_PRICE,
+  ONE_LP_TOKEN,
   PROTOCOL_AUTHORITY_SEED,
   PROTOCOL_STATE_SEED,
+  U64_MAX,
 } from "./consts";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -31,8 +43,19 @@ import {
 } from "@solana/spl-token";
 import {
   IBurnLpToken,
+  ICompound,
   IInitLpPool,
+  IMigrateLpPool,
   IMintLpToken,
+  IMintLpTokenByAmounts,
+  IRebalance,
+  ISetLpPoolFees,
+  ISetLpPoolLimits,
+  ISetLpTokenUri,
+  ISetRebalanceConfig,
+  IWithdrawLpProtocolFees,
+  IZapIn,
+  IZapOut,
   LpPoolStructure,
 } from "./types";
 import {
@@ -41,6 +64,7 @@ import {
   Market,
   Pair,
 } from "@invariant-labs/sdk-eclipse";
+import { PoolStructure } from "@invariant-labs/sdk-eclipse/lib/market";
 import { getMaxTick, getMinTick } from "@invariant-labs/sdk-eclipse/lib/utils";
 
 export class Protocol {
@@ -93,7 +117,11 @@ export class Protocol {
     );
   }
 
-  getLpPoolAddressAndBump(pair: Pair): [PublicKey, number] {
+  getLpPoolAddressAndBump(
+    pair: Pair,
+    lowerTickIndex: number = getMinTick(pair.feeTier.tickSpacing!),
+    upperTickIndex: number = getMaxTick(pair.feeTier.tickSpacing!)
+  ): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
       [
         Buffer.from(utils.bytes.utf8.encode(LP_POOL_SEED)),
@@ -101,12 +129,17 @@ export class Protocol {
         pair.tokenY.toBuffer(),
         bigNumberToBuffer(pair.feeTier.fee, 128),
         bigNumberToBuffer(new BN(pair.feeTier.tickSpacing as number), 16),
+        rangeSeed(pair.feeTier.tickSpacing!, lowerTickIndex, upperTickIndex),
       ],
       this.program.programId
     );
   }
 
-  getLpTokenAddressAndBump(pair: Pair): [PublicKey, number] {
+  getLpTokenAddressAndBump(
+    pair: Pair,
+    lowerTickIndex: number = getMinTick(pair.feeTier.tickSpacing!),
+    upperTickIndex: number = getMaxTick(pair.feeTier.tickSpacing!)
+  ): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
       [
         Buffer.from(utils.bytes.utf8.encode(LP_TOKEN_SEED)),
@@ -114,6 +147,7 @@ export class Protocol {
         pair.tokenY.toBuffer(),
         bigNumberToBuffer(pair.feeTier.fee, 128),
         bigNumberToBuffer(new BN(pair.feeTier.tickSpacing as number), 16),
+        rangeSeed(pair.feeTier.tickSpacing!, lowerTickIndex, upperTickIndex),
       ],
       this.program.programId
     );
@@ -123,13 +157,33 @@ export class Protocol {
     return getAssociatedTokenAddressSync(token, this.programAuthority, true);
   }
 
-  async getLpPool(pair: Pair) {
-    const [address] = this.getLpPoolAddressAndBump(pair);
+  async getLpPool(
+    pair: Pair,
+    lowerTickIndex?: number,
+    upperTickIndex?: number
+  ) {
+    const [address] = this.getLpPoolAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
     return (await this.program.account.lpPool.fetch(
       address
     )) as LpPoolStructure;
   }
 
+  async getEvents(signature: TransactionSignature) {
+    const tx = await this.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const eventParser = new EventParser(
+      this.program.programId,
+      this.program.coder
+    );
+    return [...eventParser.parseLogs(tx?.meta?.logMessages ?? [])];
+  }
+
   async sendTx(ix: TransactionInstruction[], signers: Keypair[]) {
     const tx = new Transaction().add(...ix);
     return await signAndSend(tx, signers, this.connection);
@@ -217,25 +271,97 @@ export class Protocol {
       .instruction();
   }
 
+  async migrateState(signer: Keypair) {
+    const ix = await this.migrateStateIx(signer);
+    return await this.sendTx([ix], [signer]);
+  }
+
+  async migrateStateIx(signer?: Keypair): Promise<TransactionInstruction> {
+    const admin = signer?.publicKey ?? this.wallet.publicKey;
+    return await this.program.methods
+      .migrateState()
+      .accounts({
+        state: this.stateAddress,
+        admin,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  async proposeAdmin(newAdmin: PublicKey, signer: Keypair) {
+    const ix = await this.proposeAdminIx(newAdmin, signer);
+    return await this.sendTx([ix], [signer]);
+  }
+
+  async proposeAdminIx(
+    newAdmin: PublicKey,
+    signer?: Keypair
+  ): Promise<TransactionInstruction> {
+    const admin = signer?.publicKey ?? this.wallet.publicKey;
+    return await this.program.methods
+      .proposeAdmin(newAdmin)
+      .accounts({
+        state: this.stateAddress,
+        admin,
+      })
+      .instruction();
+  }
+
+  async acceptAdmin(signer: Keypair) {
+    const ix = await this.acceptAdminIx(signer);
+    return await this.sendTx([ix], [signer]);
+  }
+
+  async acceptAdminIx(signer?: Keypair): Promise<TransactionInstruction> {
+    const newAdmin = signer?.publicKey ?? this.wallet.publicKey;
+    return await this.program.methods
+      .acceptAdmin()
+      .accounts({
+        state: this.stateAddress,
+        newAdmin,
+      })
+      .instruction();
+  }
+
   async initLpPool(params: IInitLpPool, signer: Keypair) {
     const ix = await this.initLpPoolIx(params, signer);
     return await this.sendTx([ix], [signer]);
   }
 
   async initLpPoolIx(
-    { pair, ...accounts }: IInitLpPool,
+    {
+      pair,
+      lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!),
+      upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!),
+      lpTokenDecimals = 6,
+      liquidityPerLpToken = new BN(ONE_LP_TOKEN),
+      ...accounts
+    }: IInitLpPool,
     signer?: Keypair
   ): Promise<TransactionInstruction> {
     const payer = signer?.publicKey ?? this.wallet.publicKey;
 
-    const [lpPool] = this.getLpPoolAddressAndBump(pair);
-    const [tokenLp] = this.getLpTokenAddressAndBump(pair);
+    const [lpPool] = this.getLpPoolAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
+    const [tokenLp] = this.getLpTokenAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
     const pool =
       accounts.pool ??
       (await pair.getAddress(new PublicKey(getMarketAddress(this.network))));
 
     return await this.program.methods
-      .initLpPool()
+      .initLpPool(
+        lowerTickIndex,
+        upperTickIndex,
+        lpTokenDecimals,
+        liquidityPerLpToken
+      )
       .accounts({
         state: this.stateAddress,
         programAuthority: this.programAuthority,
@@ -245,6 +371,8 @@ export class Protocol {
         pool,
         tokenX: pair.tokenX,
         tokenY: pair.tokenY,
+        tokenXMetadata: null,
+        tokenYMetadata: null,
         tokenProgram: TOKEN_2022_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
@@ -253,38 +381,85 @@ export class Protocol {
       .instruction();
   }
 
-  async mintLpToken(params: IMintLpToken, signer: Keypair) {
-    const setCuIx = computeUnitsInstruction(1_400_000);
-
-    const tokenXProgram =
-      params.tokenXProgram ??
-      (await getTokenProgramAddress(this.connection, params.pair.tokenX));
-    const tokenYProgram =
-      params.tokenYProgram ??
-      (await getTokenProgramAddress(this.connection, params.pair.tokenY));
+  async setLpTokenUri(params: ISetLpTokenUri, signer: Keypair) {
+    const ix = await this.setLpTokenUriIx(params, signer);
+    return await this.sendTx([ix], [signer]);
+  }
 
-    const reserveXmaybeIx = this.newReserveIfNoneIx(
-      params.pair.tokenX,
-      tokenXProgram,
-      signer
+  async setLpTokenUriIx(
+    {
+      pair,
+      uri,
+      lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!),
+      upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!),
+    }: ISetLpTokenUri,
+    signer?: Keypair
+  ): Promise<TransactionInstruction> {
+    const admin = signer?.publicKey ?? this.wallet.publicKey;
+    const [lpPool] = this.getLpPoolAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
     );
-    const reserveYmaybeIx = this.newReserveIfNoneIx(
-      params.pair.tokenY,
-      tokenYProgram,
-      signer
+    const [tokenLp] = this.getLpTokenAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
     );
 
-    const [tokenLp] = this.getLpTokenAddressAndBump(params.pair);
-    const accountLpMaybeIx = this.newLpAccountIfNoneIx(tokenLp, signer);
+    return await this.program.methods
+      .setLpTokenUri(uri)
+      .accounts({
+        state: this.stateAddress,
+        programAuthority: this.programAuthority,
+        lpPool,
+        tokenLp,
+        admin,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
 
+  async migrateLpPool(params: IMigrateLpPool, signer: Keypair) {
+    const ix = await this.migrateLpPoolIx(params, signer);
+    return await this.sendTx([ix], [signer]);
+  }
+
+  async migrateLpPoolIx(
+    {
+      pair,
+      lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!),
+      upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!),
+    }: IMigrateLpPool,
+    signer?: Keypair
+  ): Promise<TransactionInstruction> {
+    const payer = signer?.publicKey ?? this.wallet.publicKey;
+    const [lpPool] = this.getLpPoolAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
+
+    return await this.program.methods
+      .migrateLpPool()
+      .accounts({
+        lpPool,
+        payer,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+  }
+
+  async mintLpToken(params: IMintLpToken, signer: Keypair) {
+    const setCuIx = computeUnitsInstruction(1_400_000);
+    const { tokenXProgram, tokenYProgram, setupIxs } =
+      await this.mintLpTokenSetupIxs(params, signer);
     const ix = await this.mintLpTokenIx(
       { tokenXProgram, tokenYProgram, ...params },
       signer
     );
-    return await this.sendTx(
-      [setCuIx, reserveXmaybeIx, reserveYmaybeIx, accountLpMaybeIx, ix],
-      [signer]
-    );
+    return await this.sendTx([setCuIx, ...setupIxs, ix], [signer]);
   }
 
   async mintLpTokenIx(
@@ -293,104 +468,198 @@ export class Protocol {
       invariant,
       poolStructure,
       liquidityDelta,
+      maxAmountX,
+      maxAmountY,
+      lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!),
+      upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!),
       ...accounts
     }: IMintLpToken,
     signer?: Keypair
   ) {
     const owner = signer?.publicKey ?? this.wallet.publicKey;
-
-    const [lpPool] = this.getLpPoolAddressAndBump(pair);
-    const [tokenLp] = this.getLpTokenAddressAndBump(pair);
-    const reserveX = this.getReserveAddress(pair.tokenX);
-    const reserveY = this.getReserveAddress(pair.tokenY);
-    const accountLp = getAssociatedTokenAddressSync(
-      tokenLp,
+    const mintAccounts = await this.getMintLpTokenAccounts(
+      pair,
+      invariant,
       owner,
-      undefined,
-      TOKEN_2022_PROGRAM_ID,
-      ASSOCIATED_TOKEN_PROGRAM_ID
+      lowerTickIndex,
+      upperTickIndex,
+      poolStructure
     );
 
-    const {
-      tokenXReserve: invReserveX,
-      tokenYReserve: invReserveY,
-      tickmap,
-    } = poolStructure ?? (await invariant.getPool(pair));
+    return await this.program.methods
+      .mintLpToken(
+        liquidityDelta,
+        maxAmountX ?? new BN(U64_MAX),
+        maxAmountY ?? new BN(U64_MAX)
+      )
+      .accounts({
+        ...mintAccounts,
+        ...accounts,
+      })
+      .instruction();
+  }
 
-    // TODO: After Eclipse marketplace sdk update this won't need async at all
-    const pool = await pair.getAddress(
-      new PublicKey(getMarketAddress(this.network))
+  async mintLpTokenByAmounts(params: IMintLpTokenByAmounts, signer: Keypair) {
+    const setCuIx = computeUnitsInstruction(1_400_000);
+    const { tokenXProgram, tokenYProgram, setupIxs } =
+      await this.mintLpTokenSetupIxs(params, signer);
+    const ix = await this.mintLpTokenByAmountsIx(
+      { tokenXProgram, tokenYProgram, ...params },
+      signer
     );
+    return await this.sendTx([setCuIx, ...setupIxs, ix], [signer]);
+  }
 
-    const { positionListAddress: positionList } =
-      await invariant.getPositionListAddress(this.programAuthority);
-
-    const lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!);
-    const upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!);
-
-    const { tickAddress: lowerTick } = await invariant.getTickAddress(
+  async mintLpTokenByAmountsIx(
+    {
       pair,
-      lowerTickIndex
-    );
-    const { tickAddress: upperTick } = await invariant.getTickAddress(
+      invariant,
+      poolStructure,
+      amountX,
+      amountY,
+      lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!),
+      upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!),
+      ...accounts
+    }: IMintLpTokenByAmounts,
+    signer?: Keypair
+  ) {
+    const owner = signer?.publicKey ?? this.wallet.publicKey;
+    const mintAccounts = await this.getMintLpTokenAccounts(
       pair,
-      upperTickIndex
+      invariant,
+      owner,
+      lowerTickIndex,
+      upperTickIndex,
+      poolStructure
     );
 
     return await this.program.methods
-      .mintLpToken(liquidityDelta)
+      .mintLpTokenByAmounts(amountX, amountY)
       .accounts({
-        state: this.stateAddress,
-        programAuthority: this.programAuthority,
-        lpPool,
-        tokenLp,
-        accountLp,
-        owner,
-        pool,
-        tokenX: pair.tokenX,
-        tokenY: pair.tokenY,
-        reserveX,
-        reserveY,
-        invProgram: invariant.program.programId,
-        invProgramAuthority: invariant.programAuthority,
-        invState: invariant.stateAddress,
-        lowerTick,
-        upperTick,
-        invReserveX,
-        invReserveY,
-        tickmap,
-        positionList,
-        tokenProgram: TOKEN_2022_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
+        ...mintAccounts,
         ...accounts,
       })
       .instruction();
   }
 
-  async burnLpToken(params: IBurnLpToken, signer: Keypair) {
+  async zapIn(params: IZapIn, signer: Keypair) {
     const setCuIx = computeUnitsInstruction(1_400_000);
-    const ix = await this.burnLpTokenIx(params, signer);
-    return await this.sendTx([setCuIx, ix], [signer]);
+    const { tokenXProgram, tokenYProgram, setupIxs } =
+      await this.mintLpTokenSetupIxs(params, signer);
+    const ix = await this.zapInIx(
+      { tokenXProgram, tokenYProgram, ...params },
+      signer
+    );
+    return await this.sendTx([setCuIx, ...setupIxs, ix], [signer]);
   }
 
-  async burnLpTokenIx(
+  async zapInIx(
     {
       pair,
       invariant,
       poolStructure,
-      liquidityDelta,
+      xToY,
+      amount,
+      sqrtPriceLimit = new BN(xToY ? MIN_SQRT_PRICE : MAX_SQRT_PRICE),
+      minLpTokenOut = new BN(0),
+      lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!),
+      upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!),
+      crossedTicks = [],
       ...accounts
-    }: IBurnLpToken,
+    }: IZapIn,
     signer?: Keypair
   ) {
     const owner = signer?.publicKey ?? this.wallet.publicKey;
+    const mintAccounts = await this.getMintLpTokenAccounts(
+      pair,
+      invariant,
+      owner,
+      lowerTickIndex,
+      upperTickIndex,
+      poolStructure
+    );
+
+    return await this.program.methods
+      .zapIn(xToY, amount, sqrtPriceLimit, minLpTokenOut)
+      .accounts({
+        ...mintAccounts,
+        ...accounts,
+      })
+      .remainingAccounts(this.tickAccountMetas(crossedTicks))
+      .instruction();
+  }
+
+  // creates the reserves and the lp token account of the owner if they don't exist yet
+  private async mintLpTokenSetupIxs(
+    params: {
+      pair: Pair;
+      lowerTickIndex?: number;
+      upperTickIndex?: number;
+      tokenXProgram?: PublicKey;
+      tokenYProgram?: PublicKey;
+    },
+    signer: Keypair
+  ) {
+    const tokenXProgram =
+      params.tokenXProgram ??
+      (await getTokenProgramAddress(this.connection, params.pair.tokenX));
+    const tokenYProgram =
+      params.tokenYProgram ??
+      (await getTokenProgramAddress(this.connection, params.pair.tokenY));
+
+    const reserveXmaybeIx = this.newReserveIfNoneIx(
+      params.pair.tokenX,
+      tokenXProgram,
+      signer
+    );
+    const reserveYmaybeIx = this.newReserveIfNoneIx(
+      params.pair.tokenY,
+      tokenYProgram,
+      signer
+    );
+
+    const [tokenLp] = this.getLpTokenAddressAndBump(
+      params.pair,
+      params.lowerTickIndex,
+      params.upperTickIndex
+    );
+    const accountLpMaybeIx = this.newLpAccountIfNoneIx(tokenLp, signer);
+
+    return {
+      tokenXProgram,
+      tokenYProgram,
+      setupIxs: [reserveXmaybeIx, reserveYmaybeIx, accountLpMaybeIx],
+    };
+  }
+
+  private tickAccountMetas(ticks: PublicKey[]): AccountMeta[] {
+    return ticks.map((pubkey) => ({
+      pubkey,
+      isSigner: false,
+      isWritable: true,
+    }));
+  }
 
-    const [lpPool] = this.getLpPoolAddressAndBump(pair);
-    const [tokenLp] = this.getLpTokenAddressAndBump(pair);
+  private async getMintLpTokenAccounts(
+    pair: Pair,
+    invariant: Market,
+    owner: PublicKey,
+    lowerTickIndex: number,
+    upperTickIndex: number,
+    poolStructure?: PoolStructure
+  ) {
+    const [lpPool] = this.getLpPoolAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
+    const [tokenLp] = this.getLpTokenAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
     const reserveX = this.getReserveAddress(pair.tokenX);
     const reserveY = this.getReserveAddress(pair.tokenY);
-
     const accountLp = getAssociatedTokenAddressSync(
       tokenLp,
       owner,
@@ -403,14 +672,10 @@ export class Protocol {
       tokenXReserve: invReserveX,
       tokenYReserve: invReserveY,
       tickmap,
+      oracleInitialized,
+      oracleAddress,
     } = poolStructure ?? (await invariant.getPool(pair));
 
-    const tokenXProgram =
-      accounts.tokenXProgram ??
-      (await getTokenProgramAddress(this.connection, pair.tokenX));
-    const tokenYProgram =
-      accounts.tokenYProgram ??
-      (await getTokenProgramAddress(this.connection, pair.tokenY));
     // TODO: After Eclipse marketplace sdk update this won't need async at all
     const pool = await pair.getAddress(
       new PublicKey(getMarketAddress(this.network))
@@ -419,9 +684,6 @@ export class Protocol {
     const { positionListAddress: positionList } =
       await invariant.getPositionListAddress(this.programAuthority);
 
-    const lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!);
-    const upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!);
-
     const { tickAddress: lowerTick } = await invariant.getTickAddress(
       pair,
       lowerTickIndex
@@ -431,36 +693,570 @@ export class Protocol {
       upperTickIndex
     );
 
-    return await this.program.methods
-      .burnLpToken(liquidityDelta)
-      .accounts({
-        state: this.stateAddress,
-        programAuthority: this.programAuthority,
-        lpPool,
-        tokenLp,
-        accountLp,
-        owner,
+    return {
+      state: this.stateAddress,
+      programAuthority: this.programAuthority,
+      lpPool,
+      tokenLp,
+      accountLp,
+      owner,
+      pool,
+      oracle: oracleInitialized ? oracleAddress : null,
+      tokenX: pair.tokenX,
+      tokenY: pair.tokenY,
+      reserveX,
+      reserveY,
+      invProgram: invariant.program.programId,
+      invProgramAuthority: invariant.programAuthority,
+      invState: invariant.stateAddress,
+      lowerTick,
+      upperTick,
+      invReserveX,
+      invReserveY,
+      tickmap,
+      positionList,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  async burnLpToken(params: IBurnLpToken, signer: Keypair) {
+    const setCuIx = computeUnitsInstruction(1_400_000);
+    const ix = await this.burnLpTokenIx(params, signer);
+    return await this.sendTx([setCuIx, ix], [signer]);
+  }
+
+  async burnLpTokenIx(
+    {
+      pair,
+      invariant,
+      poolStructure,
+      liquidityDelta,
+      minAmountX,
+      minAmountY,
+      lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!),
+      upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!),
+      ...accounts
+    }: IBurnLpToken,
+    signer?: Keypair
+  ) {
+    const owner = signer?.publicKey ?? this.wallet.publicKey;
+    const burnAccounts = await this.getBurnLpTokenAccounts(
+      pair,
+      invariant,
+      owner,
+      lowerTickIndex,
+      upperTickIndex,
+      poolStructure,
+      accounts.tokenXProgram,
+      accounts.tokenYProgram
+    );
+
+    return await this.program.methods
+      .burnLpToken(
+        liquidityDelta,
+        minAmountX ?? new BN(0),
+        minAmountY ?? new BN(0)
+      )
+      .accounts({
+        ...burnAccounts,
+        ...accounts,
+      })
+      .instruction();
+  }
+
+  async zapOut(params: IZapOut, signer: Keypair) {
+    const setCuIx = computeUnitsInstruction(1_400_000);
+    const ix = await this.zapOutIx(params, signer);
+    return await this.sendTx([setCuIx, ix], [signer]);
+  }
+
+  async zapOutIx(
+    {
+      pair,
+      invariant,
+      poolStructure,
+      liquidityDelta,
+      xToY,
+      sqrtPriceLimit = new BN(xToY ? MIN_SQRT_PRICE : MAX_SQRT_PRICE),
+      minAmountOut = new BN(0),
+      lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!),
+      upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!),
+      crossedTicks = [],
+      ...accounts
+    }: IZapOut,
+    signer?: Keypair
+  ) {
+    const owner = signer?.publicKey ?? this.wallet.publicKey;
+    const burnAccounts = await this.getBurnLpTokenAccounts(
+      pair,
+      invariant,
+      owner,
+      lowerTickIndex,
+      upperTickIndex,
+      poolStructure,
+      accounts.tokenXProgram,
+      accounts.tokenYProgram
+    );
+
+    return await this.program.methods
+      .zapOut(liquidityDelta, xToY, sqrtPriceLimit, minAmountOut)
+      .accounts({
+        ...burnAccounts,
+        ...accounts,
+      })
+      .remainingAccounts(this.tickAccountMetas(crossedTicks))
+      .instruction();
+  }
+
+  async compound(params: ICompound, signer: Keypair) {
+    const setCuIx = computeUnitsInstruction(1_400_000);
+    const ix = await this.compoundIx(params, signer);
+    return await this.sendTx([setCuIx, ix], [signer]);
+  }
+
+  async compoundIx(
+    {
+      pair,
+      invariant,
+      poolStructure,
+      lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!),
+      upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!),
+      ...accounts
+    }: ICompound,
+    signer?: Keypair
+  ) {
+    const payer = signer?.publicKey ?? this.wallet.publicKey;
+
+    const [lpPool] = this.getLpPoolAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
+    const reserveX = this.getReserveAddress(pair.tokenX);
+    const reserveY = this.getReserveAddress(pair.tokenY);
+
+    const { tokenXReserve: invReserveX, tokenYReserve: invReserveY } =
+      poolStructure ?? (await invariant.getPool(pair));
+
+    const tokenXProgram =
+      accounts.tokenXProgram ??
+      (await getTokenProgramAddress(this.connection, pair.tokenX));
+    const tokenYProgram =
+      accounts.tokenYProgram ??
+      (await getTokenProgramAddress(this.connection, pair.tokenY));
+    // TODO: After Eclipse marketplace sdk update this won't need async at all
+    const pool = await pair.getAddress(
+      new PublicKey(getMarketAddress(this.network))
+    );
+
+    const { tickAddress: lowerTick } = await invariant.getTickAddress(
+      pair,
+      lowerTickIndex
+    );
+    const { tickAddress: upperTick } = await invariant.getTickAddress(
+      pair,
+      upperTickIndex
+    );
+
+    return await this.program.methods
+      .compound()
+      .accounts({
+        payer,
+        state: this.stateAddress,
+        programAuthority: this.programAuthority,
+        lpPool,
+        reserveX,
+        reserveY,
+        invProgram: invariant.program.programId,
+        invState: invariant.stateAddress,
+        invProgramAuthority: invariant.programAuthority,
         pool,
+        lowerTick,
+        upperTick,
         tokenX: pair.tokenX,
         tokenY: pair.tokenY,
-        reserveX,
-        reserveY,
+        invReserveX,
+        invReserveY,
         tokenXProgram,
         tokenYProgram,
+        ...accounts,
+      })
+      .instruction();
+  }
+
+  async setRebalanceConfig(params: ISetRebalanceConfig, signer: Keypair) {
+    const ix = await this.setRebalanceConfigIx(params, signer);
+    return await this.sendTx([ix], [signer]);
+  }
+
+  async setRebalanceConfigIx(
+    {
+      pair,
+      keeper,
+      rangeWidth,
+      rebalanceCooldown,
+      maxTickDeviation,
+      twapWindow,
+      lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!),
+      upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!),
+    }: ISetRebalanceConfig,
+    signer?: Keypair
+  ): Promise<TransactionInstruction> {
+    const admin = signer?.publicKey ?? this.wallet.publicKey;
+    const [lpPool] = this.getLpPoolAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
+
+    return await this.program.methods
+      .setRebalanceConfig(
+        keeper,
+        rangeWidth,
+        rebalanceCooldown,
+        maxTickDeviation,
+        twapWindow
+      )
+      .accounts({
+        state: this.stateAddress,
+        lpPool,
+        admin,
+      })
+      .instruction();
+  }
+
+  async rebalance(params: IRebalance, signer: Keypair) {
+    const setCuIx = computeUnitsInstruction(1_400_000);
+    const ix = await this.rebalanceIx(params, signer);
+    return await this.sendTx([setCuIx, ix], [signer]);
+  }
+
+  async rebalanceIx(
+    {
+      pair,
+      invariant,
+      poolStructure,
+      newLowerTickIndex,
+      newUpperTickIndex,
+      lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!),
+      upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!),
+      crossedTicks = [],
+      ...accounts
+    }: IRebalance,
+    signer?: Keypair
+  ): Promise<TransactionInstruction> {
+    const keeper = signer?.publicKey ?? this.wallet.publicKey;
+
+    const [lpPool] = this.getLpPoolAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
+    // position is held in the current range, not the initial one
+    const lpPoolState = await this.getLpPool(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
+    const reserveX = this.getReserveAddress(pair.tokenX);
+    const reserveY = this.getReserveAddress(pair.tokenY);
+
+    const {
+      tokenXReserve: invReserveX,
+      tokenYReserve: invReserveY,
+      tickmap,
+      oracleAddress: oracle,
+    } = poolStructure ?? (await invariant.getPool(pair));
+
+    const tokenXProgram =
+      accounts.tokenXProgram ??
+      (await getTokenProgramAddress(this.connection, pair.tokenX));
+    const tokenYProgram =
+      accounts.tokenYProgram ??
+      (await getTokenProgramAddress(this.connection, pair.tokenY));
+    // TODO: After Eclipse marketplace sdk update this won't need async at all
+    const pool = await pair.getAddress(
+      new PublicKey(getMarketAddress(this.network))
+    );
+
+    const { positionListAddress: positionList } =
+      await invariant.getPositionListAddress(this.programAuthority);
+
+    const { tickAddress: lowerTick } = await invariant.getTickAddress(
+      pair,
+      lpPoolState.lowerTickIndex
+    );
+    const { tickAddress: upperTick } = await invariant.getTickAddress(
+      pair,
+      lpPoolState.upperTickIndex
+    );
+    const { tickAddress: newLowerTick } = await invariant.getTickAddress(
+      pair,
+      newLowerTickIndex
+    );
+    const { tickAddress: newUpperTick } = await invariant.getTickAddress(
+      pair,
+      newUpperTickIndex
+    );
+
+    return await this.program.methods
+      .rebalance()
+      .accounts({
+        keeper,
+        state: this.stateAddress,
+        programAuthority: this.programAuthority,
+        lpPool,
+        reserveX,
+        reserveY,
         invProgram: invariant.program.programId,
-        invProgramAuthority: invariant.programAuthority,
         invState: invariant.stateAddress,
+        invProgramAuthority: invariant.programAuthority,
+        pool,
+        oracle,
+        positionList,
         lowerTick,
         upperTick,
+        newLowerTick,
+        newUpperTick,
+        tickmap,
+        tokenX: pair.tokenX,
+        tokenY: pair.tokenY,
         invReserveX,
         invReserveY,
-        tickmap,
-        positionList,
-        tokenProgram: TOKEN_2022_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenXProgram,
+        tokenYProgram,
+        rent: SYSVAR_RENT_PUBKEY,
         systemProgram: SystemProgram.programId,
         ...accounts,
       })
+      .remainingAccounts(this.tickAccountMetas(crossedTicks))
+      .instruction();
+  }
+
+  async setLpPoolFees(params: ISetLpPoolFees, signer: Keypair) {
+    const ix = await this.setLpPoolFeesIx(params, signer);
+    return await this.sendTx([ix], [signer]);
+  }
+
+  async setLpPoolFeesIx(
+    {
+      pair,
+      mintFee,
+      burnFee,
+      performanceFee,
+      lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!),
+      upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!),
+    }: ISetLpPoolFees,
+    signer?: Keypair
+  ): Promise<TransactionInstruction> {
+    const admin = signer?.publicKey ?? this.wallet.publicKey;
+    const [lpPool] = this.getLpPoolAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
+
+    return await this.program.methods
+      .setLpPoolFees(mintFee, burnFee, performanceFee)
+      .accounts({
+        state: this.stateAddress,
+        lpPool,
+        admin,
+      })
       .instruction();
   }
+
+  async withdrawLpProtocolFees(
+    params: IWithdrawLpProtocolFees,
+    signer: Keypair
+  ) {
+    const ix = await this.withdrawLpProtocolFeesIx(params, signer);
+    return await this.sendTx([ix], [signer]);
+  }
+
+  async withdrawLpProtocolFeesIx(
+    {
+      pair,
+      lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!),
+      upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!),
+      ...accounts
+    }: IWithdrawLpProtocolFees,
+    signer?: Keypair
+  ): Promise<TransactionInstruction> {
+    const admin = signer?.publicKey ?? this.wallet.publicKey;
+    const [lpPool] = this.getLpPoolAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
+    const reserveX = this.getReserveAddress(pair.tokenX);
+    const reserveY = this.getReserveAddress(pair.tokenY);
+
+    const tokenXProgram =
+      accounts.tokenXProgram ??
+      (await getTokenProgramAddress(this.connection, pair.tokenX));
+    const tokenYProgram =
+      accounts.tokenYProgram ??
+      (await getTokenProgramAddress(this.connection, pair.tokenY));
+
+    return await this.program.methods
+      .withdrawLpProtocolFees()
+      .accounts({
+        state: this.stateAddress,
+        programAuthority: this.programAuthority,
+        lpPool,
+        reserveX,
+        reserveY,
+        tokenX: pair.tokenX,
+        tokenY: pair.tokenY,
+        admin,
+        tokenXProgram,
+        tokenYProgram,
+        ...accounts,
+      })
+      .instruction();
+  }
+
+  async setPaused(paused: boolean, signer: Keypair) {
+    const ix = await this.setPausedIx(paused, signer);
+    return await this.sendTx([ix], [signer]);
+  }
+
+  async setPausedIx(
+    paused: boolean,
+    signer?: Keypair
+  ): Promise<TransactionInstruction> {
+    const admin = signer?.publicKey ?? this.wallet.publicKey;
+    return await this.program.methods
+      .setPaused(paused)
+      .accounts({
+        state: this.stateAddress,
+        admin,
+      })
+      .instruction();
+  }
+
+  async setLpPoolLimits(params: ISetLpPoolLimits, signer: Keypair) {
+    const ix = await this.setLpPoolLimitsIx(params, signer);
+    return await this.sendTx([ix], [signer]);
+  }
+
+  async setLpPoolLimitsIx(
+    {
+      pair,
+      paused,
+      liquidityCap = new BN(0),
+      lowerTickIndex = getMinTick(pair.feeTier.tickSpacing!),
+      upperTickIndex = getMaxTick(pair.feeTier.tickSpacing!),
+    }: ISetLpPoolLimits,
+    signer?: Keypair
+  ): Promise<TransactionInstruction> {
+    const admin = signer?.publicKey ?? this.wallet.publicKey;
+    const [lpPool] = this.getLpPoolAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
+
+    return await this.program.methods
+      .setLpPoolLimits(paused, liquidityCap)
+      .accounts({
+        state: this.stateAddress,
+        lpPool,
+        admin,
+      })
+      .instruction();
+  }
+
+  private async getBurnLpTokenAccounts(
+    pair: Pair,
+    invariant: Market,
+    owner: PublicKey,
+    lowerTickIndex: number,
+    upperTickIndex: number,
+    poolStructure?: PoolStructure,
+    tokenXProgram?: PublicKey,
+    tokenYProgram?: PublicKey
+  ) {
+    const [lpPool] = this.getLpPoolAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
+    const [tokenLp] = this.getLpTokenAddressAndBump(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
+    const reserveX = this.getReserveAddress(pair.tokenX);
+    const reserveY = this.getReserveAddress(pair.tokenY);
+
+    const accountLp = getAssociatedTokenAddressSync(
+      tokenLp,
+      owner,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+
+    const {
+      tokenXReserve: invReserveX,
+      tokenYReserve: invReserveY,
+      tickmap,
+      oracleInitialized,
+      oracleAddress,
+    } = poolStructure ?? (await invariant.getPool(pair));
+
+    // TODO: After Eclipse marketplace sdk update this won't need async at all
+    const pool = await pair.getAddress(
+      new PublicKey(getMarketAddress(this.network))
+    );
+
+    const { positionListAddress: positionList } =
+      await invariant.getPositionListAddress(this.programAuthority);
+
+    const { tickAddress: lowerTick } = await invariant.getTickAddress(
+      pair,
+      lowerTickIndex
+    );
+    const { tickAddress: upperTick } = await invariant.getTickAddress(
+      pair,
+      upperTickIndex
+    );
+
+    return {
+      state: this.stateAddress,
+      programAuthority: this.programAuthority,
+      lpPool,
+      tokenLp,
+      accountLp,
+      owner,
+      pool,
+      oracle: oracleInitialized ? oracleAddress : null,
+      tokenX: pair.tokenX,
+      tokenY: pair.tokenY,
+      reserveX,
+      reserveY,
+      tokenXProgram:
+        tokenXProgram ??
+        (await getTokenProgramAddress(this.connection, pair.tokenX)),
+      tokenYProgram:
+        tokenYProgram ??
+        (await getTokenProgramAddress(this.connection, pair.tokenY)),
+      invProgram: invariant.program.programId,
+      invProgramAuthority: invariant.programAuthority,
+      invState: invariant.stateAddress,
+      lowerTick,
+      upperTick,
+      invReserveX,
+      invReserveY,
+      tickmap,
+      positionList,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+  }
 }
@@ -18,6 +18,26 @@ export interface LpPoolStructure {
   fee: Decimal;
   tokenBump: number;
   bump: number;
+  version: number;
+  initialLowerTickIndex: number;
+  initialUpperTickIndex: number;
+  keeper: PublicKey;
+  rangeWidth: number;
+  rebalanceCooldown: BN;
+  lastRebalanceTimestamp: BN;
+  maxTickDeviation: number;
+  mintFee: Decimal;
+  burnFee: Decimal;
+  feeProtocolTokenX: BN;
+  feeProtocolTokenY: BN;
+  performanceFee: Decimal;
+  paused: boolean;
+  liquidityCap: Decimal;
+  lpTokenDecimals: number;
+  liquidityPerLpToken: BN;
+  lowerTickIndex: number;
+  upperTickIndex: number;
+  twapWindow: number;
 }
 
 export interface ITransaction {
@@ -27,11 +47,31 @@ export interface ITransaction {
 
 export interface IInitLpPool {
   pair: Pair;
+  lowerTickIndex?: number;
+  upperTickIndex?: number;
+  lpTokenDecimals?: number;
+  liquidityPerLpToken?: BN;
   pool?: PublicKey;
+  // Metaplex metadata the symbols of legacy token mints are read from
+  tokenXMetadata?: PublicKey | null;
+  tokenYMetadata?: PublicKey | null;
   tokenXProgram?: PublicKey;
   tokenYProgram?: PublicKey;
 }
 
+export interface ISetLpTokenUri {
+  pair: Pair;
+  lowerTickIndex?: number;
+  upperTickIndex?: number;
+  uri: string;
+}
+
+export interface IMigrateLpPool {
+  pair: Pair;
+  lowerTickIndex?: number;
+  upperTickIndex?: number;
+}
+
 export interface IMintLpToken {
   // data
   pair: Pair;
@@ -39,6 +79,50 @@ export interface IMintLpToken {
   poolStructure?: PoolStructure;
   // params
   liquidityDelta: BN;
+  maxAmountX?: BN;
+  maxAmountY?: BN;
+  lowerTickIndex?: number;
+  upperTickIndex?: number;
+  // invariant accounts
+  position: PublicKey;
+  accountX: PublicKey;
+  accountY: PublicKey;
+  tokenXProgram?: PublicKey;
+  tokenYProgram?: PublicKey;
+}
+
+export interface IMintLpTokenByAmounts {
+  // data
+  pair: Pair;
+  invariant: Market;
+  poolStructure?: PoolStructure;
+  // params
+  amountX: BN;
+  amountY: BN;
+  lowerTickIndex?: number;
+  upperTickIndex?: number;
+  // invariant accounts
+  position: PublicKey;
+  accountX: PublicKey;
+  accountY: PublicKey;
+  tokenXProgram?: PublicKey;
+  tokenYProgram?: PublicKey;
+}
+
+export interface IZapIn {
+  // data
+  pair: Pair;
+  invariant: Market;
+  poolStructure?: PoolStructure;
+  // params
+  xToY: boolean;
+  amount: BN;
+  sqrtPriceLimit?: BN;
+  minLpTokenOut?: BN;
+  lowerTickIndex?: number;
+  upperTickIndex?: number;
+  // ticks the swap of the part of the amount can cross
+  crossedTicks?: PublicKey[];
   // invariant accounts
   position: PublicKey;
   accountX: PublicKey;
@@ -54,6 +138,49 @@ export interface IBurnLpToken {
   poolStructure?: PoolStructure;
   // params
   liquidityDelta: BN;
+  minAmountX?: BN;
+  minAmountY?: BN;
+  lowerTickIndex?: number;
+  upperTickIndex?: number;
+  // fullrange accounts
+  lastPositionLpPool: PublicKey;
+  // invariant accounts
+  position: PublicKey;
+  lastPosition: PublicKey;
+  accountX: PublicKey;
+  accountY: PublicKey;
+  tokenXProgram?: PublicKey;
+  tokenYProgram?: PublicKey;
+}
+
+export interface ICompound {
+  // data
+  pair: Pair;
+  invariant: Market;
+  poolStructure?: PoolStructure;
+  // params
+  lowerTickIndex?: number;
+  upperTickIndex?: number;
+  // invariant accounts
+  position: PublicKey;
+  tokenXProgram?: PublicKey;
+  tokenYProgram?: PublicKey;
+}
+
+export interface IZapOut {
+  // data
+  pair: Pair;
+  invariant: Market;
+  poolStructure?: PoolStructure;
+  // params
+  liquidityDelta: BN;
+  xToY: boolean;
+  sqrtPriceLimit?: BN;
+  minAmountOut?: BN;
+  lowerTickIndex?: number;
+  upperTickIndex?: number;
+  // ticks the swap of the withdrawn amount can cross
+  crossedTicks?: PublicKey[];
   // fullrange accounts
   lastPositionLpPool: PublicKey;
   // invariant accounts
@@ -64,3 +191,67 @@ export interface IBurnLpToken {
   tokenXProgram?: PublicKey;
   tokenYProgram?: PublicKey;
 }
+
+export interface ISetRebalanceConfig {
+  pair: Pair;
+  lowerTickIndex?: number;
+  upperTickIndex?: number;
+  // default public key turns the lp pool back into a static one
+  keeper: PublicKey;
+  rangeWidth: number;
+  rebalanceCooldown: BN;
+  maxTickDeviation: number;
+  twapWindow: number;
+}
+
+export interface IRebalance {
+  // data
+  pair: Pair;
+  invariant: Market;
+  poolStructure?: PoolStructure;
+  // params
+  lowerTickIndex?: number;
+  upperTickIndex?: number;
+  // range computed on chain from the oracle, see computeRebalanceRange
+  newLowerTickIndex: number;
+  newUpperTickIndex: number;
+  // ticks the swap of the excess token can cross
+  crossedTicks?: PublicKey[];
+  // fullrange accounts
+  lastPositionLpPool: PublicKey;
+  // invariant accounts
+  position: PublicKey;
+  lastPosition: PublicKey;
+  newPosition: PublicKey;
+  tokenXProgram?: PublicKey;
+  tokenYProgram?: PublicKey;
+}
+
+export interface ISetLpPoolFees {
+  pair: Pair;
+  lowerTickIndex?: number;
+  upperTickIndex?: number;
+  mintFee: BN;
+  burnFee: BN;
+  performanceFee: BN;
+}
+
+export interface IWithdrawLpProtocolFees {
+  pair: Pair;
+  lowerTickIndex?: number;
+  upperTickIndex?: number;
+  // admin accounts receiving the fees
+  accountX: PublicKey;
+  accountY: PublicKey;
+  tokenXProgram?: PublicKey;
+  tokenYProgram?: PublicKey;
+}
+
+export interface ISetLpPoolLimits {
+  pair: Pair;
+  lowerTickIndex?: number;
+  upperTickIndex?: number;
+  paused: boolean;
+  // zero means no cap
+  liquidityCap?: BN;
+}
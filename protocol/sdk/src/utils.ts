@@ -5,10 +5,12 @@ import {
   ConfirmOptions,
   Connection,
   Keypair,
+  PublicKey,
   Transaction,
   TransactionInstruction,
   TransactionSignature,
 } from "@solana/web3.js";
+import { getMaxTick, getMinTick } from "@invariant-labs/sdk-eclipse/lib/utils";
 
 export const signAndSend = async (
   tx: Transaction,
@@ -57,3 +59,42 @@ export const bigNumberToBuffer = (n: BN, size: 16 | 32 | 64 | 128 | 256) => {
 
   return buffer;
 };
+
+export const tickIndexToBuffer = (tickIndex: number) => {
+  const buffer = Buffer.alloc(4);
+  buffer.writeInt32LE(tickIndex);
+  return buffer;
+};
+
+// full range pools keep the address they had before custom ranges were supported
+export const rangeSeed = (
+  tickSpacing: number,
+  lowerTickIndex: number,
+  upperTickIndex: number
+) => {
+  if (
+    lowerTickIndex === getMinTick(tickSpacing) &&
+    upperTickIndex === getMaxTick(tickSpacing)
+  ) {
+    return Buffer.alloc(0);
+  }
+  return Buffer.concat([
+    tickIndexToBuffer(lowerTickIndex),
+    tickIndexToBuffer(upperTickIndex),
+  ]);
+};
+
+export const METAPLEX_METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
+// metadata account of a legacy token mint, used for the symbols of LP tokens
+export const getMetaplexMetadataAddress = (mint: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      METAPLEX_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+    ],
+    METAPLEX_METADATA_PROGRAM_ID
+  )[0];
@@ -4,6 +4,7 @@ set -e
 e2e_tests=(
     "math"
     "init"
+    "admin"
     "init-lp-pool"
     "burn"
     "mint-high-tick-big"
@@ -11,6 +12,14 @@ e2e_tests=(
     "mint-zero-tick-big"
     "mint-zero-tick-small"
     "multi-pool"
+    "mint-by-amounts"
+    "zap-in"
+    "zap-out"
+    "compound"
+    "rebalance"
+    "lp-pool-fees"
+    "pause"
+    "events"
 )
 
 # currenty, there are no unit tests 
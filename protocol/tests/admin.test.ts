@@ -0,0 +1,73 @@
+import { AnchorProvider } from "@coral-xyz/anchor";
+import { Network } from "../sdk/src/network";
+import { Protocol } from "../sdk/src/protocol";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { INVARIANT_ADDRESS, requestAirdrop } from "./test-utils";
+import { Market } from "@invariant-labs/sdk-eclipse";
+import { assert } from "chai";
+
+describe("admin", () => {
+  const { wallet: walletAnchor, connection } = AnchorProvider.local();
+  const owner = Keypair.generate();
+  const newAdmin = Keypair.generate();
+
+  let protocol: Protocol;
+
+  before(async () => {
+    await requestAirdrop(connection, owner.publicKey, 1e14);
+    await requestAirdrop(connection, newAdmin.publicKey, 1e14);
+
+    protocol = await Protocol.build(Network.LOCAL, walletAnchor, connection);
+    const market = await Market.build(
+      Network.LOCAL,
+      walletAnchor,
+      connection,
+      INVARIANT_ADDRESS
+    );
+    await protocol.init(owner, market);
+  });
+
+  it("migrate state", async () => {
+    let err = false;
+    try {
+      await protocol.migrateState(owner);
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "migrated a state of the current layout");
+  });
+
+  it("transfer admin", async () => {
+    let err = false;
+    try {
+      await protocol.proposeAdmin(newAdmin.publicKey, newAdmin);
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "non-admin proposed an admin");
+
+    await protocol.proposeAdmin(newAdmin.publicKey, owner);
+    {
+      const state = await protocol.program.account.state.fetch(
+        protocol.stateAddress
+      );
+      assert.ok(state.admin.equals(owner.publicKey));
+      assert.ok(state.pendingAdmin.equals(newAdmin.publicKey));
+    }
+
+    err = false;
+    try {
+      await protocol.acceptAdmin(owner);
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "admin accepted in place of the proposed one");
+
+    await protocol.acceptAdmin(newAdmin);
+    const state = await protocol.program.account.state.fetch(
+      protocol.stateAddress
+    );
+    assert.ok(state.admin.equals(newAdmin.publicKey));
+    assert.ok(state.pendingAdmin.equals(PublicKey.default));
+  });
+});
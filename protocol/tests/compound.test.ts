@@ -0,0 +1,243 @@
+import { AnchorProvider, BN } from "@coral-xyz/anchor";
+import { Network } from "../sdk/src/network";
+import { Protocol } from "../sdk/src/protocol";
+import { Keypair } from "@solana/web3.js";
+import {
+  createTokenMint,
+  initMarket,
+  INVARIANT_ADDRESS,
+  requestAirdrop,
+} from "./test-utils";
+import { assert } from "chai";
+import {
+  getAssociatedTokenAddressSync,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Pair } from "@invariant-labs/sdk-eclipse";
+import {
+  fromFee,
+  getMaxTick,
+  getMinTick,
+  toDecimal,
+} from "@invariant-labs/sdk-eclipse/lib/utils";
+import {
+  CreateTick,
+  FeeTier,
+  Market,
+} from "@invariant-labs/sdk-eclipse/lib/market";
+
+describe("compound", () => {
+  const { wallet: walletAnchor, connection } = AnchorProvider.local();
+  const owner = Keypair.generate();
+  const mintAuthority = Keypair.generate();
+
+  let protocol: Protocol;
+  let market: Market;
+  const feeTier: FeeTier = {
+    fee: fromFee(new BN(600)),
+    tickSpacing: 10,
+  };
+  let pair: Pair;
+  const lowerTick = getMinTick(feeTier.tickSpacing!);
+  const upperTick = getMaxTick(feeTier.tickSpacing!);
+  const initTick = 0;
+  const ownerAmount = 1e10;
+
+  before(async () => {
+    let giveSOL = [owner.publicKey, mintAuthority.publicKey];
+    await Promise.all(
+      giveSOL.map((account) => requestAirdrop(connection, account, 1e14))
+    );
+
+    market = await Market.build(
+      Network.LOCAL,
+      walletAnchor,
+      connection,
+      INVARIANT_ADDRESS
+    );
+
+    const [token0, token1] = await Promise.all([
+      createTokenMint(connection, owner, mintAuthority.publicKey, 6),
+      createTokenMint(connection, owner, mintAuthority.publicKey, 6),
+    ]);
+    pair = new Pair(token0, token1, feeTier);
+
+    await initMarket(market, [pair], owner, initTick);
+
+    protocol = await Protocol.build(Network.LOCAL, walletAnchor, connection);
+    await protocol.init(owner, market);
+
+    const lowerTickVars: CreateTick = {
+      pair,
+      index: lowerTick,
+      payer: owner.publicKey,
+    };
+    const upperTickVars: CreateTick = {
+      pair,
+      index: upperTick,
+      payer: owner.publicKey,
+    };
+    await market.createTick(lowerTickVars, owner);
+    await market.createTick(upperTickVars, owner);
+
+    const userTokenXAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      owner,
+      pair.tokenX,
+      owner.publicKey
+    );
+    const userTokenYAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      owner,
+      pair.tokenY,
+      owner.publicKey
+    );
+    await mintTo(
+      connection,
+      owner,
+      pair.tokenX,
+      userTokenXAccount.address,
+      mintAuthority,
+      ownerAmount
+    );
+    await mintTo(
+      connection,
+      owner,
+      pair.tokenY,
+      userTokenYAccount.address,
+      mintAuthority,
+      ownerAmount
+    );
+
+    await protocol.initLpPool({ pair }, owner);
+  });
+
+  it("test", async () => {
+    const userTokenXAccountAddress = getAssociatedTokenAddressSync(
+      pair.tokenX,
+      owner.publicKey
+    );
+    const userTokenYAccountAddress = getAssociatedTokenAddressSync(
+      pair.tokenY,
+      owner.publicKey
+    );
+    const { positionAddress } = await market.getPositionAddress(
+      protocol.programAuthority,
+      0
+    );
+    const mintParams = {
+      pair,
+      invariant: market,
+
+      position: positionAddress,
+      accountX: userTokenXAccountAddress,
+      accountY: userTokenYAccountAddress,
+    };
+
+    await protocol.mintLpTokenByAmounts(
+      { ...mintParams, amountX: new BN(1e8), amountY: new BN(1e8) },
+      owner
+    );
+
+    let err = false;
+    try {
+      await protocol.compound(
+        { pair, invariant: market, position: positionAddress },
+        owner
+      );
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "compounded without any fees");
+
+    // swaps of the zaps accrue fees in both tokens to the position
+    await protocol.zapIn(
+      { ...mintParams, xToY: true, amount: new BN(1e7) },
+      owner
+    );
+    await protocol.zapIn(
+      { ...mintParams, xToY: false, amount: new BN(1e7) },
+      owner
+    );
+
+    const liquidityBefore = (
+      await market.getPosition(protocol.programAuthority, 0)
+    ).liquidity.v;
+    const [tokenLp] = protocol.getLpTokenAddressAndBump(pair);
+    const lpSupplyBefore = (
+      await getMint(connection, tokenLp, undefined, TOKEN_2022_PROGRAM_ID)
+    ).supply;
+
+    await protocol.compound(
+      { pair, invariant: market, position: positionAddress },
+      owner
+    );
+
+    const positionAfter = await market.getPosition(
+      protocol.programAuthority,
+      0
+    );
+    assert.ok(positionAfter.liquidity.v.gt(liquidityBefore));
+    // compounding doesn't mint lp tokens
+    assert.equal(
+      (await getMint(connection, tokenLp, undefined, TOKEN_2022_PROGRAM_ID))
+        .supply,
+      lpSupplyBefore
+    );
+  });
+
+  it("compounds fees of swaps made directly on the market", async () => {
+    const accountX = getAssociatedTokenAddressSync(
+      pair.tokenX,
+      owner.publicKey
+    );
+    const accountY = getAssociatedTokenAddressSync(
+      pair.tokenY,
+      owner.publicKey
+    );
+    const { positionAddress } = await market.getPositionAddress(
+      protocol.programAuthority,
+      0
+    );
+
+    // the position isn't touched, so its tokens owed stay at zero
+    for (const xToY of [true, false]) {
+      const { sqrtPrice } = await market.getPool(pair);
+      await market.swap(
+        {
+          pair,
+          owner: owner.publicKey,
+          xToY,
+          amount: new BN(1e6),
+          estimatedPriceAfterSwap: { v: sqrtPrice.v },
+          slippage: toDecimal(1, 1),
+          accountX,
+          accountY,
+          byAmountIn: true,
+        },
+        owner
+      );
+    }
+
+    const positionBefore = await market.getPosition(
+      protocol.programAuthority,
+      0
+    );
+    assert.equal(positionBefore.tokensOwedX.v.toString(), "0");
+    assert.equal(positionBefore.tokensOwedY.v.toString(), "0");
+
+    await protocol.compound(
+      { pair, invariant: market, position: positionAddress },
+      owner
+    );
+
+    const positionAfter = await market.getPosition(
+      protocol.programAuthority,
+      0
+    );
+    assert.ok(positionAfter.liquidity.v.gt(positionBefore.liquidity.v));
+  });
+});
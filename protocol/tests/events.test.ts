@@ -0,0 +1,204 @@
+import { AnchorProvider, BN, IdlEvents } from "@coral-xyz/anchor";
+import { Network } from "../sdk/src/network";
+import { Protocol } from "../sdk/src/protocol";
+import { Protocol as ProtocolProgram } from "../sdk/src/idl/protocol";
+import { Keypair } from "@solana/web3.js";
+import {
+  createTokenMint,
+  initMarket,
+  INVARIANT_ADDRESS,
+  requestAirdrop,
+} from "./test-utils";
+import { assert } from "chai";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Pair } from "@invariant-labs/sdk-eclipse";
+import {
+  fromFee,
+  getMaxTick,
+  getMinTick,
+} from "@invariant-labs/sdk-eclipse/lib/utils";
+import {
+  CreateTick,
+  FeeTier,
+  Market,
+} from "@invariant-labs/sdk-eclipse/lib/market";
+
+describe("events", () => {
+  const { wallet: walletAnchor, connection } = AnchorProvider.local();
+  const owner = Keypair.generate();
+  const mintAuthority = Keypair.generate();
+
+  let protocol: Protocol;
+  let market: Market;
+  const feeTier: FeeTier = {
+    fee: fromFee(new BN(600)),
+    tickSpacing: 10,
+  };
+  let pair: Pair;
+  const lowerTick = getMinTick(feeTier.tickSpacing!);
+  const upperTick = getMaxTick(feeTier.tickSpacing!);
+  const initTick = 0;
+  const ownerAmount = 1e10;
+
+  before(async () => {
+    let giveSOL = [owner.publicKey, mintAuthority.publicKey];
+    await Promise.all(
+      giveSOL.map((account) => requestAirdrop(connection, account, 1e14))
+    );
+
+    market = await Market.build(
+      Network.LOCAL,
+      walletAnchor,
+      connection,
+      INVARIANT_ADDRESS
+    );
+
+    const [token0, token1] = await Promise.all([
+      createTokenMint(connection, owner, mintAuthority.publicKey, 6),
+      createTokenMint(connection, owner, mintAuthority.publicKey, 6),
+    ]);
+    pair = new Pair(token0, token1, feeTier);
+
+    await initMarket(market, [pair], owner, initTick);
+
+    protocol = await Protocol.build(Network.LOCAL, walletAnchor, connection);
+    await protocol.init(owner, market);
+
+    const lowerTickVars: CreateTick = {
+      pair,
+      index: lowerTick,
+      payer: owner.publicKey,
+    };
+    const upperTickVars: CreateTick = {
+      pair,
+      index: upperTick,
+      payer: owner.publicKey,
+    };
+    await market.createTick(lowerTickVars, owner);
+    await market.createTick(upperTickVars, owner);
+
+    const userTokenXAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      owner,
+      pair.tokenX,
+      owner.publicKey
+    );
+    const userTokenYAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      owner,
+      pair.tokenY,
+      owner.publicKey
+    );
+    await mintTo(
+      connection,
+      owner,
+      pair.tokenX,
+      userTokenXAccount.address,
+      mintAuthority,
+      ownerAmount
+    );
+    await mintTo(
+      connection,
+      owner,
+      pair.tokenY,
+      userTokenYAccount.address,
+      mintAuthority,
+      ownerAmount
+    );
+
+    await protocol.initLpPool({ pair }, owner);
+  });
+
+  it("test", async () => {
+    const userTokenXAccountAddress = getAssociatedTokenAddressSync(
+      pair.tokenX,
+      owner.publicKey
+    );
+    const userTokenYAccountAddress = getAssociatedTokenAddressSync(
+      pair.tokenY,
+      owner.publicKey
+    );
+    const { positionAddress } = await market.getPositionAddress(
+      protocol.programAuthority,
+      0
+    );
+    const [lpPoolAddress] = protocol.getLpPoolAddressAndBump(pair);
+    const [tokenLp] = protocol.getLpTokenAddressAndBump(pair);
+    const getLpAmount = async () => {
+      return (
+        await getAccount(
+          connection,
+          getAssociatedTokenAddressSync(
+            tokenLp,
+            owner.publicKey,
+            undefined,
+            TOKEN_2022_PROGRAM_ID
+          ),
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        )
+      ).amount;
+    };
+
+    const liquidityDelta = new BN(50000000);
+    const mintSignature = await protocol.mintLpToken(
+      {
+        pair,
+        invariant: market,
+
+        liquidityDelta,
+
+        position: positionAddress,
+        accountX: userTokenXAccountAddress,
+        accountY: userTokenYAccountAddress,
+      },
+      owner
+    );
+    await connection.confirmTransaction(mintSignature, "confirmed");
+    const mintEvents = await protocol.getEvents(mintSignature);
+    assert.equal(mintEvents.length, 1);
+    assert.equal(mintEvents[0].name, "MintLpTokenEvent");
+    const mintEvent = mintEvents[0]
+      .data as IdlEvents<ProtocolProgram>["MintLpTokenEvent"];
+    assert.ok(mintEvent.lpPool.equals(lpPoolAddress));
+    assert.ok(mintEvent.owner.equals(owner.publicKey));
+    assert.equal(
+      mintEvent.lpTokenAmount.toString(),
+      (await getLpAmount()).toString()
+    );
+
+    const position = await market.getPosition(protocol.programAuthority, 0);
+    const burnSignature = await protocol.burnLpToken(
+      {
+        pair,
+        invariant: market,
+
+        liquidityDelta: position.liquidity.v,
+
+        lastPositionLpPool: lpPoolAddress,
+
+        position: positionAddress,
+        lastPosition: positionAddress,
+        accountX: userTokenXAccountAddress,
+        accountY: userTokenYAccountAddress,
+      },
+      owner
+    );
+    await connection.confirmTransaction(burnSignature, "confirmed");
+    const burnEvents = await protocol.getEvents(burnSignature);
+    assert.equal(burnEvents.length, 1);
+    assert.equal(burnEvents[0].name, "BurnLpTokenEvent");
+    const burnEvent = burnEvents[0]
+      .data as IdlEvents<ProtocolProgram>["BurnLpTokenEvent"];
+    assert.ok(burnEvent.lpPool.equals(lpPoolAddress));
+    assert.ok(burnEvent.owner.equals(owner.publicKey));
+    assert.ok(burnEvent.liquidity.eq(position.liquidity.v));
+    assert.ok(burnEvent.lpTokenAmount.eq(mintEvent.lpTokenAmount));
+  });
+});
@@ -16,7 +16,9 @@ import { assert } from "chai";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   getAccount,
+  getMint,
   getOrCreateAssociatedTokenAccount,
+  getTokenMetadata,
   TOKEN_2022_PROGRAM_ID,
 } from "@solana/spl-token";
 
@@ -102,4 +104,117 @@ describe("init lp-pool", () => {
       assert.equal(lpTokenAccountInfo.amount, 0n);
     }
   });
+
+  it("init custom range", async () => {
+    const lowerTickIndex = -100;
+    const upperTickIndex = 200;
+
+    let err = false;
+    try {
+      await protocol.initLpPool(
+        {
+          pair,
+          lowerTickIndex: upperTickIndex,
+          upperTickIndex: lowerTickIndex,
+        },
+        owner
+      );
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "initialized lp pool with an inverted range");
+
+    err = false;
+    try {
+      await protocol.initLpPool(
+        { pair, lowerTickIndex: lowerTickIndex + 5, upperTickIndex },
+        owner
+      );
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "initialized lp pool with a range not aligned to tick spacing");
+
+    await protocol.initLpPool({ pair, lowerTickIndex, upperTickIndex }, owner);
+    const lpPool: LpPoolStructure = await protocol.getLpPool(
+      pair,
+      lowerTickIndex,
+      upperTickIndex
+    );
+
+    assert.equal(lpPool.lowerTickIndex, lowerTickIndex);
+    assert.equal(lpPool.upperTickIndex, upperTickIndex);
+    assert.equal(lpPool.initialLowerTickIndex, lowerTickIndex);
+    assert.equal(lpPool.initialUpperTickIndex, upperTickIndex);
+
+    // ranges of the same pair have their own lp pools
+    await protocol.initLpPool({ pair }, owner);
+    const fullRangeLpPool: LpPoolStructure = await protocol.getLpPool(pair);
+    assert.notEqual(fullRangeLpPool.lowerTickIndex, lowerTickIndex);
+  });
+
+  it("set lp token uri", async () => {
+    await protocol.initLpPool({ pair }, owner);
+    const [lpToken] = protocol.getLpTokenAddressAndBump(pair);
+    const uri = "https://invariant.app/lp-token.json";
+
+    let err = false;
+    try {
+      await protocol.setLpTokenUri({ pair, uri }, wallet);
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "non-admin set the lp token uri");
+
+    await protocol.setLpTokenUri({ pair, uri }, owner);
+    const metadata = await getTokenMetadata(
+      connection,
+      lpToken,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assert.ok(metadata);
+    assert.equal(metadata!.uri, uri);
+  });
+
+  it("init lp token config", async () => {
+    let err = false;
+    try {
+      await protocol.initLpPool({ pair, lpTokenDecimals: 10 }, owner);
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "initialized lp pool with too many lp token decimals");
+
+    err = false;
+    try {
+      await protocol.initLpPool(
+        { pair, liquidityPerLpToken: new BN(0) },
+        owner
+      );
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "initialized lp pool with zero liquidity per lp token");
+
+    const lpTokenDecimals = 9;
+    const liquidityPerLpToken = new BN(1000);
+    await protocol.initLpPool(
+      { pair, lpTokenDecimals, liquidityPerLpToken },
+      owner
+    );
+
+    const lpPool: LpPoolStructure = await protocol.getLpPool(pair);
+    assert.equal(lpPool.lpTokenDecimals, lpTokenDecimals);
+    assert.ok(lpPool.liquidityPerLpToken.eq(liquidityPerLpToken));
+
+    const [lpToken] = protocol.getLpTokenAddressAndBump(pair);
+    const mint = await getMint(
+      connection,
+      lpToken,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assert.equal(mint.decimals, lpTokenDecimals);
+  });
 });
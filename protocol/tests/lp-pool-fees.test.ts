@@ -0,0 +1,269 @@
+import { AnchorProvider, BN } from "@coral-xyz/anchor";
+import { Network } from "../sdk/src/network";
+import { Protocol } from "../sdk/src/protocol";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  createTokenMint,
+  initMarket,
+  INVARIANT_ADDRESS,
+  requestAirdrop,
+} from "./test-utils";
+import { assert } from "chai";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { Pair } from "@invariant-labs/sdk-eclipse";
+import {
+  fromFee,
+  getMaxTick,
+  getMinTick,
+  toDecimal,
+} from "@invariant-labs/sdk-eclipse/lib/utils";
+import {
+  CreateTick,
+  FeeTier,
+  Market,
+} from "@invariant-labs/sdk-eclipse/lib/market";
+
+describe("lp pool fees", () => {
+  const { wallet: walletAnchor, connection } = AnchorProvider.local();
+  const owner = Keypair.generate();
+  const mintAuthority = Keypair.generate();
+
+  let protocol: Protocol;
+  let market: Market;
+  const feeTier: FeeTier = {
+    fee: fromFee(new BN(600)),
+    tickSpacing: 10,
+  };
+  let pair: Pair;
+  const lowerTick = getMinTick(feeTier.tickSpacing!);
+  const upperTick = getMaxTick(feeTier.tickSpacing!);
+  const initTick = 0;
+  const ownerAmount = 1e10;
+
+  before(async () => {
+    let giveSOL = [owner.publicKey, mintAuthority.publicKey];
+    await Promise.all(
+      giveSOL.map((account) => requestAirdrop(connection, account, 1e14))
+    );
+
+    market = await Market.build(
+      Network.LOCAL,
+      walletAnchor,
+      connection,
+      INVARIANT_ADDRESS
+    );
+
+    const [token0, token1] = await Promise.all([
+      createTokenMint(connection, owner, mintAuthority.publicKey, 6),
+      createTokenMint(connection, owner, mintAuthority.publicKey, 6),
+    ]);
+    pair = new Pair(token0, token1, feeTier);
+
+    await initMarket(market, [pair], owner, initTick);
+
+    protocol = await Protocol.build(Network.LOCAL, walletAnchor, connection);
+    await protocol.init(owner, market);
+
+    const lowerTickVars: CreateTick = {
+      pair,
+      index: lowerTick,
+      payer: owner.publicKey,
+    };
+    const upperTickVars: CreateTick = {
+      pair,
+      index: upperTick,
+      payer: owner.publicKey,
+    };
+    await market.createTick(lowerTickVars, owner);
+    await market.createTick(upperTickVars, owner);
+
+    const userTokenXAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      owner,
+      pair.tokenX,
+      owner.publicKey
+    );
+    const userTokenYAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      owner,
+      pair.tokenY,
+      owner.publicKey
+    );
+    await mintTo(
+      connection,
+      owner,
+      pair.tokenX,
+      userTokenXAccount.address,
+      mintAuthority,
+      ownerAmount
+    );
+    await mintTo(
+      connection,
+      owner,
+      pair.tokenY,
+      userTokenYAccount.address,
+      mintAuthority,
+      ownerAmount
+    );
+
+    await protocol.initLpPool({ pair }, owner);
+  });
+
+  it("test", async () => {
+    const userTokenXAccountAddress = getAssociatedTokenAddressSync(
+      pair.tokenX,
+      owner.publicKey
+    );
+    const userTokenYAccountAddress = getAssociatedTokenAddressSync(
+      pair.tokenY,
+      owner.publicKey
+    );
+    const { positionAddress } = await market.getPositionAddress(
+      protocol.programAuthority,
+      0
+    );
+    const getTokenAccount = async (tokenAccount: PublicKey) => {
+      return await getAccount(connection, tokenAccount, undefined);
+    };
+    const fees = {
+      pair,
+      mintFee: fromFee(new BN(1000)),
+      burnFee: new BN(0),
+      performanceFee: new BN(0),
+    };
+
+    let err = false;
+    try {
+      await protocol.setLpPoolFees(
+        { ...fees, mintFee: new BN(10).pow(new BN(12)) },
+        owner
+      );
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "set a mint fee taking the whole deposit");
+
+    err = false;
+    try {
+      await protocol.setLpPoolFees(
+        { ...fees, performanceFee: fromFee(new BN(50000)) },
+        owner
+      );
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "set a performance fee above the maximum");
+
+    await protocol.setLpPoolFees(fees, owner);
+    {
+      const lpPool = await protocol.getLpPool(pair);
+      assert.ok(lpPool.mintFee.v.eq(fees.mintFee));
+      assert.ok(lpPool.burnFee.v.eqn(0));
+    }
+
+    await protocol.mintLpTokenByAmounts(
+      {
+        pair,
+        invariant: market,
+
+        amountX: new BN(1e8),
+        amountY: new BN(1e8),
+
+        position: positionAddress,
+        accountX: userTokenXAccountAddress,
+        accountY: userTokenYAccountAddress,
+      },
+      owner
+    );
+
+    const lpPool = await protocol.getLpPool(pair);
+    assert.ok(lpPool.feeProtocolTokenX.gtn(0));
+    assert.ok(lpPool.feeProtocolTokenY.gtn(0));
+
+    const balanceXBefore = (await getTokenAccount(userTokenXAccountAddress))
+      .amount;
+    const balanceYBefore = (await getTokenAccount(userTokenYAccountAddress))
+      .amount;
+    await protocol.withdrawLpProtocolFees(
+      {
+        pair,
+        accountX: userTokenXAccountAddress,
+        accountY: userTokenYAccountAddress,
+      },
+      owner
+    );
+
+    const balanceXAfter = (await getTokenAccount(userTokenXAccountAddress))
+      .amount;
+    const balanceYAfter = (await getTokenAccount(userTokenYAccountAddress))
+      .amount;
+    assert.equal(
+      (balanceXAfter - balanceXBefore).toString(),
+      lpPool.feeProtocolTokenX.toString()
+    );
+    assert.equal(
+      (balanceYAfter - balanceYBefore).toString(),
+      lpPool.feeProtocolTokenY.toString()
+    );
+    const lpPoolAfter = await protocol.getLpPool(pair);
+    assert.ok(lpPoolAfter.feeProtocolTokenX.eqn(0));
+    assert.ok(lpPoolAfter.feeProtocolTokenY.eqn(0));
+  });
+
+  it("performance fee is charged on the claimed fee", async () => {
+    const accountX = getAssociatedTokenAddressSync(
+      pair.tokenX,
+      owner.publicKey
+    );
+    const accountY = getAssociatedTokenAddressSync(
+      pair.tokenY,
+      owner.publicKey
+    );
+    const { positionAddress } = await market.getPositionAddress(
+      protocol.programAuthority,
+      0
+    );
+    await protocol.setLpPoolFees(
+      {
+        pair,
+        mintFee: new BN(0),
+        burnFee: new BN(0),
+        performanceFee: fromFee(new BN(10000)),
+      },
+      owner
+    );
+
+    // swapping on the market doesn't touch the position's tokens owed
+    for (const xToY of [true, false]) {
+      const { sqrtPrice } = await market.getPool(pair);
+      await market.swap(
+        {
+          pair,
+          owner: owner.publicKey,
+          xToY,
+          amount: new BN(1e7),
+          estimatedPriceAfterSwap: { v: sqrtPrice.v },
+          slippage: toDecimal(1, 1),
+          accountX,
+          accountY,
+          byAmountIn: true,
+        },
+        owner
+      );
+    }
+
+    await protocol.compound(
+      { pair, invariant: market, position: positionAddress },
+      owner
+    );
+
+    const lpPool = await protocol.getLpPool(pair);
+    assert.ok(lpPool.feeProtocolTokenX.gtn(0));
+    assert.ok(lpPool.feeProtocolTokenY.gtn(0));
+  });
+});
@@ -0,0 +1,197 @@
+import { AnchorProvider, BN } from "@coral-xyz/anchor";
+import { Network } from "../sdk/src/network";
+import { Protocol } from "../sdk/src/protocol";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  createTokenMint,
+  initMarket,
+  INVARIANT_ADDRESS,
+  requestAirdrop,
+} from "./test-utils";
+import { assert } from "chai";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Pair } from "@invariant-labs/sdk-eclipse";
+import {
+  fromFee,
+  getMaxTick,
+  getMinTick,
+} from "@invariant-labs/sdk-eclipse/lib/utils";
+import {
+  CreateTick,
+  FeeTier,
+  Market,
+} from "@invariant-labs/sdk-eclipse/lib/market";
+
+describe("mint lp token by amounts", () => {
+  const { wallet: walletAnchor, connection } = AnchorProvider.local();
+  const owner = Keypair.generate();
+  const mintAuthority = Keypair.generate();
+
+  let protocol: Protocol;
+  let market: Market;
+  const feeTier: FeeTier = {
+    fee: fromFee(new BN(600)),
+    tickSpacing: 10,
+  };
+  let pair: Pair;
+  const lowerTick = getMinTick(feeTier.tickSpacing!);
+  const upperTick = getMaxTick(feeTier.tickSpacing!);
+  const initTick = 0;
+  const ownerAmount = 1e10;
+
+  before(async () => {
+    let giveSOL = [owner.publicKey, mintAuthority.publicKey];
+    await Promise.all(
+      giveSOL.map((account) => requestAirdrop(connection, account, 1e14))
+    );
+
+    market = await Market.build(
+      Network.LOCAL,
+      walletAnchor,
+      connection,
+      INVARIANT_ADDRESS
+    );
+
+    const [token0, token1] = await Promise.all([
+      createTokenMint(connection, owner, mintAuthority.publicKey, 6),
+      createTokenMint(connection, owner, mintAuthority.publicKey, 6),
+    ]);
+    pair = new Pair(token0, token1, feeTier);
+
+    await initMarket(market, [pair], owner, initTick);
+
+    protocol = await Protocol.build(Network.LOCAL, walletAnchor, connection);
+    await protocol.init(owner, market);
+
+    const lowerTickVars: CreateTick = {
+      pair,
+      index: lowerTick,
+      payer: owner.publicKey,
+    };
+    const upperTickVars: CreateTick = {
+      pair,
+      index: upperTick,
+      payer: owner.publicKey,
+    };
+    await market.createTick(lowerTickVars, owner);
+    await market.createTick(upperTickVars, owner);
+
+    const userTokenXAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      owner,
+      pair.tokenX,
+      owner.publicKey
+    );
+    const userTokenYAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      owner,
+      pair.tokenY,
+      owner.publicKey
+    );
+    await mintTo(
+      connection,
+      owner,
+      pair.tokenX,
+      userTokenXAccount.address,
+      mintAuthority,
+      ownerAmount
+    );
+    await mintTo(
+      connection,
+      owner,
+      pair.tokenY,
+      userTokenYAccount.address,
+      mintAuthority,
+      ownerAmount
+    );
+
+    await protocol.initLpPool({ pair }, owner);
+  });
+
+  it("test", async () => {
+    const userTokenXAccountAddress = getAssociatedTokenAddressSync(
+      pair.tokenX,
+      owner.publicKey
+    );
+    const userTokenYAccountAddress = getAssociatedTokenAddressSync(
+      pair.tokenY,
+      owner.publicKey
+    );
+    const { positionAddress } = await market.getPositionAddress(
+      protocol.programAuthority,
+      0
+    );
+
+    let err = false;
+    try {
+      await protocol.mintLpTokenByAmounts(
+        {
+          pair,
+          invariant: market,
+
+          amountX: new BN(0),
+          amountY: new BN(0),
+
+          position: positionAddress,
+          accountX: userTokenXAccountAddress,
+          accountY: userTokenYAccountAddress,
+        },
+        owner
+      );
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "minted for amounts too small to provide liquidity");
+
+    const amountX = new BN(1e6);
+    const amountY = new BN(2e6);
+    await protocol.mintLpTokenByAmounts(
+      {
+        pair,
+        invariant: market,
+
+        amountX,
+        amountY,
+
+        position: positionAddress,
+        accountX: userTokenXAccountAddress,
+        accountY: userTokenYAccountAddress,
+      },
+      owner
+    );
+
+    const getTokenAccount = async (tokenAccount: PublicKey) => {
+      return await getAccount(connection, tokenAccount, undefined);
+    };
+    const [tokenLp] = protocol.getLpTokenAddressAndBump(pair);
+    const accountLp = await getAccount(
+      connection,
+      getAssociatedTokenAddressSync(
+        tokenLp,
+        owner.publicKey,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assert.ok(accountLp.amount > 0n);
+
+    // the deposit is capped by the given amounts
+    const balanceX = (await getTokenAccount(userTokenXAccountAddress)).amount;
+    const balanceY = (await getTokenAccount(userTokenYAccountAddress)).amount;
+    const spentX = new BN(ownerAmount).sub(new BN(balanceX.toString()));
+    const spentY = new BN(ownerAmount).sub(new BN(balanceY.toString()));
+    assert.ok(spentX.gtn(0) && spentX.lte(amountX));
+    assert.ok(spentY.gtn(0) && spentY.lte(amountY));
+
+    const position = await market.getPosition(protocol.programAuthority, 0);
+    assert.ok(position.liquidity.v.gtn(0));
+  });
+});
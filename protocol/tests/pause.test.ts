@@ -0,0 +1,179 @@
+import { AnchorProvider, BN } from "@coral-xyz/anchor";
+import { Network } from "../sdk/src/network";
+import { Protocol } from "../sdk/src/protocol";
+import { Keypair } from "@solana/web3.js";
+import {
+  createTokenMint,
+  initMarket,
+  INVARIANT_ADDRESS,
+  requestAirdrop,
+} from "./test-utils";
+import { assert } from "chai";
+import {
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { Pair } from "@invariant-labs/sdk-eclipse";
+import {
+  fromFee,
+  getMaxTick,
+  getMinTick,
+} from "@invariant-labs/sdk-eclipse/lib/utils";
+import {
+  CreateTick,
+  FeeTier,
+  Market,
+} from "@invariant-labs/sdk-eclipse/lib/market";
+
+describe("pause and limits", () => {
+  const { wallet: walletAnchor, connection } = AnchorProvider.local();
+  const owner = Keypair.generate();
+  const mintAuthority = Keypair.generate();
+
+  let protocol: Protocol;
+  let market: Market;
+  const feeTier: FeeTier = {
+    fee: fromFee(new BN(600)),
+    tickSpacing: 10,
+  };
+  let pair: Pair;
+  const lowerTick = getMinTick(feeTier.tickSpacing!);
+  const upperTick = getMaxTick(feeTier.tickSpacing!);
+  const initTick = 0;
+  const ownerAmount = 1e10;
+
+  before(async () => {
+    let giveSOL = [owner.publicKey, mintAuthority.publicKey];
+    await Promise.all(
+      giveSOL.map((account) => requestAirdrop(connection, account, 1e14))
+    );
+
+    market = await Market.build(
+      Network.LOCAL,
+      walletAnchor,
+      connection,
+      INVARIANT_ADDRESS
+    );
+
+    const [token0, token1] = await Promise.all([
+      createTokenMint(connection, owner, mintAuthority.publicKey, 6),
+      createTokenMint(connection, owner, mintAuthority.publicKey, 6),
+    ]);
+    pair = new Pair(token0, token1, feeTier);
+
+    await initMarket(market, [pair], owner, initTick);
+
+    protocol = await Protocol.build(Network.LOCAL, walletAnchor, connection);
+    await protocol.init(owner, market);
+
+    const lowerTickVars: CreateTick = {
+      pair,
+      index: lowerTick,
+      payer: owner.publicKey,
+    };
+    const upperTickVars: CreateTick = {
+      pair,
+      index: upperTick,
+      payer: owner.publicKey,
+    };
+    await market.createTick(lowerTickVars, owner);
+    await market.createTick(upperTickVars, owner);
+
+    const userTokenXAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      owner,
+      pair.tokenX,
+      owner.publicKey
+    );
+    const userTokenYAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      owner,
+      pair.tokenY,
+      owner.publicKey
+    );
+    await mintTo(
+      connection,
+      owner,
+      pair.tokenX,
+      userTokenXAccount.address,
+      mintAuthority,
+      ownerAmount
+    );
+    await mintTo(
+      connection,
+      owner,
+      pair.tokenY,
+      userTokenYAccount.address,
+      mintAuthority,
+      ownerAmount
+    );
+
+    await protocol.initLpPool({ pair }, owner);
+  });
+
+  it("test", async () => {
+    const userTokenXAccountAddress = getAssociatedTokenAddressSync(
+      pair.tokenX,
+      owner.publicKey
+    );
+    const userTokenYAccountAddress = getAssociatedTokenAddressSync(
+      pair.tokenY,
+      owner.publicKey
+    );
+    const { positionAddress } = await market.getPositionAddress(
+      protocol.programAuthority,
+      0
+    );
+    const mintParams = {
+      pair,
+      invariant: market,
+
+      amountX: new BN(1e6),
+      amountY: new BN(1e6),
+
+      position: positionAddress,
+      accountX: userTokenXAccountAddress,
+      accountY: userTokenYAccountAddress,
+    };
+    const mintFails = async () => {
+      try {
+        await protocol.mintLpTokenByAmounts(mintParams, owner);
+      } catch (e) {
+        return true;
+      }
+      return false;
+    };
+
+    await protocol.setPaused(true, owner);
+    const state = await protocol.program.account.state.fetch(
+      protocol.stateAddress
+    );
+    assert.ok(state.paused);
+    assert(await mintFails(), "minted while the protocol is paused");
+    await protocol.setPaused(false, owner);
+
+    await protocol.setLpPoolLimits({ pair, paused: true }, owner);
+    assert.ok((await protocol.getLpPool(pair)).paused);
+    assert(await mintFails(), "minted while the lp pool is paused");
+    await protocol.setLpPoolLimits({ pair, paused: false }, owner);
+
+    await protocol.mintLpTokenByAmounts(mintParams, owner);
+
+    // cap at the current liquidity leaves no room for another deposit
+    const position = await market.getPosition(protocol.programAuthority, 0);
+    await protocol.setLpPoolLimits(
+      { pair, paused: false, liquidityCap: position.liquidity.v },
+      owner
+    );
+    assert(await mintFails(), "minted above the liquidity cap");
+
+    await protocol.setLpPoolLimits({ pair, paused: false }, owner);
+    await protocol.mintLpTokenByAmounts(mintParams, owner);
+    assert.ok(
+      (await market.getPosition(protocol.programAuthority, 0)).liquidity.v.gt(
+        position.liquidity.v
+      )
+    );
+  });
+});
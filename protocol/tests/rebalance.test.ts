@@ -0,0 +1,202 @@
+import { AnchorProvider, BN } from "@coral-xyz/anchor";
+import { Network } from "../sdk/src/network";
+import { Protocol } from "../sdk/src/protocol";
+import { computeRebalanceRange } from "../sdk/src/math";
+import { Keypair } from "@solana/web3.js";
+import {
+  createTokenMint,
+  initMarket,
+  INVARIANT_ADDRESS,
+  requestAirdrop,
+} from "./test-utils";
+import { assert } from "chai";
+import { getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import { Pair } from "@invariant-labs/sdk-eclipse";
+import {
+  fromFee,
+  getMaxTick,
+  getMinTick,
+} from "@invariant-labs/sdk-eclipse/lib/utils";
+import {
+  CreateTick,
+  FeeTier,
+  Market,
+} from "@invariant-labs/sdk-eclipse/lib/market";
+
+describe("rebalance", () => {
+  const { wallet: walletAnchor, connection } = AnchorProvider.local();
+  const owner = Keypair.generate();
+  const mintAuthority = Keypair.generate();
+  const keeper = Keypair.generate();
+
+  let protocol: Protocol;
+  let market: Market;
+  const feeTier: FeeTier = {
+    fee: fromFee(new BN(600)),
+    tickSpacing: 10,
+  };
+  let pair: Pair;
+  const lowerTick = getMinTick(feeTier.tickSpacing!);
+  const upperTick = getMaxTick(feeTier.tickSpacing!);
+  const initTick = 0;
+  const ownerAmount = 1e10;
+
+  before(async () => {
+    let giveSOL = [owner.publicKey, mintAuthority.publicKey, keeper.publicKey];
+    await Promise.all(
+      giveSOL.map((account) => requestAirdrop(connection, account, 1e14))
+    );
+
+    market = await Market.build(
+      Network.LOCAL,
+      walletAnchor,
+      connection,
+      INVARIANT_ADDRESS
+    );
+
+    const [token0, token1] = await Promise.all([
+      createTokenMint(connection, owner, mintAuthority.publicKey, 6),
+      createTokenMint(connection, owner, mintAuthority.publicKey, 6),
+    ]);
+    pair = new Pair(token0, token1, feeTier);
+
+    await initMarket(market, [pair], owner, initTick);
+
+    protocol = await Protocol.build(Network.LOCAL, walletAnchor, connection);
+    await protocol.init(owner, market);
+
+    const lowerTickVars: CreateTick = {
+      pair,
+      index: lowerTick,
+      payer: owner.publicKey,
+    };
+    const upperTickVars: CreateTick = {
+      pair,
+      index: upperTick,
+      payer: owner.publicKey,
+    };
+    await market.createTick(lowerTickVars, owner);
+    await market.createTick(upperTickVars, owner);
+
+    const userTokenXAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      owner,
+      pair.tokenX,
+      owner.publicKey
+    );
+    const userTokenYAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      owner,
+      pair.tokenY,
+      owner.publicKey
+    );
+    await mintTo(
+      connection,
+      owner,
+      pair.tokenX,
+      userTokenXAccount.address,
+      mintAuthority,
+      ownerAmount
+    );
+    await mintTo(
+      connection,
+      owner,
+      pair.tokenY,
+      userTokenYAccount.address,
+      mintAuthority,
+      ownerAmount
+    );
+
+    await protocol.initLpPool({ pair }, owner);
+  });
+
+  it("migrate", async () => {
+    let err = false;
+    try {
+      await protocol.migrateLpPool({ pair }, owner);
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "migrated an lp pool of the current version");
+  });
+
+  it("set rebalance config", async () => {
+    const config = {
+      pair,
+      keeper: keeper.publicKey,
+      rangeWidth: 200,
+      rebalanceCooldown: new BN(60),
+      maxTickDeviation: 50,
+      twapWindow: 300,
+    };
+
+    let err = false;
+    try {
+      await protocol.setRebalanceConfig({ ...config, rangeWidth: 205 }, owner);
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "set a range width not aligned to tick spacing");
+
+    err = false;
+    try {
+      await protocol.setRebalanceConfig({ ...config, twapWindow: 0 }, owner);
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "set an empty twap window");
+
+    err = false;
+    try {
+      await protocol.setRebalanceConfig(config, keeper);
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "non-admin set the rebalance config");
+
+    await protocol.setRebalanceConfig(config, owner);
+
+    const lpPool = await protocol.getLpPool(pair);
+    assert.ok(lpPool.keeper.equals(keeper.publicKey));
+    assert.equal(lpPool.rangeWidth, config.rangeWidth);
+    assert.ok(lpPool.rebalanceCooldown.eq(config.rebalanceCooldown));
+    assert.equal(lpPool.maxTickDeviation, config.maxTickDeviation);
+    assert.equal(lpPool.twapWindow, config.twapWindow);
+  });
+
+  it("rebalance by non-keeper", async () => {
+    const { positionAddress } = await market.getPositionAddress(
+      protocol.programAuthority,
+      0
+    );
+    const [lpPoolAddress] = protocol.getLpPoolAddressAndBump(pair);
+    const { lowerTickIndex, upperTickIndex } = computeRebalanceRange(
+      initTick,
+      feeTier.tickSpacing!,
+      200
+    );
+
+    let err = false;
+    try {
+      await protocol.rebalance(
+        {
+          pair,
+          invariant: market,
+
+          newLowerTickIndex: lowerTickIndex,
+          newUpperTickIndex: upperTickIndex,
+
+          lastPositionLpPool: lpPoolAddress,
+
+          position: positionAddress,
+          lastPosition: positionAddress,
+          newPosition: positionAddress,
+        },
+        owner
+      );
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "rebalanced by an account other than the keeper");
+  });
+});
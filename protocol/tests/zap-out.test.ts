@@ -0,0 +1,240 @@
+import { AnchorProvider, BN, IdlEvents } from "@coral-xyz/anchor";
+import { Network } from "../sdk/src/network";
+import { Protocol } from "../sdk/src/protocol";
+import { Protocol as ProtocolProgram } from "../sdk/src/idl/protocol";
+import { U64_MAX } from "../sdk/src/consts";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  createTokenMint,
+  initMarket,
+  INVARIANT_ADDRESS,
+  requestAirdrop,
+} from "./test-utils";
+import { assert } from "chai";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Pair } from "@invariant-labs/sdk-eclipse";
+import {
+  fromFee,
+  getMaxTick,
+  getMinTick,
+} from "@invariant-labs/sdk-eclipse/lib/utils";
+import {
+  CreateTick,
+  FeeTier,
+  Market,
+} from "@invariant-labs/sdk-eclipse/lib/market";
+
+describe("zap out", () => {
+  const { wallet: walletAnchor, connection } = AnchorProvider.local();
+  const owner = Keypair.generate();
+  const mintAuthority = Keypair.generate();
+
+  let protocol: Protocol;
+  let market: Market;
+  const feeTier: FeeTier = {
+    fee: fromFee(new BN(600)),
+    tickSpacing: 10,
+  };
+  let pair: Pair;
+  const lowerTick = getMinTick(feeTier.tickSpacing!);
+  const upperTick = getMaxTick(feeTier.tickSpacing!);
+  const initTick = 0;
+  const ownerAmount = 1e10;
+
+  before(async () => {
+    let giveSOL = [owner.publicKey, mintAuthority.publicKey];
+    await Promise.all(
+      giveSOL.map((account) => requestAirdrop(connection, account, 1e14))
+    );
+
+    market = await Market.build(
+      Network.LOCAL,
+      walletAnchor,
+      connection,
+      INVARIANT_ADDRESS
+    );
+
+    const [token0, token1] = await Promise.all([
+      createTokenMint(connection, owner, mintAuthority.publicKey, 6),
+      createTokenMint(connection, owner, mintAuthority.publicKey, 6),
+    ]);
+    pair = new Pair(token0, token1, feeTier);
+
+    await initMarket(market, [pair], owner, initTick);
+
+    protocol = await Protocol.build(Network.LOCAL, walletAnchor, connection);
+    await protocol.init(owner, market);
+
+    const lowerTickVars: CreateTick = {
+      pair,
+      index: lowerTick,
+      payer: owner.publicKey,
+    };
+    const upperTickVars: CreateTick = {
+      pair,
+      index: upperTick,
+      payer: owner.publicKey,
+    };
+    await market.createTick(lowerTickVars, owner);
+    await market.createTick(upperTickVars, owner);
+
+    const userTokenXAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      owner,
+      pair.tokenX,
+      owner.publicKey
+    );
+    const userTokenYAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      owner,
+      pair.tokenY,
+      owner.publicKey
+    );
+    await mintTo(
+      connection,
+      owner,
+      pair.tokenX,
+      userTokenXAccount.address,
+      mintAuthority,
+      ownerAmount
+    );
+    await mintTo(
+      connection,
+      owner,
+      pair.tokenY,
+      userTokenYAccount.address,
+      mintAuthority,
+      ownerAmount
+    );
+
+    await protocol.initLpPool({ pair }, owner);
+  });
+
+  it("test", async () => {
+    const userTokenXAccountAddress = getAssociatedTokenAddressSync(
+      pair.tokenX,
+      owner.publicKey
+    );
+    const userTokenYAccountAddress = getAssociatedTokenAddressSync(
+      pair.tokenY,
+      owner.publicKey
+    );
+    const { positionAddress } = await market.getPositionAddress(
+      protocol.programAuthority,
+      0
+    );
+    const [tokenLp] = protocol.getLpTokenAddressAndBump(pair);
+    const accountLpAddress = getAssociatedTokenAddressSync(
+      tokenLp,
+      owner.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const getTokenAccount = async (tokenAccount: PublicKey) => {
+      return await getAccount(connection, tokenAccount, undefined);
+    };
+    const getLpAmount = async () => {
+      return (
+        await getAccount(
+          connection,
+          accountLpAddress,
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        )
+      ).amount;
+    };
+
+    await protocol.mintLpTokenByAmounts(
+      {
+        pair,
+        invariant: market,
+
+        amountX: new BN(1e8),
+        amountY: new BN(1e8),
+
+        position: positionAddress,
+        accountX: userTokenXAccountAddress,
+        accountY: userTokenYAccountAddress,
+      },
+      owner
+    );
+    const lpAmountBefore = await getLpAmount();
+    const balanceXBefore = (await getTokenAccount(userTokenXAccountAddress))
+      .amount;
+    const balanceYBefore = (await getTokenAccount(userTokenYAccountAddress))
+      .amount;
+
+    const position = await market.getPosition(protocol.programAuthority, 0);
+    const [lastPositionLpPool] = protocol.getLpPoolAddressAndBump(pair);
+    const zapOutParams = {
+      pair,
+      invariant: market,
+
+      liquidityDelta: position.liquidity.v.divn(2),
+      xToY: true,
+
+      lastPositionLpPool,
+
+      position: positionAddress,
+      lastPosition: positionAddress,
+      accountX: userTokenXAccountAddress,
+      accountY: userTokenYAccountAddress,
+    };
+
+    let err = false;
+    try {
+      await protocol.zapOut(
+        { ...zapOutParams, minAmountOut: new BN(U64_MAX) },
+        owner
+      );
+    } catch (e) {
+      err = true;
+    }
+    assert(err, "zapped out below the minimal amount");
+
+    const signature = await protocol.zapOut(zapOutParams, owner);
+
+    const lpAmountAfter = await getLpAmount();
+    assert.ok(lpAmountAfter < lpAmountBefore);
+    // the withdrawn x is swapped, so all of the output is in y
+    assert.equal(
+      (await getTokenAccount(userTokenXAccountAddress)).amount,
+      balanceXBefore
+    );
+    const balanceYAfter = (await getTokenAccount(userTokenYAccountAddress))
+      .amount;
+    assert.ok(balanceYAfter > balanceYBefore);
+
+    // the withdrawal is reported along with the swap made after it
+    const events = await protocol.getEvents(signature);
+    assert.deepEqual(
+      events.map((event) => event.name),
+      ["BurnLpTokenEvent", "ZapOutEvent"]
+    );
+    const burnEvent = events[0]
+      .data as IdlEvents<ProtocolProgram>["BurnLpTokenEvent"];
+    const zapEvent = events[1]
+      .data as IdlEvents<ProtocolProgram>["ZapOutEvent"];
+    assert.ok(zapEvent.xToY);
+    assert.ok(zapEvent.lpTokenAmount.eq(burnEvent.lpTokenAmount));
+    assert.equal(
+      zapEvent.lpTokenAmount.toString(),
+      (lpAmountBefore - lpAmountAfter).toString()
+    );
+    assert.ok(zapEvent.swapAmountIn.eq(burnEvent.amountX));
+    assert.ok(zapEvent.swapAmountOut.gtn(0));
+    assert.equal(
+      zapEvent.amountOut.toString(),
+      (balanceYAfter - balanceYBefore).toString()
+    );
+    assert.ok(
+      zapEvent.amountOut.eq(burnEvent.amountY.add(zapEvent.swapAmountOut))
+    );
+  });
+});